@@ -0,0 +1,721 @@
+//! # Catalyst preparation
+//!
+//! This module calculates the amounts to weigh out for a supported catalyst such as `Pt5wt%/SiO2`.
+//!
+//! The wt% terms of the target formula are the loaded phase and the stoichiometric part is the support,
+//! e.g. `Pt5wt%/SiO2` or `(NiO)10wt%/Al2O3`. `ImpregnationRecipe` takes a `Precursor` for each active element,
+//! e.g. `H2PtCl6·6H2O` for Pt, with an optional assay purity or solution concentration,
+//! and `calculate` gives the precursor and support masses for a batch.
+//! `incipient_wetness` plans the solution for incipient wetness impregnation from the pore volume of the support,
+//! and splits it into several impregnation steps if the precursor solubility is too low.
+//!
+//! `ChemicalFormula` also provides the loading of each element of the loaded phase in mmol/g
+//! with `loading_mmol_per_g()` and `loading_mmol_per_g_support()`, and the surface density in atoms/nm²
+//! from the BET surface area of the support with `surface_density()`.
+//!
+//! # Example
+//! ```
+//! use chemical_formula::prelude::*;
+//! use approx::assert_abs_diff_eq;
+//!
+//! let mut recipe = ImpregnationRecipe::new(parse_formula("Pt5wt%/SiO2").unwrap());
+//! recipe.precursor(
+//!     ElementSymbol::Pt,
+//!     Precursor::new(parse_formula("H2PtCl6·6H2O").unwrap()),
+//! );
+//!
+//! let amounts = recipe.calculate(10.0).unwrap();
+//!
+//! assert_abs_diff_eq!(amounts.support_mass, 9.5, epsilon = 1e-9);
+//! // 0.5 g of Pt is 2.56 mmol of H2PtCl6·6H2O
+//! assert_abs_diff_eq!(amounts.precursors[0].mass, 0.5 / 195.08 * 517.9, epsilon = 1e-3);
+//! ```
+
+use crate::element::{ChemicalFormula, ElementSymbol, FormulaError};
+use std::collections::BTreeMap;
+
+/// Avogadro constant in 1/mol
+///
+/// # Reference
+/// [CODATA 2018](https://physics.nist.gov/cgi-bin/cuu/Value?na)
+pub const AVOGADRO_CONSTANT: f64 = 6.02214076e23;
+
+/// Concentration of a precursor solution
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SolutionConcentration {
+    /// mol of precursor per L
+    Molar(f64),
+    /// g of the active element per L, e.g. a Pt standard solution of 10 g/L
+    ElementGramsPerLiter(f64),
+    /// wt% of precursor in the solution
+    WeightPercent(f64),
+}
+
+/// Precursor of an active element
+///
+/// The purity is the assay in wt% and defaults to 100.
+/// The solubility is the maximum concentration in g of precursor per mL of solution.
+#[derive(Debug, Clone)]
+pub struct Precursor {
+    pub formula: ChemicalFormula,
+    pub purity: f64,
+    pub concentration: Option<SolutionConcentration>,
+    pub solubility: Option<f64>,
+}
+
+impl Precursor {
+    /// Create a pure solid precursor
+    pub fn new(formula: ChemicalFormula) -> Self {
+        Precursor {
+            formula,
+            purity: 100.,
+            concentration: None,
+            solubility: None,
+        }
+    }
+
+    /// Set the assay purity in wt%
+    pub fn purity(&mut self, purity: f64) -> &mut Self {
+        self.purity = purity;
+        self
+    }
+
+    /// Set the concentration of the precursor solution
+    pub fn concentration(&mut self, concentration: SolutionConcentration) -> &mut Self {
+        self.concentration = Some(concentration);
+        self
+    }
+
+    /// Set the solubility in g of precursor per mL of solution
+    pub fn solubility(&mut self, solubility: f64) -> &mut Self {
+        self.solubility = Some(solubility);
+        self
+    }
+
+    /// Check that the purity, concentration and solubility are positive
+    fn check_positive(&self) -> Result<(), FormulaError> {
+        if self.purity <= 0. {
+            return Err(FormulaError::NotPositive("purity"));
+        }
+
+        let concentration = match self.concentration {
+            Some(SolutionConcentration::Molar(value))
+            | Some(SolutionConcentration::ElementGramsPerLiter(value))
+            | Some(SolutionConcentration::WeightPercent(value)) => value,
+            None => 1.,
+        };
+        if concentration <= 0. {
+            return Err(FormulaError::NotPositive("concentration"));
+        }
+
+        if self.solubility.is_some_and(|solubility| solubility <= 0.) {
+            return Err(FormulaError::NotPositive("solubility"));
+        }
+
+        Ok(())
+    }
+}
+
+/// Amount of a precursor calculated by `ImpregnationRecipe::calculate`
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrecursorAmount {
+    pub element: ElementSymbol,
+    /// Mass of the active element in g
+    pub element_mass: f64,
+    pub moles: f64,
+    /// Mass of the precursor in g, including the impurities given by the purity
+    pub mass: f64,
+    /// Volume of the precursor solution in mL, if the concentration is given by volume
+    pub solution_volume: Option<f64>,
+    /// Mass of the precursor solution in g, if the concentration is given by wt%
+    pub solution_mass: Option<f64>,
+}
+
+/// Amounts to weigh out for a batch, returned by `ImpregnationRecipe::calculate`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImpregnationAmounts {
+    /// Mass of the final catalyst in g
+    pub batch_mass: f64,
+    /// In the order the precursors were added
+    pub precursors: Vec<PrecursorAmount>,
+    /// Mass of the support in g
+    pub support_mass: f64,
+}
+
+/// Solution of a precursor for incipient wetness impregnation
+///
+/// The concentrations are for a single impregnation. With several steps, each step uses
+/// the concentrations divided by the number of steps of the plan.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrecursorSolution {
+    pub element: ElementSymbol,
+    /// mol of precursor per L
+    pub molarity: f64,
+    /// g of precursor per mL
+    pub grams_per_ml: f64,
+    /// Number of steps needed to stay within the solubility, 1 if the solubility is not given
+    pub steps: usize,
+}
+
+/// Plan of an incipient wetness impregnation, returned by `ImpregnationRecipe::incipient_wetness`
+#[derive(Debug, Clone, PartialEq)]
+pub struct IncipientWetnessPlan {
+    pub amounts: ImpregnationAmounts,
+    /// Volume of solution that fills the pores of the support in mL, used in every step
+    pub solution_volume: f64,
+    /// In the order the precursors were added
+    pub solutions: Vec<PrecursorSolution>,
+    /// Number of impregnation steps, the largest number of steps of the solutions
+    pub steps: usize,
+}
+
+impl IncipientWetnessPlan {
+    /// Whether the solubility of a precursor forces more than one impregnation step
+    pub fn is_multi_step(&self) -> bool {
+        self.steps > 1
+    }
+}
+
+/// Recipe of a supported catalyst prepared by impregnation
+///
+/// Every element of the loaded phase needs a precursor, except O, e.g. of `(NiO)10wt%/Al2O3`,
+/// which is assumed to come from the calcination atmosphere.
+///
+/// # Example
+/// ```
+/// use chemical_formula::prelude::*;
+/// use approx::assert_abs_diff_eq;
+///
+/// let mut recipe = ImpregnationRecipe::new(parse_formula("Pt1wt%Sn1wt%/Al2O3").unwrap());
+///
+/// let mut pt = Precursor::new(parse_formula("Pt(NH3)4(NO3)2").unwrap());
+/// pt.concentration(SolutionConcentration::ElementGramsPerLiter(10.0));
+/// let mut sn = Precursor::new(parse_formula("SnCl2·2H2O").unwrap());
+/// sn.purity(98.0);
+///
+/// recipe.precursor(ElementSymbol::Pt, pt).precursor(ElementSymbol::Sn, sn);
+/// let amounts = recipe.calculate(5.0).unwrap();
+///
+/// // 50 mg of Pt from a 10 g/L solution
+/// assert_abs_diff_eq!(amounts.precursors[0].solution_volume.unwrap(), 5.0, epsilon = 1e-9);
+/// assert_abs_diff_eq!(amounts.support_mass, 4.9, epsilon = 1e-9);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ImpregnationRecipe {
+    pub target: ChemicalFormula,
+    pub precursors: Vec<(ElementSymbol, Precursor)>,
+}
+
+impl ImpregnationRecipe {
+    pub fn new(target: ChemicalFormula) -> Self {
+        ImpregnationRecipe {
+            target,
+            precursors: Vec::new(),
+        }
+    }
+
+    /// Set the precursor of an active element
+    pub fn precursor(&mut self, element: ElementSymbol, precursor: Precursor) -> &mut Self {
+        match self.precursors.iter_mut().find(|(e, _)| *e == element) {
+            Some((_, old_precursor)) => *old_precursor = precursor,
+            None => self.precursors.push((element, precursor)),
+        }
+        self
+    }
+
+    /// Calculate the precursor and support masses for a batch of `batch_mass` g of catalyst
+    ///
+    /// Returns `FormulaError::NotPositive` if `batch_mass`, or the purity, concentration or solubility of a precursor is not positive,
+    /// `FormulaError::ElementNotLoaded` if a precursor is given for an element that is not in the loaded phase,
+    /// `FormulaError::NoPrecursor` if an element of the loaded phase other than O has no precursor,
+    /// and `FormulaError::ElementNotInPrecursor` if the precursor does not contain its element.
+    pub fn calculate(&self, batch_mass: f64) -> Result<ImpregnationAmounts, FormulaError> {
+        if batch_mass <= 0. {
+            return Err(FormulaError::NotPositive("batch_mass"));
+        }
+        for (_, precursor) in self.precursors.iter() {
+            precursor.check_positive()?;
+        }
+
+        let loading = self.loading()?;
+        if let Some((element, _)) = self
+            .precursors
+            .iter()
+            .find(|(element, _)| !loading.contains_key(element))
+        {
+            return Err(FormulaError::ElementNotLoaded(*element));
+        }
+        if let Some(element) = loading.keys().find(|element| {
+            **element != ElementSymbol::O && !self.precursors.iter().any(|(e, _)| e == *element)
+        }) {
+            return Err(FormulaError::NoPrecursor(*element));
+        }
+
+        let precursors = self
+            .precursors
+            .iter()
+            .map(|(element, precursor)| {
+                precursor_amount(*element, precursor, batch_mass * loading[element] / 100.)
+            })
+            .collect::<Result<Vec<_>, FormulaError>>()?;
+
+        Ok(ImpregnationAmounts {
+            batch_mass,
+            precursors,
+            support_mass: batch_mass * (100. - loading.values().sum::<f64>()) / 100.,
+        })
+    }
+
+    /// Plan an incipient wetness impregnation of a batch
+    ///
+    /// `pore_volume` is the pore volume of the support in mL/g, so the solution volume is
+    /// the support mass times the pore volume. All precursors are dissolved in the same solution.
+    ///
+    /// Returns `FormulaError::NotPositive` if `pore_volume` or the support mass is not positive, besides the errors of `calculate`.
+    ///
+    /// # Example
+    /// ```
+    /// use chemical_formula::prelude::*;
+    /// use approx::assert_abs_diff_eq;
+    ///
+    /// let mut recipe = ImpregnationRecipe::new(parse_formula("Pt1wt%/Al2O3").unwrap());
+    /// let mut precursor = Precursor::new(parse_formula("Pt(NH3)4(NO3)2").unwrap());
+    /// precursor.solubility(0.01);
+    /// recipe.precursor(ElementSymbol::Pt, precursor);
+    ///
+    /// let plan = recipe.incipient_wetness(10.0, 0.5).unwrap();
+    ///
+    /// assert_abs_diff_eq!(plan.solution_volume, 4.95, epsilon = 1e-9);
+    /// // 0.1 g of Pt is 0.198 g of Pt(NH3)4(NO3)2, or 0.040 g/mL in 4.95 mL
+    /// assert_abs_diff_eq!(plan.solutions[0].grams_per_ml, 0.0401, epsilon = 1e-4);
+    /// assert_eq!(plan.steps, 5);
+    /// assert!(plan.is_multi_step());
+    /// ```
+    pub fn incipient_wetness(
+        &self,
+        batch_mass: f64,
+        pore_volume: f64,
+    ) -> Result<IncipientWetnessPlan, FormulaError> {
+        if pore_volume <= 0. {
+            return Err(FormulaError::NotPositive("pore_volume"));
+        }
+
+        let amounts = self.calculate(batch_mass)?;
+        if amounts.support_mass <= 0. {
+            return Err(FormulaError::NotPositive("support_mass"));
+        }
+        let solution_volume = amounts.support_mass * pore_volume;
+
+        let solutions = amounts
+            .precursors
+            .iter()
+            .zip(self.precursors.iter())
+            .map(|(amount, (_, precursor))| {
+                let grams_per_ml =
+                    amount.moles * precursor.formula.molecular_weight()? / solution_volume;
+                let steps = match precursor.solubility {
+                    Some(solubility) => (grams_per_ml / solubility).ceil().max(1.) as usize,
+                    None => 1,
+                };
+
+                Ok(PrecursorSolution {
+                    element: amount.element,
+                    molarity: amount.moles / solution_volume * 1000.,
+                    grams_per_ml,
+                    steps,
+                })
+            })
+            .collect::<Result<Vec<_>, FormulaError>>()?;
+
+        Ok(IncipientWetnessPlan {
+            steps: solutions
+                .iter()
+                .map(|solution| solution.steps)
+                .max()
+                .unwrap_or(1),
+            amounts,
+            solution_volume,
+            solutions,
+        })
+    }
+
+    /// wt% of each element of the loaded phase in the final catalyst
+    ///
+    /// This is `ChemicalFormula::loading_wt_percent` of the target.
+    pub fn loading(&self) -> Result<BTreeMap<ElementSymbol, f64>, FormulaError> {
+        self.target.loading_wt_percent()
+    }
+}
+
+impl ChemicalFormula {
+    /// wt% of each element of the loaded phase in the whole catalyst
+    ///
+    /// The loaded phase is given by the wt% and at% terms, and at% terms are converted to wt%.
+    ///
+    /// # Example
+    /// ```
+    /// use chemical_formula::prelude::*;
+    ///
+    /// let formula = parse_formula("Pt1wt%/Al2O3").unwrap();
+    /// assert_eq!(formula.loading_wt_percent().unwrap()[&ElementSymbol::Pt], 1.0);
+    /// ```
+    pub fn loading_wt_percent(&self) -> Result<BTreeMap<ElementSymbol, f64>, FormulaError> {
+        let mut loading = self.wt_percent.clone();
+
+        if !self.at_percent.is_empty() {
+            let formula = self.to_molecular_formula()?;
+            let molecular_weight = formula.molecular_weight()?;
+
+            for element in self.at_percent.keys() {
+                let stoichiometry = formula.stoichiometry.get(element).copied().unwrap_or(0.);
+                *loading.entry(*element).or_insert(0.) +=
+                    stoichiometry * element.atomic_weight() / molecular_weight * 100.;
+            }
+        }
+
+        if loading.values().sum::<f64>() > 100. {
+            return Err(FormulaError::WeightPercentOverflow);
+        }
+
+        Ok(loading)
+    }
+
+    /// Loading of each element of the loaded phase in mmol per g of catalyst
+    ///
+    /// # Example
+    /// ```
+    /// use chemical_formula::prelude::*;
+    /// use approx::assert_abs_diff_eq;
+    ///
+    /// let formula = parse_formula("Pt1wt%/Al2O3").unwrap();
+    /// let loading = formula.loading_mmol_per_g().unwrap();
+    ///
+    /// // 10 mg of Pt per g
+    /// assert_abs_diff_eq!(loading[&ElementSymbol::Pt], 10. / 195.084, epsilon = 1e-9);
+    /// ```
+    pub fn loading_mmol_per_g(&self) -> Result<BTreeMap<ElementSymbol, f64>, FormulaError> {
+        Ok(self
+            .loading_wt_percent()?
+            .into_iter()
+            .map(|(element, wt_ratio)| (element, wt_ratio * 10. / element.atomic_weight()))
+            .collect())
+    }
+
+    /// Loading of each element of the loaded phase in mmol per g of support
+    ///
+    /// Returns `FormulaError::WeightPercentOverflow` if the loaded phase leaves no support.
+    ///
+    /// # Example
+    /// ```
+    /// use chemical_formula::prelude::*;
+    /// use approx::assert_abs_diff_eq;
+    ///
+    /// let formula = parse_formula("Pt1wt%/Al2O3").unwrap();
+    /// let loading = formula.loading_mmol_per_g_support().unwrap();
+    ///
+    /// // 10 mg of Pt per 990 mg of Al2O3
+    /// assert_abs_diff_eq!(loading[&ElementSymbol::Pt], 10. / 195.084 / 0.99, epsilon = 1e-9);
+    /// ```
+    pub fn loading_mmol_per_g_support(&self) -> Result<BTreeMap<ElementSymbol, f64>, FormulaError> {
+        let loading = self.loading_wt_percent()?;
+        let support_ratio = (100. - loading.values().sum::<f64>()) / 100.;
+
+        if support_ratio <= 0. {
+            return Err(FormulaError::WeightPercentOverflow);
+        }
+
+        Ok(loading
+            .into_iter()
+            .map(|(element, wt_ratio)| {
+                (
+                    element,
+                    wt_ratio * 10. / element.atomic_weight() / support_ratio,
+                )
+            })
+            .collect())
+    }
+
+    /// Surface density of each element of the loaded phase in atoms/nm²
+    ///
+    /// `bet_area` is the BET surface area of the support in m²/g.
+    ///
+    /// # Example
+    /// ```
+    /// use chemical_formula::prelude::*;
+    /// use approx::assert_abs_diff_eq;
+    ///
+    /// let formula = parse_formula("(V2O5)5wt%/TiO2").unwrap();
+    /// let density = formula.surface_density(50.0).unwrap();
+    ///
+    /// // 5 wt% V2O5 on 50 m²/g TiO2 is about 7 V atoms/nm²
+    /// assert_abs_diff_eq!(density[&ElementSymbol::V], 6.97, epsilon = 1e-2);
+    /// ```
+    pub fn surface_density(
+        &self,
+        bet_area: f64,
+    ) -> Result<BTreeMap<ElementSymbol, f64>, FormulaError> {
+        Ok(self
+            .loading_mmol_per_g_support()?
+            .into_iter()
+            .map(|(element, loading)| {
+                (
+                    element,
+                    loading * 1e-3 * AVOGADRO_CONSTANT / (bet_area * 1e18),
+                )
+            })
+            .collect())
+    }
+}
+
+fn precursor_amount(
+    element: ElementSymbol,
+    precursor: &Precursor,
+    element_mass: f64,
+) -> Result<PrecursorAmount, FormulaError> {
+    let formula = precursor.formula.to_molecular_formula()?;
+    let atoms = formula
+        .stoichiometry
+        .get(&element)
+        .copied()
+        .filter(|atoms| *atoms > 0.)
+        .ok_or(FormulaError::ElementNotInPrecursor(element))?;
+
+    let moles = element_mass / element.atomic_weight() / atoms;
+    let pure_mass = moles * formula.molecular_weight()?;
+
+    let (solution_volume, solution_mass) = match precursor.concentration {
+        None => (None, None),
+        Some(SolutionConcentration::Molar(molarity)) => (Some(moles / molarity * 1000.), None),
+        Some(SolutionConcentration::ElementGramsPerLiter(concentration)) => {
+            (Some(element_mass / concentration * 1000.), None)
+        }
+        Some(SolutionConcentration::WeightPercent(wt_ratio)) => {
+            (None, Some(pure_mass / wt_ratio * 100.))
+        }
+    };
+
+    Ok(PrecursorAmount {
+        element,
+        element_mass,
+        moles,
+        mass: pure_mass / precursor.purity * 100.,
+        solution_volume,
+        solution_mass,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_formula;
+    use approx::assert_abs_diff_eq;
+
+    #[test]
+    fn test_impregnation_recipe() {
+        let mut recipe = ImpregnationRecipe::new(parse_formula("(NiO)10wt%/Al2O3").unwrap());
+        let mut precursor = Precursor::new(parse_formula("Ni(NO3)2·6H2O").unwrap());
+        precursor
+            .purity(98.0)
+            .concentration(SolutionConcentration::Molar(1.0));
+        recipe.precursor(ElementSymbol::Ni, precursor);
+
+        let amounts = recipe.calculate(20.0).unwrap();
+        let nio = parse_formula("NiO").unwrap().molecular_weight().unwrap();
+        let nitrate = parse_formula("Ni(NO3)2·6H2O")
+            .unwrap()
+            .molecular_weight()
+            .unwrap();
+
+        let precursor = &amounts.precursors[0];
+        assert_abs_diff_eq!(precursor.moles, 2.0 / nio, epsilon = 1e-12);
+        assert_abs_diff_eq!(precursor.mass, 2.0 / nio * nitrate / 0.98, epsilon = 1e-9);
+        assert_abs_diff_eq!(
+            precursor.solution_volume.unwrap(),
+            2.0 / nio * 1000.,
+            epsilon = 1e-9
+        );
+        assert_abs_diff_eq!(amounts.support_mass, 18.0, epsilon = 1e-9);
+
+        let mut recipe = ImpregnationRecipe::new(parse_formula("Pt2at%/Al2O3").unwrap());
+        recipe.precursor(
+            ElementSymbol::Pt,
+            Precursor::new(parse_formula("H2PtCl6").unwrap()),
+        );
+        let loading = recipe.loading().unwrap();
+        let expected = parse_formula("Pt2at%/Al2O3")
+            .unwrap()
+            .to_wt_percent()
+            .unwrap()
+            .wt_percent[&ElementSymbol::Pt];
+        assert_abs_diff_eq!(loading[&ElementSymbol::Pt], expected, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn test_incipient_wetness() {
+        let mut recipe = ImpregnationRecipe::new(parse_formula("Pt1wt%Sn2wt%/Al2O3").unwrap());
+        let mut pt = Precursor::new(parse_formula("H2PtCl6·6H2O").unwrap());
+        pt.solubility(1.0);
+        let mut sn = Precursor::new(parse_formula("SnCl4·5H2O").unwrap());
+        sn.solubility(0.05);
+        recipe
+            .precursor(ElementSymbol::Pt, pt)
+            .precursor(ElementSymbol::Sn, sn);
+
+        let plan = recipe.incipient_wetness(5.0, 0.6).unwrap();
+        assert_abs_diff_eq!(plan.solution_volume, 4.85 * 0.6, epsilon = 1e-9);
+
+        let sn_moles = 0.1 / ElementSymbol::Sn.atomic_weight();
+        let sn_weight = parse_formula("SnCl4·5H2O")
+            .unwrap()
+            .molecular_weight()
+            .unwrap();
+        assert_abs_diff_eq!(
+            plan.solutions[1].molarity,
+            sn_moles / (4.85 * 0.6) * 1000.,
+            epsilon = 1e-9
+        );
+        assert_abs_diff_eq!(
+            plan.solutions[1].grams_per_ml,
+            sn_moles * sn_weight / (4.85 * 0.6),
+            epsilon = 1e-9
+        );
+
+        // 0.12 g/mL of SnCl4·5H2O needs three steps at 0.05 g/mL
+        assert_eq!(plan.solutions[0].steps, 1);
+        assert_eq!(plan.solutions[1].steps, 3);
+        assert_eq!(plan.steps, 3);
+    }
+
+    #[test]
+    fn test_loading() {
+        let formula = parse_formula("Pt1wt%Sn1at%/Al2O3").unwrap();
+        let wt_percent = formula.to_wt_percent().unwrap().wt_percent;
+        let loading = formula.loading_mmol_per_g().unwrap();
+
+        assert_eq!(loading.len(), 2);
+        assert_abs_diff_eq!(
+            loading[&ElementSymbol::Sn],
+            wt_percent[&ElementSymbol::Sn] * 10. / ElementSymbol::Sn.atomic_weight(),
+            epsilon = 1e-9
+        );
+
+        let support_ratio =
+            (100. - wt_percent[&ElementSymbol::Pt] - wt_percent[&ElementSymbol::Sn]) / 100.;
+        let support_loading = formula.loading_mmol_per_g_support().unwrap();
+        assert_abs_diff_eq!(
+            support_loading[&ElementSymbol::Pt],
+            loading[&ElementSymbol::Pt] / support_ratio,
+            epsilon = 1e-9
+        );
+
+        // 1 mmol/g on 602.2 m²/g is 1 atom/nm²
+        let formula = parse_formula("Pt1wt%/Al2O3").unwrap();
+        let density = formula.surface_density(602.214076).unwrap();
+        assert_abs_diff_eq!(
+            density[&ElementSymbol::Pt],
+            formula.loading_mmol_per_g_support().unwrap()[&ElementSymbol::Pt],
+            epsilon = 1e-9
+        );
+
+        assert!(parse_formula("Pt")
+            .unwrap()
+            .loading_mmol_per_g()
+            .unwrap()
+            .is_empty());
+        assert!(parse_formula("(Pt100wt%)")
+            .unwrap()
+            .loading_mmol_per_g_support()
+            .is_err());
+    }
+
+    #[test]
+    fn test_impregnation_recipe_errors() {
+        let mut recipe = ImpregnationRecipe::new(parse_formula("Pt5wt%/SiO2").unwrap());
+        recipe.precursor(
+            ElementSymbol::Pd,
+            Precursor::new(parse_formula("Pd(NO3)2").unwrap()),
+        );
+        assert!(matches!(
+            recipe.calculate(1.0),
+            Err(FormulaError::ElementNotLoaded(ElementSymbol::Pd))
+        ));
+
+        let mut recipe = ImpregnationRecipe::new(parse_formula("Pt5wt%/SiO2").unwrap());
+        recipe.precursor(
+            ElementSymbol::Pt,
+            Precursor::new(parse_formula("Pd(NO3)2").unwrap()),
+        );
+        assert!(matches!(
+            recipe.calculate(1.0),
+            Err(FormulaError::ElementNotInPrecursor(ElementSymbol::Pt))
+        ));
+
+        let mut recipe = ImpregnationRecipe::new(parse_formula("Pt1wt%/Al2O3").unwrap());
+        let mut precursor = Precursor::new(parse_formula("Pt(NH3)4(NO3)2").unwrap());
+        precursor.solubility(0.01);
+        recipe.precursor(ElementSymbol::Pt, precursor.clone());
+
+        for (batch_mass, pore_volume, name) in [
+            (1.0, 0.0, "pore_volume"),
+            (1.0, -0.5, "pore_volume"),
+            (0.0, 0.5, "batch_mass"),
+        ] {
+            assert!(matches!(
+                recipe.incipient_wetness(batch_mass, pore_volume),
+                Err(FormulaError::NotPositive(n)) if n == name
+            ));
+        }
+
+        precursor.solubility(0.0);
+        recipe.precursor(ElementSymbol::Pt, precursor);
+        assert!(matches!(
+            recipe.incipient_wetness(1.0, 0.5),
+            Err(FormulaError::NotPositive("solubility"))
+        ));
+
+        // Sn has no precursor
+        let mut recipe = ImpregnationRecipe::new(parse_formula("Pt1wt%Sn1wt%/Al2O3").unwrap());
+        recipe.precursor(
+            ElementSymbol::Pt,
+            Precursor::new(parse_formula("H2PtCl6").unwrap()),
+        );
+        assert!(matches!(
+            recipe.calculate(1.0),
+            Err(FormulaError::NoPrecursor(ElementSymbol::Sn))
+        ));
+
+        let mut recipe = ImpregnationRecipe::new(parse_formula("Pt1wt%/Al2O3").unwrap());
+        recipe.precursor(
+            ElementSymbol::Pt,
+            Precursor::new(parse_formula("H2PtCl6").unwrap()),
+        );
+        for batch_mass in [0.0, -1.0] {
+            assert!(matches!(
+                recipe.calculate(batch_mass),
+                Err(FormulaError::NotPositive("batch_mass"))
+            ));
+        }
+
+        let mut precursor = Precursor::new(parse_formula("H2PtCl6").unwrap());
+        precursor.purity(0.0);
+        recipe.precursor(ElementSymbol::Pt, precursor);
+        assert!(matches!(
+            recipe.calculate(1.0),
+            Err(FormulaError::NotPositive("purity"))
+        ));
+
+        for concentration in [
+            SolutionConcentration::Molar(0.0),
+            SolutionConcentration::ElementGramsPerLiter(-10.0),
+            SolutionConcentration::WeightPercent(0.0),
+        ] {
+            let mut precursor = Precursor::new(parse_formula("H2PtCl6").unwrap());
+            precursor.concentration(concentration);
+            recipe.precursor(ElementSymbol::Pt, precursor);
+            assert!(matches!(
+                recipe.calculate(1.0),
+                Err(FormulaError::NotPositive("concentration"))
+            ));
+        }
+    }
+}
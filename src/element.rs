@@ -3,7 +3,7 @@
 //!
 //! There is a None variant for the case where the element to express is not in the periodic table.
 //!
-//! Simple api to obtain a `ElementSymbol` from `&str` is provided as `.from_str()`.;
+//! `ElementSymbol` implements `FromStr` and `TryFrom<&str>`. Unknown symbols are rejected with an `UnknownElement` error.
 //!
 //! Simple api to retreve the atomic weight of the element is provided as `.atomic_weight()`.
 //!
@@ -12,9 +12,12 @@
 //! use chemical_formula::prelude::*;
 //! use approx::assert_abs_diff_eq;
 //!
-//! let o = ElementSymbol::from_str("O");
+//! let o: ElementSymbol = "O".parse().unwrap();
 //! assert_eq!(o, ElementSymbol::O);
 //!
+//! let err = ElementSymbol::try_from("CL").unwrap_err();
+//! assert_eq!(err.suggestions, vec![ElementSymbol::Cl]);
+//!
 //! let h = ElementSymbol::H;
 //! approx::assert_abs_diff_eq!(h.atomic_weight(), 1.008, epsilon = 1e-6);
 //! ```
@@ -23,21 +26,34 @@
 //!
 //! The `ChemicalFormula` struct is used to represent a chemical formula.
 //! The `ChemicalFormula` struct has the following fields:
-//! * `element` - A `HashSet` of `ElementSymbol` enums.
-//! * `stoichiometry` - A `HashMap` of `ElementSymbol` enums and the stoichiometry.
-//! * `wt_percent` - A `HashMap` of `ElementSymbol` enums and the wt%.
+//! * `element` - A `Vec` of `ElementSymbol` enums in the order they were first added.
+//! * `stoichiometry` - A `BTreeMap` of `ElementSymbol` enums and the stoichiometry, ordered by atomic number.
+//! * `wt_percent` - A `BTreeMap` of `ElementSymbol` enums and the wt%, ordered by atomic number.
+//! * `at_percent` - A `BTreeMap` of `ElementSymbol` enums and the at%, ordered by atomic number.
+//! * `isotope` - A `BTreeMap` of the isotope-labelled part of the stoichiometry.
+//! * `charge` - The net charge of the formula.
 //!
 //! The `ChemicalFormula` struct has the following methods:
 //! * `add_element` - Add an element to the formula.
 //! * `add_wt_percent` - Add an element to the formula by wt%.
+//! * `add_ppm` - Add an element to the formula by mass ppm, which is stored as wt%.
+//! * `add_at_percent` - Add an element to the formula by at%.
+//! * `add_isotope` - Add an isotope to the formula.
 //! * `multiply` - Multiply the stoichiometry and wt% by a multiplier.
 //! * `to_molecular_formula` - Convert the formula to molecular formula.
 //! * `to_mol_percent` - Convert the formula to mol%.
 //! * `molecular_weight` - Calculate the molecular weight of the formula.
 //! * `to_wt` - Calculate the molecular weight representation of the formula.
 //! * `to_wt_percent` - Convert the formula to wt%.
+//! * `to_ppm` - Mass fraction of each element in ppm.
+//! * `to_at_percent` - Convert the formula to at%.
+//! * `from_mol_percent` - Mix formulas by mol% of formula units.
 //! * `multiply_wt_percent` - Multiply the wt% by a multiplier.
-//! * `add_formula` - Add another formula to the formula.
+//! * `add_formula` - Add another formula to the formula. The charges are added.
+//! * `is_neutral` - Check if the net charge is zero.
+//! * `iter_stoichiometry` - Iterate over the stoichiometry in atomic number order.
+//! * `iter_wt_percent` - Iterate over the wt% in atomic number order.
+//! * `monoisotopic_mass`, `nominal_mass`, `most_abundant_mass` and `mz` - Masses for mass spectrometry, see the `isotope` module.
 //!
 //! Please refer to the API reference for more details.
 //!
@@ -65,11 +81,15 @@
 //! assert_abs_diff_eq!(formula.wt_percent[&ElementSymbol::N], 40.0, epsilon = 1e-6);
 //! ```
 
+use crate::isotope::Isotope;
+use crate::parser::ParseError;
+use crate::periodic::ELEMENTS;
 use once_cell::sync::Lazy;
+use std::collections::BTreeMap;
 use std::collections::HashMap;
-use std::collections::HashSet;
 use std::error::Error;
-use std::mem;
+use std::fmt;
+use std::str::FromStr;
 
 /// Error type used in chemical-formula-rs
 ///
@@ -78,31 +98,292 @@ pub enum FormulaError {
     FileIOError,
     FileParseError,
     WeightPercentOverflow,
+    AtomicPercentOverflow,
+    MolPercentOverflow,
     NoFormula,
+    ParseError(Box<ParseError>),
+    UnknownElement(UnknownElement),
+    NoneElement,
+    NotNeutral(i32),
+    UnknownIsotope(Isotope),
+    IsotopePercent(Isotope),
+    NoIsotopeData(ElementSymbol),
+    NonIntegerStoichiometry(ElementSymbol),
+    ZeroCharge,
+    UnresolvedVariable(String),
+    Unbalanceable,
+    MultipleBalances(usize),
+    QuantityCount { expected: usize, found: usize },
+    NoLimitingReagent,
+    ElementNotLoaded(ElementSymbol),
+    ElementNotInPrecursor(ElementSymbol),
+    ElementNotInFormula(ElementSymbol),
+    NoEmpiricalFormula,
+    NoOxide(ElementSymbol),
+    UnknownOxidationStates(ElementSymbol),
+    NoOxidationStates,
+    InvalidAtomicNumber(u8),
+    NonIntegerCharge(f64),
+    UnknownVariable(String),
+    MoleFractionOutOfRange(f64),
+    UnbalancedReaction,
+    NotPositive(&'static str),
+    CoefficientOverflow,
+    NoPrecursor(ElementSymbol),
 }
 
 impl Error for FormulaError {
     fn description(&self) -> &str {
-        match *self {
+        match self {
             FormulaError::FileIOError => "File IO error",
             FormulaError::FileParseError => "File parse error",
             FormulaError::WeightPercentOverflow => "Weight percent overflow",
+            FormulaError::AtomicPercentOverflow => "Atomic percent overflow",
+            FormulaError::MolPercentOverflow => "Mol percent overflow",
             FormulaError::NoFormula => "No formula",
+            FormulaError::ParseError(_) => "Parse error",
+            FormulaError::UnknownElement(_) => "Unknown element",
+            FormulaError::NoneElement => "ElementSymbol::None in strict formula",
+            FormulaError::NotNeutral(_) => "Formula is not charge neutral",
+            FormulaError::UnknownIsotope(_) => "Unknown isotope",
+            FormulaError::IsotopePercent(_) => "wt%, at% or mol% of an isotope is not supported",
+            FormulaError::NoIsotopeData(_) => "No isotope data",
+            FormulaError::NonIntegerStoichiometry(_) => "Stoichiometry is not an integer",
+            FormulaError::ZeroCharge => "Charge is zero",
+            FormulaError::UnresolvedVariable(_) => "Variable of the stoichiometry has no value",
+            FormulaError::Unbalanceable => "Reaction cannot be balanced",
+            FormulaError::MultipleBalances(_) => "Reaction has multiple independent balances",
+            FormulaError::QuantityCount { .. } => {
+                "Number of quantities does not match the reactants"
+            }
+            FormulaError::NoLimitingReagent => "All reactants are in excess",
+            FormulaError::ElementNotLoaded(_) => "Element is not in the loaded phase",
+            FormulaError::ElementNotInPrecursor(_) => "Element is not in the precursor",
+            FormulaError::ElementNotInFormula(_) => "Element is not in the formula",
+            FormulaError::NoEmpiricalFormula => "No empirical formula within the tolerance",
+            FormulaError::NoOxide(_) => "No oxide of the element in the oxide basis",
+            FormulaError::UnknownOxidationStates(_) => "No oxidation states of the element",
+            FormulaError::NoOxidationStates => "No oxidation states balance the charge",
+            FormulaError::InvalidAtomicNumber(_) => "No element has the atomic number",
+            FormulaError::NonIntegerCharge(_) => "Charge is not an integer",
+            FormulaError::UnknownVariable(_) => "Variable is not used",
+            FormulaError::MoleFractionOutOfRange(_) => "Mole fraction is outside 0 - 1",
+            FormulaError::UnbalancedReaction => "Reaction is not balanced",
+            FormulaError::NotPositive(_) => "Value is not positive",
+            FormulaError::CoefficientOverflow => "Coefficients are too large to balance exactly",
+            FormulaError::NoPrecursor(_) => "No precursor of the loaded element",
         }
     }
 }
 
 impl std::fmt::Display for FormulaError {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        match *self {
+        match self {
             FormulaError::FileIOError => write!(f, "File IO error"),
             FormulaError::FileParseError => write!(f, "File parse error"),
             FormulaError::WeightPercentOverflow => write!(f, "Weight percent overflow"),
+            FormulaError::AtomicPercentOverflow => write!(f, "Atomic percent overflow"),
+            FormulaError::MolPercentOverflow => write!(f, "Mol percent overflow"),
             FormulaError::NoFormula => write!(f, "No formula"),
+            FormulaError::ParseError(err) => write!(f, "{}", err),
+            FormulaError::UnknownElement(err) => write!(f, "{}", err),
+            FormulaError::NoneElement => write!(f, "ElementSymbol::None in strict formula"),
+            FormulaError::NotNeutral(charge) => {
+                write!(f, "Formula is not charge neutral (charge {:+})", charge)
+            }
+            FormulaError::UnknownIsotope(isotope) => {
+                write!(f, "No isotope data for `{}`", isotope)
+            }
+            FormulaError::IsotopePercent(isotope) => {
+                write!(
+                    f,
+                    "wt%, at% or mol% of an isotope is not supported (`{}`)",
+                    isotope
+                )
+            }
+            FormulaError::NoIsotopeData(element) => {
+                write!(f, "No naturally abundant isotope of `{}`", element)
+            }
+            FormulaError::NonIntegerStoichiometry(element) => {
+                write!(f, "Stoichiometry of `{}` is not an integer", element)
+            }
+            FormulaError::ZeroCharge => write!(f, "Charge is zero"),
+            FormulaError::UnresolvedVariable(variable) => {
+                write!(
+                    f,
+                    "Variable `{}` of the stoichiometry has no value",
+                    variable
+                )
+            }
+            FormulaError::Unbalanceable => write!(f, "Reaction cannot be balanced"),
+            FormulaError::MultipleBalances(count) => write!(
+                f,
+                "Reaction has {} independent balances, which need more constraints",
+                count
+            ),
+            FormulaError::QuantityCount { expected, found } => write!(
+                f,
+                "{} quantities are given for {} reactants",
+                found, expected
+            ),
+            FormulaError::NoLimitingReagent => write!(f, "All reactants are in excess"),
+            FormulaError::ElementNotLoaded(element) => {
+                write!(
+                    f,
+                    "`{}` is not in the loaded phase of the catalyst",
+                    element
+                )
+            }
+            FormulaError::ElementNotInPrecursor(element) => {
+                write!(f, "`{}` is not in the precursor", element)
+            }
+            FormulaError::ElementNotInFormula(element) => {
+                write!(f, "`{}` is not in the formula", element)
+            }
+            FormulaError::NoEmpiricalFormula => {
+                write!(f, "No empirical formula within the tolerance")
+            }
+            FormulaError::NoOxide(element) => {
+                write!(f, "No oxide of `{}` in the oxide basis", element)
+            }
+            FormulaError::UnknownOxidationStates(element) => {
+                write!(f, "No oxidation states of `{}`", element)
+            }
+            FormulaError::NoOxidationStates => {
+                write!(f, "No oxidation states balance the charge of the formula")
+            }
+            FormulaError::InvalidAtomicNumber(atomic_number) => {
+                write!(f, "No element has the atomic number {}", atomic_number)
+            }
+            FormulaError::NonIntegerCharge(charge) => {
+                write!(f, "Charge {} is not an integer", charge)
+            }
+            FormulaError::UnknownVariable(variable) => {
+                write!(f, "Variable `{}` is not used", variable)
+            }
+            FormulaError::MoleFractionOutOfRange(x) => {
+                write!(f, "Mole fraction {} is outside 0 - 1", x)
+            }
+            FormulaError::UnbalancedReaction => {
+                write!(f, "Reaction is not balanced, use `Reaction::balance` first")
+            }
+            FormulaError::NotPositive(name) => write!(f, "`{}` is not positive", name),
+            FormulaError::CoefficientOverflow => write!(
+                f,
+                "Coefficients of the reaction are too large to balance exactly"
+            ),
+            FormulaError::NoPrecursor(element) => {
+                write!(f, "No precursor of `{}` in the recipe", element)
+            }
+        }
+    }
+}
+
+impl From<UnknownElement> for FormulaError {
+    fn from(err: UnknownElement) -> Self {
+        FormulaError::UnknownElement(err)
+    }
+}
+
+/// Error returned when a string is not a known element symbol
+///
+/// `suggestions` holds the element symbols that match the input ignoring case.
+/// `split` holds the elements the input reads as when it is written as a formula, e.g. `CO` is C and O.
+///
+/// # Example
+/// ```
+/// use chemical_formula::prelude::*;
+///
+/// let err = "CO".parse::<ElementSymbol>().unwrap_err();
+/// assert_eq!(err.suggestions, vec![ElementSymbol::Co]);
+/// assert_eq!(err.split, vec![ElementSymbol::C, ElementSymbol::O]);
+/// assert_eq!(
+///     err.to_string(),
+///     "Unknown element symbol `CO`, did you mean `Co`? (`CO` is read as C, O in a formula)"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownElement {
+    pub symbol: String,
+    pub suggestions: Vec<ElementSymbol>,
+    pub split: Vec<ElementSymbol>,
+}
+
+impl UnknownElement {
+    fn new(symbol: &str) -> Self {
+        let mut suggestions: Vec<ElementSymbol> = ATOMIC_WEIGHT
+            .keys()
+            .filter(|element| **element != ElementSymbol::None)
+            .filter(|element| element.to_string().eq_ignore_ascii_case(symbol))
+            .copied()
+            .collect();
+        suggestions.sort_by_key(|element| *element as u8);
+
+        UnknownElement {
+            symbol: symbol.to_string(),
+            suggestions,
+            split: split_symbols(symbol).unwrap_or_default(),
+        }
+    }
+}
+
+/// Split a string into element symbols, each starting with an uppercase letter
+fn split_symbols(s: &str) -> Option<Vec<ElementSymbol>> {
+    let mut elements = Vec::new();
+    let mut start = 0;
+
+    for (i, c) in s.char_indices().skip(1) {
+        if c.is_ascii_uppercase() {
+            elements.push(ElementSymbol::lookup(&s[start..i])?);
+            start = i;
         }
     }
+
+    if elements.is_empty() {
+        return None;
+    }
+    elements.push(ElementSymbol::lookup(&s[start..])?);
+
+    Some(elements)
 }
 
+impl Error for UnknownElement {}
+
+impl fmt::Display for UnknownElement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Unknown element symbol `{}`", self.symbol)?;
+
+        for (i, element) in self.suggestions.iter().enumerate() {
+            if i == 0 {
+                write!(f, ", did you mean `{}`", element)?;
+            } else {
+                write!(f, " or `{}`", element)?;
+            }
+        }
+        if !self.suggestions.is_empty() {
+            write!(f, "?")?;
+        }
+
+        if !self.split.is_empty() {
+            let split: Vec<String> = self.split.iter().map(|e| e.to_string()).collect();
+            write!(
+                f,
+                " (`{}` is read as {} in a formula)",
+                self.symbol,
+                split.join(", ")
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Electron mass in unified atomic mass units
+///
+/// # Reference
+/// [CODATA 2018](https://physics.nist.gov/cgi-bin/cuu/Value?meu)
+pub const ELECTRON_MASS: f64 = 5.48579909065e-4;
+
 /// Hashmap of atomic weight
 ///
 /// The key is the `ElementSymbol` enums and the value is the atomic weight.
@@ -250,7 +531,8 @@ pub static ATOMIC_WEIGHT: Lazy<HashMap<ElementSymbol, f64>> = Lazy::new(|| {
 ///
 /// There is a None variant for the case where the element to express is not in the periodic table.
 ///
-/// Simple api to obtain a `ElementSymbol` from `&str` is provided as `.from_str()`.;
+/// `ElementSymbol` implements `FromStr` and `TryFrom<&str>`. Unknown symbols are rejected with an `UnknownElement` error.
+/// `TryFrom<u8>` and `atomic_number()` convert from and to the atomic number, and `Ord` orders by atomic number.
 ///
 /// Simple api to retreve the atomic weight of the element is provided as `.atomic_weight()`.
 ///
@@ -259,13 +541,13 @@ pub static ATOMIC_WEIGHT: Lazy<HashMap<ElementSymbol, f64>> = Lazy::new(|| {
 /// use chemical_formula::prelude::*;
 /// use approx::assert_abs_diff_eq;
 ///
-/// let o = ElementSymbol::from_str("O");
+/// let o: ElementSymbol = "O".parse().unwrap();
 /// assert_eq!(o, ElementSymbol::O);
 ///
 /// let h = ElementSymbol::H;
 /// approx::assert_abs_diff_eq!(h.atomic_weight(), 1.008, epsilon = 1e-6);
 /// ```
-#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum ElementSymbol {
     #[default]
     None = 0,
@@ -390,8 +672,8 @@ pub enum ElementSymbol {
 }
 
 impl ElementSymbol {
-    pub fn from_str(s: &str) -> ElementSymbol {
-        match s {
+    fn lookup(s: &str) -> Option<ElementSymbol> {
+        let element = match s {
             "H" => ElementSymbol::H,
             "He" => ElementSymbol::He,
             "Li" => ElementSymbol::Li,
@@ -510,21 +792,98 @@ impl ElementSymbol {
             "Lv" => ElementSymbol::Lv,
             "Ts" => ElementSymbol::Ts,
             "Og" => ElementSymbol::Og,
-            _ => ElementSymbol::None,
-        }
+            _ => return None,
+        };
+
+        Some(element)
     }
 
     pub fn atomic_weight(&self) -> f64 {
         ATOMIC_WEIGHT[self]
     }
+
+    /// Atomic number of the element, 0 for `ElementSymbol::None`
+    ///
+    /// # Example
+    /// ```
+    /// use chemical_formula::prelude::*;
+    ///
+    /// assert_eq!(ElementSymbol::Fe.atomic_number(), 26);
+    /// assert_eq!(ElementSymbol::try_from(26).unwrap(), ElementSymbol::Fe);
+    /// assert!(ElementSymbol::try_from(119).is_err());
+    /// ```
+    pub fn atomic_number(&self) -> u8 {
+        *self as u8
+    }
+}
+
+impl FromStr for ElementSymbol {
+    type Err = UnknownElement;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ElementSymbol::lookup(s).ok_or_else(|| UnknownElement::new(s))
+    }
+}
+
+impl TryFrom<&str> for ElementSymbol {
+    type Error = UnknownElement;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl TryFrom<u8> for ElementSymbol {
+    type Error = FormulaError;
+
+    /// Returns `FormulaError::InvalidAtomicNumber` outside 1 - 118
+    fn try_from(atomic_number: u8) -> Result<Self, Self::Error> {
+        match atomic_number {
+            1..=118 => Ok(ELEMENTS[atomic_number as usize - 1].symbol),
+            _ => Err(FormulaError::InvalidAtomicNumber(atomic_number)),
+        }
+    }
+}
+
+impl fmt::Display for ElementSymbol {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
 }
 
 /// Struct to represent the chemical formula along wt%  
+///
+/// `element` holds the elements in the order they were first added, which is the order used by `Display`.
+/// `stoichiometry` and `wt_percent` are ordered by atomic number, so iteration and `Debug` output are stable.
+///
+/// `isotope` holds the isotope-labelled atoms, e.g. 2.0 of `D` for `D2O`.
+/// The labelled atoms are also counted in `stoichiometry`, so `D2O` has a stoichiometry of 2.0 for H.
+/// The labels are kept only for stoichiometric amounts and are dropped when converting to wt%.
+///
+/// `charge` is the net charge of the formula, e.g. -2 for `SO4^2-`.
+///
+/// `at_percent` holds the elements given by at%, the percentage of all atoms of the formula.
+/// `to_molecular_formula` converts the wt% and at% together into stoichiometry.
+///
+/// When `strict` is set, the formula refuses `ElementSymbol::None` entries.
+/// `try_add_element`, `try_add_wt_percent` and `try_add_at_percent` return an error instead of adding it,
+/// and `add_element`, `add_wt_percent`, `add_at_percent` and `add_formula` panic.
+/// The conversions return `FormulaError::NoneElement` if `strict` was set after the entry was added.
 #[derive(Debug, Clone, Default)]
 pub struct ChemicalFormula {
-    pub element: HashSet<ElementSymbol>,
-    pub stoichiometry: HashMap<ElementSymbol, f64>,
-    pub wt_percent: HashMap<ElementSymbol, f64>,
+    pub element: Vec<ElementSymbol>,
+    pub stoichiometry: BTreeMap<ElementSymbol, f64>,
+    pub wt_percent: BTreeMap<ElementSymbol, f64>,
+    pub at_percent: BTreeMap<ElementSymbol, f64>,
+    pub isotope: BTreeMap<Isotope, f64>,
+    pub charge: i32,
+    pub strict: bool,
+}
+
+/// mol and wt% of each element returned by `ChemicalFormula::measured_composition`
+pub(crate) struct MeasuredComposition {
+    pub moles: BTreeMap<ElementSymbol, f64>,
+    pub wt_percent: BTreeMap<ElementSymbol, f64>,
 }
 
 impl ChemicalFormula {
@@ -541,9 +900,126 @@ impl ChemicalFormula {
     /// ```
     pub fn new() -> Self {
         ChemicalFormula {
-            element: HashSet::new(),
-            stoichiometry: HashMap::new(),
-            wt_percent: HashMap::new(),
+            element: Vec::new(),
+            stoichiometry: BTreeMap::new(),
+            wt_percent: BTreeMap::new(),
+            at_percent: BTreeMap::new(),
+            isotope: BTreeMap::new(),
+            charge: 0,
+            strict: false,
+        }
+    }
+
+    /// Create a empty ChemicalFormula in strict mode
+    ///
+    /// # Example
+    /// ```
+    /// use chemical_formula::prelude::*;
+    ///
+    /// let mut formula = ChemicalFormula::new_strict();
+    /// formula.try_add_element(ElementSymbol::O, 1.0).unwrap();
+    ///
+    /// assert!(formula.try_add_element(ElementSymbol::None, 1.0).is_err());
+    /// assert!(!formula.element.contains(&ElementSymbol::None));
+    /// ```
+    pub fn new_strict() -> Self {
+        ChemicalFormula {
+            strict: true,
+            ..ChemicalFormula::new()
+        }
+    }
+
+    /// Check that a strict formula has no `ElementSymbol::None` entry
+    ///
+    /// # Example
+    /// ```
+    /// use chemical_formula::prelude::*;
+    ///
+    /// let mut formula = ChemicalFormula::new();
+    /// formula.add_element(ElementSymbol::None, 1.0);
+    /// assert!(formula.check_strict().is_ok());
+    ///
+    /// formula.strict = true;
+    /// assert!(formula.check_strict().is_err());
+    /// assert!(formula.molecular_weight().is_err());
+    /// ```
+    pub fn check_strict(&self) -> Result<(), FormulaError> {
+        if self.strict && self.element.contains(&ElementSymbol::None) {
+            return Err(FormulaError::NoneElement);
+        }
+
+        Ok(())
+    }
+
+    /// Add an element to the formula, refusing `ElementSymbol::None` in strict mode
+    ///
+    /// # Example
+    /// ```
+    /// use chemical_formula::prelude::*;
+    ///
+    /// let mut formula = ChemicalFormula::new_strict();
+    /// let element = "CL".parse().unwrap_or_default();
+    ///
+    /// assert!(formula.try_add_element(element, 1.0).is_err());
+    /// ```
+    pub fn try_add_element(
+        &mut self,
+        element: ElementSymbol,
+        stoichiometry: f64,
+    ) -> Result<&mut Self, FormulaError> {
+        if self.strict && element == ElementSymbol::None {
+            return Err(FormulaError::NoneElement);
+        }
+
+        Ok(self.add_element(element, stoichiometry))
+    }
+
+    /// Add an element to the formula by wt%, refusing `ElementSymbol::None` in strict mode
+    ///
+    /// # Example
+    /// ```
+    /// use chemical_formula::prelude::*;
+    ///
+    /// let mut formula = ChemicalFormula::new_strict();
+    /// formula.try_add_wt_percent(ElementSymbol::Pt, 5.0).unwrap();
+    ///
+    /// assert!(formula.try_add_wt_percent(ElementSymbol::None, 5.0).is_err());
+    /// ```
+    pub fn try_add_wt_percent(
+        &mut self,
+        element: ElementSymbol,
+        wt_ratio: f64,
+    ) -> Result<&mut Self, FormulaError> {
+        if self.strict && element == ElementSymbol::None {
+            return Err(FormulaError::NoneElement);
+        }
+
+        Ok(self.add_wt_percent(element, wt_ratio))
+    }
+
+    /// Add an element to the formula by at%, refusing `ElementSymbol::None` in strict mode
+    pub fn try_add_at_percent(
+        &mut self,
+        element: ElementSymbol,
+        at_ratio: f64,
+    ) -> Result<&mut Self, FormulaError> {
+        if self.strict && element == ElementSymbol::None {
+            return Err(FormulaError::NoneElement);
+        }
+
+        Ok(self.add_at_percent(element, at_ratio))
+    }
+
+    /// Record an element in `element`, keeping the order of first appearance
+    ///
+    /// Panics for `ElementSymbol::None` in strict mode.
+    fn insert_element(&mut self, element: ElementSymbol) {
+        assert!(
+            !(self.strict && element == ElementSymbol::None),
+            "ElementSymbol::None in strict formula, use the try_add_* methods to handle it"
+        );
+        if !self.element.contains(&element) {
+            self.element.push(element);
         }
     }
 
@@ -566,8 +1042,11 @@ impl ChemicalFormula {
     ///
     /// assert_abs_diff_eq!(formula.stoichiometry[&ElementSymbol::O], 1.0);
     /// ```
+    ///
+    /// # Panics
+    /// Panics if `element` is `ElementSymbol::None` in strict mode. Use `try_add_element` to get an error instead.
     pub fn add_element(&mut self, element: ElementSymbol, stoichiometry: f64) -> &mut Self {
-        self.element.insert(element);
+        self.insert_element(element);
 
         self.stoichiometry
             .entry(element)
@@ -602,8 +1081,10 @@ impl ChemicalFormula {
     /// assert_abs_diff_eq!(formula.wt_percent[&ElementSymbol::H], 20.0);
     /// ```
     ///
+    /// # Panics
+    /// Panics if `element` is `ElementSymbol::None` in strict mode. Use `try_add_wt_percent` to get an error instead.
     pub fn add_wt_percent(&mut self, element: ElementSymbol, wt_ratio: f64) -> &mut Self {
-        self.element.insert(element);
+        self.insert_element(element);
 
         self.wt_percent
             .entry(element)
@@ -612,15 +1093,128 @@ impl ChemicalFormula {
         self
     }
 
-    /// Multiply the stoichiometry and wt% by a multiplier
+    /// Add an element to the formula by mass ppm
+    ///
+    /// The ppm is normalised to wt%, i.e. 1 ppm is 1e-4 wt%.
+    ///
+    /// # Example
+    /// ```
+    /// use chemical_formula::prelude::*;
+    /// use approx::assert_abs_diff_eq;
+    ///
+    /// let mut formula = ChemicalFormula::new();
+    /// formula.add_element(ElementSymbol::Ti, 1.0);
+    /// formula.add_element(ElementSymbol::O, 2.0);
+    /// formula.add_ppm(ElementSymbol::Pd, 500.0);
+    ///
+    /// assert_abs_diff_eq!(formula.wt_percent[&ElementSymbol::Pd], 0.05);
+    /// ```
+    pub fn add_ppm(&mut self, element: ElementSymbol, ppm: f64) -> &mut Self {
+        self.add_wt_percent(element, ppm / 1e4)
+    }
+
+    /// Add an element to the formula by at%
+    ///
+    /// The at% is the percentage of all atoms of the formula. For an element, mol% is the same as at%.
+    ///
+    /// # Arguments
+    /// * `element` - A `ElementSymbol` enum
+    /// * `at_ratio` - A f64 value that represents the at% ratio (0.0 - 100.0)
+    ///
+    /// # Example
+    /// ```
+    /// use chemical_formula::prelude::*;
+    /// use approx::assert_abs_diff_eq;
+    ///
+    /// let mut formula = ChemicalFormula::new();
+    /// formula.add_element(ElementSymbol::Ni, 1.0);
+    /// formula.add_at_percent(ElementSymbol::Cu, 20.0);
+    ///
+    /// let molecular_formula = formula.to_molecular_formula().unwrap();
+    /// assert_abs_diff_eq!(molecular_formula.stoichiometry[&ElementSymbol::Cu], 0.25, epsilon = 1e-9);
+    /// ```
+    ///
+    /// # Panics
+    /// Panics if `element` is `ElementSymbol::None` in strict mode. Use `try_add_at_percent` to get an error instead.
+    pub fn add_at_percent(&mut self, element: ElementSymbol, at_ratio: f64) -> &mut Self {
+        self.insert_element(element);
+
+        self.at_percent
+            .entry(element)
+            .and_modify(|e| *e += at_ratio)
+            .or_insert(at_ratio);
+        self
+    }
+
+    /// Add an isotope to the formula
+    ///
+    /// The stoichiometry is added to both `isotope` and the element in `stoichiometry`.
+    ///
+    /// # Arguments
+    /// * `isotope` - A `Isotope`
+    /// * `stoichiometry` - A f64 value that represents the stoichiometry
     ///
-    /// This method will simply multiply the stoichiometry and wt% by a multiplier.
+    /// # Example
+    /// ```
+    /// use chemical_formula::prelude::*;
+    /// use approx::assert_abs_diff_eq;
+    ///
+    /// let mut formula = ChemicalFormula::new();
+    /// formula.add_element(ElementSymbol::C, 1.0);
+    /// formula.add_isotope(Isotope::new(ElementSymbol::O, 18), 1.0);
+    /// formula.add_element(ElementSymbol::O, 1.0);
+    ///
+    /// assert_abs_diff_eq!(formula.stoichiometry[&ElementSymbol::O], 2.0);
+    /// assert_abs_diff_eq!(formula.molecular_weight().unwrap(), 46.01, epsilon = 1e-2);
+    /// ```
+    pub fn add_isotope(&mut self, isotope: Isotope, stoichiometry: f64) -> &mut Self {
+        self.add_element(isotope.element, stoichiometry);
+
+        self.isotope
+            .entry(isotope)
+            .and_modify(|e| *e += stoichiometry)
+            .or_insert(stoichiometry);
+
+        self
+    }
+
+    /// Mass of `stoichiometry` atoms of the element, using the isotopic mass for the labelled atoms
+    fn element_weight(
+        &self,
+        element: ElementSymbol,
+        stoichiometry: f64,
+    ) -> Result<f64, FormulaError> {
+        let mut unlabelled = stoichiometry;
+        let mut weight = 0.0;
+
+        for (isotope, amount) in self
+            .isotope
+            .iter()
+            .filter(|(isotope, _)| isotope.element == element)
+        {
+            let mass = isotope
+                .mass()
+                .ok_or(FormulaError::UnknownIsotope(*isotope))?;
+            unlabelled -= amount;
+            weight += mass * amount;
+        }
+
+        Ok(weight + ATOMIC_WEIGHT[&element] * unlabelled)
+    }
+
+    /// Multiply the stoichiometry, wt% and at% by a multiplier
+    ///
+    /// This method will simply multiply the stoichiometry, wt% and at% by a multiplier.
     /// The method will not check if the wt% sum is over 100.
     ///
     /// For example, if the formula has 10 wt% of H and the multiplier is 2, the resulting wt% of H
     /// will be 20wt%. If the formula has stoichiometry of 1.0 of O and the multiplier is 2, the resulting
     /// stoichiometry of O will be 2.0.
     ///
+    /// The charge is multiplied as well, so `SO4^2-` multiplied by 2 has the charge -4.
+    /// The charge of a charged formula multiplied by a non-integer is rounded to the nearest integer.
+    /// Use `try_multiply` to get an error instead.
+    ///
     /// # Arguments
     /// * `multiplier` - A f64 value that represents the multiplier
     /// # Example
@@ -637,13 +1231,54 @@ impl ChemicalFormula {
     /// assert_abs_diff_eq!(formula.stoichiometry[&ElementSymbol::O], 2.0, epsilon = 1e-6);
     /// ```
     pub fn multiply(&mut self, multiplier: f64) -> &mut Self {
-        for (element, stoichiometry) in self.stoichiometry.iter_mut() {
+        self.charge = (self.charge as f64 * multiplier).round() as i32;
+        self.scale(multiplier)
+    }
+
+    /// Multiply the stoichiometry, wt%, at% and charge by a multiplier
+    ///
+    /// Same as `multiply`, but returns `FormulaError::NonIntegerCharge` if the multiplied charge is not an integer.
+    ///
+    /// # Example
+    /// ```
+    /// use chemical_formula::prelude::*;
+    ///
+    /// let mut formula = parse_formula("SO4^2-").unwrap();
+    /// formula.try_multiply(2.0).unwrap();
+    /// assert_eq!(formula.charge, -4);
+    ///
+    /// assert!(matches!(
+    ///     formula.try_multiply(0.3),
+    ///     Err(FormulaError::NonIntegerCharge(_))
+    /// ));
+    /// ```
+    pub fn try_multiply(&mut self, multiplier: f64) -> Result<&mut Self, FormulaError> {
+        let charge = self.charge as f64 * multiplier;
+
+        if (charge - charge.round()).abs() > 1e-9 {
+            return Err(FormulaError::NonIntegerCharge(charge));
+        }
+
+        Ok(self.multiply(multiplier))
+    }
+
+    /// Multiply the stoichiometry, wt% and at% by a multiplier, keeping the charge
+    fn scale(&mut self, multiplier: f64) -> &mut Self {
+        for stoichiometry in self.stoichiometry.values_mut() {
             *stoichiometry *= multiplier;
         }
 
-        for (element, wt_ratio) in self.wt_percent.iter_mut() {
+        for wt_ratio in self.wt_percent.values_mut() {
             *wt_ratio *= multiplier;
         }
+
+        for at_ratio in self.at_percent.values_mut() {
+            *at_ratio *= multiplier;
+        }
+
+        for stoichiometry in self.isotope.values_mut() {
+            *stoichiometry *= multiplier;
+        }
         self
     }
 
@@ -675,7 +1310,9 @@ impl ChemicalFormula {
     /// In the case where ChemicalFormula has no stoihiometry,the stoichiometry is a relative values.
     /// This is because the wt% is a relative term and the absolute value of the stoichiometry cannot be determined.
     pub fn to_molecular_formula(&self) -> Result<ChemicalFormula, FormulaError> {
-        if self.wt_percent.is_empty() {
+        self.check_strict()?;
+
+        if self.wt_percent.is_empty() && self.at_percent.is_empty() {
             return Ok(self.clone());
         }
 
@@ -691,41 +1328,77 @@ impl ChemicalFormula {
             return Err(FormulaError::WeightPercentOverflow);
         }
 
+        let mut at_ratio_sum = 0.0;
+        let mut at_ratio_molecular_weight_sum = 0.0;
+
+        for (element, at_ratio) in self.at_percent.iter() {
+            at_ratio_sum += at_ratio;
+            at_ratio_molecular_weight_sum += at_ratio * element.atomic_weight();
+        }
+
+        if at_ratio_sum > 100. {
+            return Err(FormulaError::AtomicPercentOverflow);
+        }
+
         let residue = 100. - wt_ratio_sum;
 
-        let mut molecular_weight_residue = if self.stoichiometry.is_empty() {
-            100.0
+        let (molecular_weight_residue, atoms_residue) = if self.stoichiometry.is_empty() {
+            (100.0, 0.0)
         } else {
-            self.stoichiometry
-                .iter()
-                .map(|(element, stoichiometry)| ATOMIC_WEIGHT[element] * stoichiometry)
-                .fold(0.0, |acc, x| acc + x)
+            (
+                self.stoichiometry
+                    .iter()
+                    .map(|(element, stoichiometry)| self.element_weight(*element, *stoichiometry))
+                    .sum::<Result<f64, FormulaError>>()?,
+                self.stoichiometry.values().sum(),
+            )
+        };
+
+        // Mass and number of atoms of the whole formula
+        let (mass, atoms) = if self.at_percent.is_empty() {
+            (molecular_weight_residue * 100. / residue, 0.0)
+        } else if self.stoichiometry.is_empty() && self.wt_percent.is_empty() {
+            // The at% is used as the relative stoichiometry
+            (0.0, 100.0)
+        } else {
+            // The total mass m and number of atoms n satisfy
+            // m = m_residue + m * Σwt / 100 + n * Σ(at * M) / 100
+            // n = n_residue + m * Σ(wt / M) / 100 + n * Σat / 100
+            let a = residue / 100.;
+            let b = at_ratio_molecular_weight_sum / 100.;
+            let c = wt_ratio_molecular_weight_sum / 100.;
+            let d = (100. - at_ratio_sum) / 100.;
+            let det = a * d - b * c;
+
+            if det <= 0. {
+                return Err(FormulaError::AtomicPercentOverflow);
+            }
+
+            let mass = (molecular_weight_residue * d + b * atoms_residue) / det;
+            let atoms = (a * atoms_residue + c * molecular_weight_residue) / det;
+
+            (mass, atoms)
         };
 
-        let molecular_weight_main =
-            molecular_weight_residue * wt_ratio_molecular_weight_sum / residue;
+        let mut stoichiometry = self.stoichiometry.clone();
 
-        let mut stoichiometry: HashMap<ElementSymbol, f64> = self.stoichiometry.clone();
+        for (element, at_ratio) in self.at_percent.iter() {
+            *stoichiometry.entry(*element).or_insert(0.0) += at_ratio * atoms / 100.;
+        }
 
         for (element, wt_ratio) in self.wt_percent.iter() {
-            stoichiometry
-                .entry(*element)
-                .and_modify(|e| {
-                    *e *= molecular_weight_main * wt_ratio
-                        / element.atomic_weight()
-                        / wt_ratio_molecular_weight_sum
-                })
-                .or_insert(
-                    molecular_weight_main * wt_ratio
-                        / element.atomic_weight()
-                        / wt_ratio_molecular_weight_sum,
-                );
+            *stoichiometry.entry(*element).or_insert(0.0) +=
+                mass * wt_ratio / element.atomic_weight() / 100.;
         }
 
         Ok(ChemicalFormula {
             element: self.element.clone(),
             stoichiometry,
-            wt_percent: HashMap::new(),
+            wt_percent: BTreeMap::new(),
+            at_percent: BTreeMap::new(),
+            isotope: self.isotope.clone(),
+            charge: self.charge,
+            strict: self.strict,
         })
     }
 
@@ -758,13 +1431,9 @@ impl ChemicalFormula {
             return Ok(ChemicalFormula::new());
         }
 
-        let molecular_formula_sum = formula
-            .stoichiometry
-            .iter()
-            .map(|(element, stoichiometry)| stoichiometry)
-            .fold(0.0, |acc, x| acc + x);
+        let molecular_formula_sum = formula.stoichiometry.values().fold(0.0, |acc, x| acc + x);
 
-        formula.multiply(100. / molecular_formula_sum);
+        formula.scale(100. / molecular_formula_sum);
 
         Ok(formula)
     }
@@ -787,15 +1456,75 @@ impl ChemicalFormula {
     /// ```
     ///
     pub fn molecular_weight(&self) -> Result<f64, FormulaError> {
-        let stoichiometry = if self.wt_percent.is_empty() {
-            self.stoichiometry.clone()
+        self.check_strict()?;
+
+        let formula = if self.wt_percent.is_empty() && self.at_percent.is_empty() {
+            self.clone()
         } else {
-            self.to_molecular_formula().unwrap().stoichiometry
+            self.to_molecular_formula()?
         };
-        Ok(stoichiometry
+
+        formula
+            .stoichiometry
             .iter()
-            .map(|(element, stoichiometry)| ATOMIC_WEIGHT[element] * stoichiometry)
-            .fold(0.0, |acc, x| acc + x))
+            .map(|(element, stoichiometry)| formula.element_weight(*element, *stoichiometry))
+            .sum()
+    }
+
+    /// Calculate the molecular weight of the formula including the mass of the missing or extra electrons
+    ///
+    /// The electron mass is subtracted for each positive charge and added for each negative charge.
+    ///
+    /// # Example
+    /// ```
+    /// use chemical_formula::prelude::*;
+    /// use chemical_formula::element::ELECTRON_MASS;
+    /// use approx::assert_abs_diff_eq;
+    ///
+    /// let formula = parse_formula("SO4^2-").unwrap();
+    ///
+    /// assert_abs_diff_eq!(
+    ///     formula.molecular_weight_with_electrons().unwrap(),
+    ///     formula.molecular_weight().unwrap() + 2. * ELECTRON_MASS,
+    ///     epsilon = 1e-9
+    /// );
+    /// ```
+    pub fn molecular_weight_with_electrons(&self) -> Result<f64, FormulaError> {
+        Ok(self.molecular_weight()? - self.charge as f64 * ELECTRON_MASS)
+    }
+
+    /// Whether the net charge of the formula is zero
+    ///
+    /// # Example
+    /// ```
+    /// use chemical_formula::prelude::*;
+    ///
+    /// let mut formula = parse_formula("Ca2+").unwrap();
+    /// assert!(!formula.is_neutral());
+    ///
+    /// formula.add_formula(&parse_formula("Cl-").unwrap());
+    /// formula.add_formula(&parse_formula("Cl-").unwrap());
+    /// assert!(formula.is_neutral());
+    /// ```
+    pub fn is_neutral(&self) -> bool {
+        self.charge == 0
+    }
+
+    /// Check that the net charge of the formula is zero
+    ///
+    /// # Example
+    /// ```
+    /// use chemical_formula::prelude::*;
+    ///
+    /// let formula = parse_formula("NH4+").unwrap();
+    /// assert!(matches!(formula.check_neutral(), Err(FormulaError::NotNeutral(1))));
+    /// ```
+    pub fn check_neutral(&self) -> Result<(), FormulaError> {
+        if !self.is_neutral() {
+            return Err(FormulaError::NotNeutral(self.charge));
+        }
+
+        Ok(())
     }
 
     /// Calculate the molecular weight representation of the formula
@@ -816,26 +1545,32 @@ impl ChemicalFormula {
     /// assert_abs_diff_eq!(wt_percent.wt_percent[&ElementSymbol::Na], 10.0, epsilon = 1e-6);
     /// ```
     pub fn to_wt(&self) -> Result<ChemicalFormula, FormulaError> {
-        if self.stoichiometry.is_empty() {
+        self.check_strict()?;
+
+        if self.stoichiometry.is_empty() && self.at_percent.is_empty() {
             return Ok(self.clone());
         }
 
         let formula = self.to_molecular_formula()?;
         let molecular_weight = formula.molecular_weight()?;
 
-        let mut wt_ratio = HashMap::new();
+        let mut wt_ratio = BTreeMap::new();
 
         for (element, stoichiometry) in formula.stoichiometry.iter() {
             wt_ratio.insert(
                 *element,
-                stoichiometry * ATOMIC_WEIGHT[element] * 100. / molecular_weight,
+                formula.element_weight(*element, *stoichiometry)? * 100. / molecular_weight,
             );
         }
 
         Ok(ChemicalFormula {
             element: formula.element,
-            stoichiometry: HashMap::new(),
+            stoichiometry: BTreeMap::new(),
             wt_percent: wt_ratio,
+            at_percent: BTreeMap::new(),
+            isotope: BTreeMap::new(),
+            charge: self.charge,
+            strict: self.strict,
         })
     }
 
@@ -875,11 +1610,55 @@ impl ChemicalFormula {
 
         let wt_total = formula.wt_percent.iter().fold(0.0, |acc, (_, x)| acc + x);
 
-        formula.multiply(100. / wt_total);
+        formula.scale(100. / wt_total);
 
         Ok(formula)
     }
 
+    /// mol and wt% of each element of a measured composition
+    ///
+    /// A formula given only by wt% terms is taken as it is, so the wt% do not have to sum to 100,
+    /// and the mol are per 100 g. Other formulas are converted with `to_molecular_formula()` and `to_wt_percent()`.
+    pub(crate) fn measured_composition(&self) -> Result<MeasuredComposition, FormulaError> {
+        if self.stoichiometry.is_empty() && self.at_percent.is_empty() {
+            Ok(MeasuredComposition {
+                moles: self
+                    .wt_percent
+                    .iter()
+                    .map(|(element, wt_ratio)| (*element, wt_ratio / element.atomic_weight()))
+                    .collect(),
+                wt_percent: self.wt_percent.clone(),
+            })
+        } else {
+            let formula = self.to_molecular_formula()?;
+            Ok(MeasuredComposition {
+                wt_percent: formula.to_wt_percent()?.wt_percent,
+                moles: formula.stoichiometry,
+            })
+        }
+    }
+
+    /// Mass fraction of each element in ppm, in atomic number order
+    ///
+    /// # Example
+    /// ```
+    /// use chemical_formula::prelude::*;
+    /// use approx::assert_abs_diff_eq;
+    ///
+    /// let formula = parse_formula("Pd500ppm/TiO2").unwrap();
+    /// let ppm = formula.to_ppm().unwrap();
+    ///
+    /// assert_abs_diff_eq!(ppm[&ElementSymbol::Pd], 500.0, epsilon = 1e-6);
+    /// assert_abs_diff_eq!(ppm.values().sum::<f64>(), 1e6, epsilon = 1e-6);
+    /// ```
+    pub fn to_ppm(&self) -> Result<BTreeMap<ElementSymbol, f64>, FormulaError> {
+        Ok(self
+            .to_wt_percent()?
+            .iter_wt_percent()
+            .map(|(element, wt_ratio)| (element, wt_ratio * 1e4))
+            .collect())
+    }
+
     /// multiplier by wt%
     ///
     /// # Arguments
@@ -906,27 +1685,168 @@ impl ChemicalFormula {
         let formula = self.to_molecular_formula()?;
         let molecular_weight = formula.molecular_weight()?;
 
-        let mut wt_ratio = HashMap::new();
+        let mut wt_ratio = BTreeMap::new();
 
         for (element, stoichiometry) in formula.stoichiometry.iter() {
             wt_ratio.insert(
                 *element,
-                stoichiometry * ATOMIC_WEIGHT[element] / molecular_weight * multiplier,
+                formula.element_weight(*element, *stoichiometry)? / molecular_weight * multiplier,
             );
         }
 
-        mem::replace(
-            self,
-            ChemicalFormula {
-                element: formula.element,
-                stoichiometry: HashMap::new(),
-                wt_percent: wt_ratio,
-            },
-        );
+        *self = ChemicalFormula {
+            element: formula.element,
+            stoichiometry: BTreeMap::new(),
+            wt_percent: wt_ratio,
+            at_percent: BTreeMap::new(),
+            isotope: BTreeMap::new(),
+            charge: self.charge,
+            strict: self.strict,
+        };
+
+        Ok(self)
+    }
+
+    /// Convert the formula to at%
+    ///
+    /// This method will convert the formula to the percentage of atoms of each element, normalized to 100at%.
+    /// The result is the same as `to_mol_percent()`, but is held in `at_percent`.
+    ///
+    /// # Example
+    /// ```
+    /// use chemical_formula::prelude::*;
+    /// use approx::assert_abs_diff_eq;
+    ///
+    /// let formula = parse_formula("Pt2at%/Al2O3").unwrap();
+    /// let at_percent = formula.to_at_percent().unwrap();
+    ///
+    /// assert_abs_diff_eq!(at_percent.at_percent[&ElementSymbol::Pt], 2.0, epsilon = 1e-9);
+    /// assert_abs_diff_eq!(at_percent.at_percent[&ElementSymbol::Al], 39.2, epsilon = 1e-9);
+    /// ```
+    pub fn to_at_percent(&self) -> Result<ChemicalFormula, FormulaError> {
+        let mut formula = self.clone();
+        formula.multiply_at_percent(100.)?;
+
+        Ok(formula)
+    }
+
+    /// multiplier by at%
+    ///
+    /// The formula is converted to at% normalized to `multiplier`.
+    /// This is used for a group given by at%, e.g. `(Al2O3)50at%`.
+    ///
+    /// # Arguments
+    /// * `multiplier` - A f64 value that represents the at% multiplier
+    ///
+    /// # Example
+    /// ```
+    /// use chemical_formula::prelude::*;
+    /// use approx::assert_abs_diff_eq;
+    ///
+    /// let mut formula = parse_formula("H2O").unwrap();
+    /// formula.multiply_at_percent(30.0).unwrap();
+    ///
+    /// assert_abs_diff_eq!(formula.at_percent[&ElementSymbol::H], 20.0, epsilon = 1e-9);
+    /// assert!(formula.stoichiometry.is_empty());
+    /// ```
+    pub fn multiply_at_percent(&mut self, multiplier: f64) -> Result<&mut Self, FormulaError> {
+        let formula = self.to_molecular_formula()?;
+        let atoms: f64 = formula.stoichiometry.values().sum();
+
+        let mut at_ratio = BTreeMap::new();
+
+        for (element, stoichiometry) in formula.stoichiometry.iter() {
+            at_ratio.insert(*element, stoichiometry / atoms * multiplier);
+        }
+
+        *self = ChemicalFormula {
+            element: formula.element,
+            stoichiometry: BTreeMap::new(),
+            wt_percent: BTreeMap::new(),
+            at_percent: at_ratio,
+            isotope: BTreeMap::new(),
+            charge: self.charge,
+            strict: self.strict,
+        };
 
         Ok(self)
     }
 
+    /// Mix formulas by mol%
+    ///
+    /// The mol% is the percentage of formula units, e.g. `(CeO2)20mol%(ZrO2)80mol%` is 0.2 CeO2 and 0.8 ZrO2
+    /// per formula unit. Each formula is converted with `to_molecular_formula()` and multiplied by its mol% / 100.
+    ///
+    /// Returns `FormulaError::MolPercentOverflow` if the mol% sum is over 100.
+    ///
+    /// # Example
+    /// ```
+    /// use chemical_formula::prelude::*;
+    /// use approx::assert_abs_diff_eq;
+    ///
+    /// let formula = ChemicalFormula::from_mol_percent(&[
+    ///     (parse_formula("Y2O3").unwrap(), 8.0),
+    ///     (parse_formula("ZrO2").unwrap(), 92.0),
+    /// ])
+    /// .unwrap();
+    ///
+    /// assert_abs_diff_eq!(formula.stoichiometry[&ElementSymbol::Y], 0.16, epsilon = 1e-9);
+    /// assert_abs_diff_eq!(formula.stoichiometry[&ElementSymbol::O], 2.08, epsilon = 1e-9);
+    /// ```
+    pub fn from_mol_percent(
+        components: &[(ChemicalFormula, f64)],
+    ) -> Result<ChemicalFormula, FormulaError> {
+        let mol_ratio_sum: f64 = components.iter().map(|(_, mol_ratio)| mol_ratio).sum();
+
+        if mol_ratio_sum > 100. {
+            return Err(FormulaError::MolPercentOverflow);
+        }
+
+        let mut formula = ChemicalFormula::new();
+
+        for (component, mol_ratio) in components.iter() {
+            let mut component = component.to_molecular_formula()?;
+            component.try_multiply(mol_ratio / 100.)?;
+            formula.add_formula(&component);
+        }
+
+        Ok(formula)
+    }
+
+    /// Iterate over the stoichiometry in atomic number order
+    ///
+    /// # Example
+    /// ```
+    /// use chemical_formula::prelude::*;
+    ///
+    /// let formula = parse_formula("OSiO").unwrap();
+    /// let stoichiometry: Vec<_> = formula.iter_stoichiometry().collect();
+    ///
+    /// assert_eq!(stoichiometry, vec![(ElementSymbol::O, 2.0), (ElementSymbol::Si, 1.0)]);
+    /// ```
+    pub fn iter_stoichiometry(&self) -> impl Iterator<Item = (ElementSymbol, f64)> + '_ {
+        self.stoichiometry
+            .iter()
+            .map(|(element, stoichiometry)| (*element, *stoichiometry))
+    }
+
+    /// Iterate over the wt% in atomic number order
+    ///
+    /// # Example
+    /// ```
+    /// use chemical_formula::prelude::*;
+    ///
+    /// let formula = parse_formula("Pt5wt%Au1wt%/SiO2").unwrap();
+    /// let wt_percent: Vec<_> = formula.iter_wt_percent().collect();
+    ///
+    /// assert_eq!(wt_percent, vec![(ElementSymbol::Pt, 5.0), (ElementSymbol::Au, 1.0)]);
+    /// ```
+    pub fn iter_wt_percent(&self) -> impl Iterator<Item = (ElementSymbol, f64)> + '_ {
+        self.wt_percent
+            .iter()
+            .map(|(element, wt_ratio)| (*element, *wt_ratio))
+    }
+
     /// Add another formula to the current formula
     /// # Arguments
     /// * `formula` - A `ChemicalFormula` to be added
@@ -951,10 +1871,18 @@ impl ChemicalFormula {
     /// assert_abs_diff_eq!(formula.wt_percent[&ElementSymbol::H], 20.0, epsilon = 1e-6);
     /// assert_abs_diff_eq!(formula.wt_percent[&ElementSymbol::N], 40.0, epsilon = 1e-6);
     /// ```
-
+    ///
+    /// # Panics
+    /// Panics if `formula` has an `ElementSymbol::None` entry and the current formula is in strict mode.
     pub fn add_formula(&mut self, formula: &ChemicalFormula) -> &mut Self {
+        self.charge += formula.charge;
+
+        for element in formula.element.iter() {
+            self.insert_element(*element);
+        }
+
         for (element, stoichiometry) in formula.stoichiometry.iter() {
-            self.element.insert(*element);
+            self.insert_element(*element);
             self.stoichiometry
                 .entry(*element)
                 .and_modify(|e| *e += stoichiometry)
@@ -962,7 +1890,7 @@ impl ChemicalFormula {
         }
 
         for (element, wt_ratio) in formula.wt_percent.iter() {
-            self.element.insert(*element);
+            self.insert_element(*element);
 
             self.wt_percent
                 .entry(*element)
@@ -970,11 +1898,28 @@ impl ChemicalFormula {
                 .or_insert(*wt_ratio);
         }
 
+        for (element, at_ratio) in formula.at_percent.iter() {
+            self.insert_element(*element);
+
+            self.at_percent
+                .entry(*element)
+                .and_modify(|e| *e += at_ratio)
+                .or_insert(*at_ratio);
+        }
+
+        for (isotope, stoichiometry) in formula.isotope.iter() {
+            self.isotope
+                .entry(*isotope)
+                .and_modify(|e| *e += stoichiometry)
+                .or_insert(*stoichiometry);
+        }
+
         self
     }
 }
 
 #[cfg(test)]
+#[allow(non_snake_case)]
 mod tests {
 
     use super::*;
@@ -994,11 +1939,11 @@ mod tests {
 
         let molecular_formula = formula.to_molecular_formula().unwrap();
 
-        let O_mol_ratio = molecular_formula
+        let _O_mol_ratio = molecular_formula
             .stoichiometry
             .get(&ElementSymbol::O)
             .unwrap();
-        let H_mol_ratio = molecular_formula
+        let _H_mol_ratio = molecular_formula
             .stoichiometry
             .get(&ElementSymbol::H)
             .unwrap();
@@ -1036,7 +1981,7 @@ mod tests {
 
         let molecular_formula = formula.to_molecular_formula().unwrap();
 
-        let H_mol_ratio = molecular_formula
+        let _H_mol_ratio = molecular_formula
             .stoichiometry
             .get(&ElementSymbol::H)
             .unwrap();
@@ -1079,7 +2024,7 @@ mod tests {
 
         let molecular_formula = formula.to_molecular_formula().unwrap();
 
-        let O_mol_ratio = molecular_formula
+        let _O_mol_ratio = molecular_formula
             .stoichiometry
             .get(&ElementSymbol::O)
             .unwrap();
@@ -1094,6 +2039,70 @@ mod tests {
         assert_abs_diff_eq!(wt_ratio.wt_percent[&ElementSymbol::O], 100., epsilon = 1e-6);
     }
 
+    #[test]
+    fn test_zero_wt_percent() {
+        let mut formula = ChemicalFormula::new();
+
+        formula.add_wt_percent(ElementSymbol::Pt, 0.0);
+        formula.add_element(ElementSymbol::Si, 1.0);
+        formula.add_element(ElementSymbol::O, 2.0);
+
+        let molecular_formula = formula.to_molecular_formula().unwrap();
+
+        assert_eq!(molecular_formula.stoichiometry[&ElementSymbol::Pt], 0.0);
+        assert_eq!(molecular_formula.stoichiometry[&ElementSymbol::Si], 1.0);
+        assert_eq!(molecular_formula.stoichiometry[&ElementSymbol::O], 2.0);
+
+        let mut formula = ChemicalFormula::new();
+
+        formula.add_wt_percent(ElementSymbol::Pt, 0.0);
+        formula.add_wt_percent(ElementSymbol::Au, 2.0);
+        formula.add_element(ElementSymbol::Si, 1.0);
+        formula.add_element(ElementSymbol::O, 2.0);
+
+        let wt_percent = formula.to_wt_percent().unwrap();
+
+        assert_abs_diff_eq!(wt_percent.wt_percent[&ElementSymbol::Pt], 0.0);
+        assert_abs_diff_eq!(
+            wt_percent.wt_percent[&ElementSymbol::Au],
+            2.0,
+            epsilon = 1e-9
+        );
+    }
+
+    #[test]
+    fn test_wt_percent_of_stoichiometric_element() {
+        // 5 wt% of Fe on Fe2O3 adds to the Fe of Fe2O3
+        let mut formula = ChemicalFormula::new();
+
+        formula.add_wt_percent(ElementSymbol::Fe, 5.0);
+        formula.add_element(ElementSymbol::Fe, 2.0);
+        formula.add_element(ElementSymbol::O, 3.0);
+
+        let fe2o3 = 2. * ElementSymbol::Fe.atomic_weight() + 3. * ElementSymbol::O.atomic_weight();
+        let mass = fe2o3 / 0.95;
+
+        let molecular_formula = formula.to_molecular_formula().unwrap();
+        assert_abs_diff_eq!(
+            molecular_formula.stoichiometry[&ElementSymbol::Fe],
+            2. + mass * 0.05 / ElementSymbol::Fe.atomic_weight(),
+            epsilon = 1e-9
+        );
+        assert_abs_diff_eq!(molecular_formula.stoichiometry[&ElementSymbol::O], 3.0);
+
+        let wt_percent = formula.to_wt_percent().unwrap();
+        assert_abs_diff_eq!(
+            wt_percent.wt_percent[&ElementSymbol::O],
+            3. * ElementSymbol::O.atomic_weight() / mass * 100.,
+            epsilon = 1e-9
+        );
+        assert_abs_diff_eq!(
+            wt_percent.wt_percent[&ElementSymbol::Fe],
+            2. * ElementSymbol::Fe.atomic_weight() / mass * 100. + 5.,
+            epsilon = 1e-9
+        );
+    }
+
     #[test]
     fn test_add_formula() {
         let mut formula = ChemicalFormula::new();
@@ -1115,11 +2124,11 @@ mod tests {
 
         let molecular_formula = formula.to_molecular_formula().unwrap();
 
-        let O_mol_ratio = molecular_formula
+        let _O_mol_ratio = molecular_formula
             .stoichiometry
             .get(&ElementSymbol::O)
             .unwrap();
-        let H_mol_ratio = molecular_formula
+        let _H_mol_ratio = molecular_formula
             .stoichiometry
             .get(&ElementSymbol::H)
             .unwrap();
@@ -1143,6 +2152,33 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_multiply_charge() {
+        let mut sulfate = ChemicalFormula::new();
+        sulfate.add_element(ElementSymbol::S, 1.0);
+        sulfate.add_element(ElementSymbol::O, 4.0);
+        sulfate.charge = -2;
+
+        let mut formula = sulfate.clone();
+        formula.multiply(2.0);
+        assert_eq!(formula.charge, -4);
+        assert_abs_diff_eq!(formula.stoichiometry[&ElementSymbol::O], 8.0);
+
+        let mut formula = ChemicalFormula::new();
+        formula.add_formula(sulfate.clone().multiply(3.0));
+        assert_eq!(formula.charge, -6);
+
+        assert_eq!(sulfate.clone().try_multiply(0.5).unwrap().charge, -1);
+        assert!(matches!(
+            sulfate.clone().try_multiply(0.25),
+            Err(FormulaError::NonIntegerCharge(_))
+        ));
+
+        // Normalisation keeps the charge
+        let mol_percent = sulfate.to_mol_percent().unwrap();
+        assert_eq!(mol_percent.charge, -2);
+    }
+
     #[test]
     fn test_multiply_formula() {
         let mut formula = ChemicalFormula::new();
@@ -1160,11 +2196,11 @@ mod tests {
 
         let molecular_formula = formula.to_molecular_formula().unwrap();
 
-        let O_mol_ratio = molecular_formula
+        let _O_mol_ratio = molecular_formula
             .stoichiometry
             .get(&ElementSymbol::O)
             .unwrap();
-        let H_mol_ratio = molecular_formula
+        let _H_mol_ratio = molecular_formula
             .stoichiometry
             .get(&ElementSymbol::H)
             .unwrap();
@@ -1203,7 +2239,7 @@ mod tests {
         let expected_H_after_wt_ratio = 10.;
         let expected_N_after_wt_ratio = 20.;
 
-        formula.multiply_wt_percent(multiplier);
+        formula.multiply_wt_percent(multiplier).unwrap();
 
         assert_abs_diff_eq!(
             formula.wt_percent[&ElementSymbol::H],
@@ -1230,4 +2266,105 @@ mod tests {
             epsilon = 1e-6
         );
     }
+
+    #[test]
+    fn test_element_from_str() {
+        assert_eq!("Pt".parse::<ElementSymbol>().unwrap(), ElementSymbol::Pt);
+        assert_eq!(ElementSymbol::try_from("Og").unwrap(), ElementSymbol::Og);
+
+        let err = "CL".parse::<ElementSymbol>().unwrap_err();
+        assert_eq!(err.symbol, "CL");
+        assert_eq!(err.suggestions, vec![ElementSymbol::Cl]);
+        assert!(err.split.is_empty());
+
+        let err = "CO".parse::<ElementSymbol>().unwrap_err();
+        assert_eq!(err.suggestions, vec![ElementSymbol::Co]);
+        assert_eq!(err.split, vec![ElementSymbol::C, ElementSymbol::O]);
+
+        let err = "Xx".parse::<ElementSymbol>().unwrap_err();
+        assert!(err.suggestions.is_empty());
+        assert!("None".parse::<ElementSymbol>().is_err());
+    }
+
+    #[test]
+    fn test_strict_formula() {
+        let mut formula = ChemicalFormula::new_strict();
+
+        formula.try_add_element(ElementSymbol::O, 1.0).unwrap();
+        assert!(matches!(
+            formula.try_add_element(ElementSymbol::None, 1.0),
+            Err(FormulaError::NoneElement)
+        ));
+        assert!(formula
+            .try_add_wt_percent(ElementSymbol::None, 1.0)
+            .is_err());
+        assert_abs_diff_eq!(formula.molecular_weight().unwrap(), 15.999);
+
+        // Strict mode set after the entry was added
+        let mut formula = ChemicalFormula::new();
+        formula.add_element(ElementSymbol::None, 1.0);
+        formula.strict = true;
+        assert!(formula.molecular_weight().is_err());
+        assert!(formula.to_wt_percent().is_err());
+
+        let mut formula = ChemicalFormula::new();
+        formula.add_element(ElementSymbol::None, 1.0);
+        assert!(formula.molecular_weight().is_ok());
+    }
+
+    #[test]
+    #[should_panic(expected = "ElementSymbol::None in strict formula")]
+    fn test_strict_add_element_panics() {
+        ChemicalFormula::new_strict().add_element(ElementSymbol::None, 1.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "ElementSymbol::None in strict formula")]
+    fn test_strict_add_wt_percent_panics() {
+        ChemicalFormula::new_strict().add_wt_percent(ElementSymbol::None, 1.0);
+    }
+
+    #[test]
+    fn test_deterministic_order() {
+        let mut formula = ChemicalFormula::new();
+
+        formula.add_element(ElementSymbol::U, 1.0);
+        formula.add_element(ElementSymbol::H, 2.0);
+        formula.add_wt_percent(ElementSymbol::Pt, 1.0);
+        formula.add_element(ElementSymbol::O, 3.0);
+        formula.add_wt_percent(ElementSymbol::Na, 2.0);
+
+        let elements: Vec<ElementSymbol> = formula.iter_stoichiometry().map(|(e, _)| e).collect();
+        assert_eq!(
+            elements,
+            vec![ElementSymbol::H, ElementSymbol::O, ElementSymbol::U]
+        );
+
+        let elements: Vec<ElementSymbol> = formula.iter_wt_percent().map(|(e, _)| e).collect();
+        assert_eq!(elements, vec![ElementSymbol::Na, ElementSymbol::Pt]);
+
+        assert_eq!(
+            formula.element,
+            vec![
+                ElementSymbol::U,
+                ElementSymbol::H,
+                ElementSymbol::Pt,
+                ElementSymbol::O,
+                ElementSymbol::Na
+            ]
+        );
+
+        let wt_percent = formula.to_wt_percent().unwrap();
+        let elements: Vec<ElementSymbol> = wt_percent.iter_wt_percent().map(|(e, _)| e).collect();
+        assert_eq!(
+            elements,
+            vec![
+                ElementSymbol::H,
+                ElementSymbol::O,
+                ElementSymbol::Na,
+                ElementSymbol::Pt,
+                ElementSymbol::U
+            ]
+        );
+    }
 }
@@ -0,0 +1,323 @@
+//! # Empirical formula
+//!
+//! This module determines the empirical formula from a measured composition,
+//! e.g. the wt% of C, H and N from combustion analysis or the wt% of the metals from ICP.
+//!
+//! `ChemicalFormula::to_empirical_formula` converts the wt% of each element to mole ratios
+//! and finds the smallest multiplier that brings every ratio within a tolerance of an integer.
+//! With `EmpiricalFormulaOptions::reference`, the ratios are normalised to a fixed count of a reference element,
+//! e.g. per 4 O, and the stoichiometry is given as fractions with the multiplier as the denominator.
+//! `EmpiricalFormula` reports the residuals of the fit in mole ratio and in wt%.
+//!
+//! # Example
+//! ```
+//! use chemical_formula::prelude::*;
+//! use chemical_formula::empirical::EmpiricalFormulaOptions;
+//!
+//! let mut measured = ChemicalFormula::new();
+//! measured
+//!     .add_wt_percent(ElementSymbol::Fe, 69.94)
+//!     .add_wt_percent(ElementSymbol::O, 30.06);
+//!
+//! let empirical = measured
+//!     .to_empirical_formula(&EmpiricalFormulaOptions::new())
+//!     .unwrap();
+//!
+//! assert_eq!(empirical.formula.to_string(), "Fe2O3");
+//! assert_eq!(empirical.multiplier, 2);
+//! ```
+
+use crate::element::{ChemicalFormula, ElementSymbol, FormulaError};
+use std::collections::BTreeMap;
+
+/// Options of `ChemicalFormula::to_empirical_formula`
+///
+/// # Example
+/// ```
+/// use chemical_formula::prelude::*;
+/// use chemical_formula::empirical::EmpiricalFormulaOptions;
+///
+/// let mut options = EmpiricalFormulaOptions::new();
+/// options.tolerance(0.05).max_multiplier(20).reference(ElementSymbol::O, 4.0);
+///
+/// assert_eq!(options.reference, Some((ElementSymbol::O, 4.0)));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct EmpiricalFormulaOptions {
+    pub tolerance: f64,
+    pub max_multiplier: u32,
+    pub reference: Option<(ElementSymbol, f64)>,
+}
+
+impl Default for EmpiricalFormulaOptions {
+    fn default() -> Self {
+        EmpiricalFormulaOptions::new()
+    }
+}
+
+impl EmpiricalFormulaOptions {
+    /// Create options with a tolerance of `0.1`, multipliers up to `10` and no reference element
+    pub fn new() -> Self {
+        EmpiricalFormulaOptions {
+            tolerance: 0.1,
+            max_multiplier: 10,
+            reference: None,
+        }
+    }
+
+    /// Set the largest deviation of a multiplied mole ratio from an integer
+    pub fn tolerance(&mut self, tolerance: f64) -> &mut Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Set the largest multiplier, which is the largest denominator if a reference element is given
+    pub fn max_multiplier(&mut self, max_multiplier: u32) -> &mut Self {
+        self.max_multiplier = max_multiplier;
+        self
+    }
+
+    /// Normalise the mole ratios to `count` atoms of `element`, e.g. per 4 O
+    pub fn reference(&mut self, element: ElementSymbol, count: f64) -> &mut Self {
+        self.reference = Some((element, count));
+        self
+    }
+}
+
+/// Empirical formula returned by `ChemicalFormula::to_empirical_formula`
+#[derive(Debug, Clone)]
+pub struct EmpiricalFormula {
+    pub formula: ChemicalFormula,
+    /// Multiplier of the mole ratios, or the denominator of the stoichiometry if a reference element is given
+    pub multiplier: u32,
+    /// Measured mole ratio of each element on the same basis as `formula`
+    pub ratios: BTreeMap<ElementSymbol, f64>,
+    /// Measured mole ratio minus the stoichiometry of `formula`
+    pub residuals: BTreeMap<ElementSymbol, f64>,
+    /// Measured wt% minus the wt% calculated from `formula`
+    pub wt_residuals: BTreeMap<ElementSymbol, f64>,
+}
+
+impl ChemicalFormula {
+    /// Determine the empirical formula from the measured composition
+    ///
+    /// The composition is taken from the wt% terms, e.g. from `add_wt_percent`, which do not have to sum to 100.
+    /// Elements that are not measured, e.g. O in CHN analysis, are not in the result
+    /// and can be added by difference with `add_wt_percent`.
+    /// A formula with stoichiometry or at% terms is converted with `to_molecular_formula()` first.
+    ///
+    /// The multipliers from 1 to `max_multiplier` are tried in order,
+    /// and the first one that brings every mole ratio within `tolerance` of a non-zero integer is taken.
+    ///
+    /// Returns `FormulaError::NoFormula` for an empty composition,
+    /// `FormulaError::ElementNotInFormula` if the reference element is not measured,
+    /// and `FormulaError::NoEmpiricalFormula` if no multiplier fits within the tolerance.
+    ///
+    /// # Example
+    /// ```
+    /// use chemical_formula::prelude::*;
+    /// use chemical_formula::empirical::EmpiricalFormulaOptions;
+    /// use approx::assert_abs_diff_eq;
+    ///
+    /// // ICP of a layered oxide with O by difference
+    /// let mut measured = ChemicalFormula::new();
+    /// measured
+    ///     .add_wt_percent(ElementSymbol::Li, 7.13)
+    ///     .add_wt_percent(ElementSymbol::Ni, 48.27)
+    ///     .add_wt_percent(ElementSymbol::Co, 6.06)
+    ///     .add_wt_percent(ElementSymbol::Mn, 5.65)
+    ///     .add_wt_percent(ElementSymbol::O, 32.89);
+    ///
+    /// let mut options = EmpiricalFormulaOptions::new();
+    /// options.reference(ElementSymbol::O, 2.0);
+    ///
+    /// let empirical = measured.to_empirical_formula(&options).unwrap();
+    ///
+    /// assert_eq!(empirical.formula.to_string(), "LiNi0.8Co0.1Mn0.1O2");
+    /// assert_eq!(empirical.multiplier, 10);
+    /// assert_abs_diff_eq!(empirical.wt_residuals[&ElementSymbol::Ni], 0.0, epsilon = 0.05);
+    /// ```
+    pub fn to_empirical_formula(
+        &self,
+        options: &EmpiricalFormulaOptions,
+    ) -> Result<EmpiricalFormula, FormulaError> {
+        let measured = self.measured_composition()?;
+        let measured_wt_percent = measured.wt_percent;
+
+        let moles: BTreeMap<ElementSymbol, f64> = measured
+            .moles
+            .into_iter()
+            .filter(|(_, amount)| *amount > 0.)
+            .collect();
+
+        if moles.is_empty() {
+            return Err(FormulaError::NoFormula);
+        }
+
+        let basis = match options.reference {
+            Some((element, count)) => match moles.get(&element) {
+                Some(amount) => amount / count,
+                None => return Err(FormulaError::ElementNotInFormula(element)),
+            },
+            None => moles.values().copied().fold(f64::INFINITY, f64::min),
+        };
+
+        let ratios: BTreeMap<ElementSymbol, f64> = moles
+            .iter()
+            .map(|(element, amount)| (*element, amount / basis))
+            .collect();
+
+        let multiplier = (1..=options.max_multiplier)
+            .find(|multiplier| {
+                ratios.values().all(|ratio| {
+                    let scaled = ratio * *multiplier as f64;
+                    scaled.round() >= 1. && (scaled - scaled.round()).abs() <= options.tolerance
+                })
+            })
+            .ok_or(FormulaError::NoEmpiricalFormula)?;
+
+        // Integer counts without a reference, and fractions with the multiplier as the denominator with a reference
+        let scale = match options.reference {
+            Some(_) => 1.,
+            None => multiplier as f64,
+        };
+
+        // Keep the element order of the measured composition
+        let mut formula = ChemicalFormula::new();
+        for element in self.element.iter() {
+            if let Some(ratio) = ratios.get(element) {
+                let count = (ratio * multiplier as f64).round() / multiplier as f64 * scale;
+                formula.add_element(*element, count);
+            }
+        }
+
+        let ratios: BTreeMap<ElementSymbol, f64> = ratios
+            .into_iter()
+            .map(|(element, ratio)| (element, ratio * scale))
+            .collect();
+        let residuals = ratios
+            .iter()
+            .map(|(element, ratio)| (*element, ratio - formula.stoichiometry[element]))
+            .collect();
+
+        let calculated_wt_percent = formula.to_wt_percent()?.wt_percent;
+        let wt_residuals = measured_wt_percent
+            .iter()
+            .filter_map(|(element, wt_ratio)| {
+                calculated_wt_percent
+                    .get(element)
+                    .map(|calculated| (*element, wt_ratio - calculated))
+            })
+            .collect();
+
+        Ok(EmpiricalFormula {
+            formula,
+            multiplier,
+            ratios,
+            residuals,
+            wt_residuals,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_formula;
+    use approx::assert_abs_diff_eq;
+
+    fn measured(wt_percent: &[(ElementSymbol, f64)]) -> ChemicalFormula {
+        let mut formula = ChemicalFormula::new();
+        for (element, wt_ratio) in wt_percent {
+            formula.add_wt_percent(*element, *wt_ratio);
+        }
+        formula
+    }
+
+    #[test]
+    fn test_empirical_formula() {
+        let options = EmpiricalFormulaOptions::new();
+
+        // CHN analysis of glucose with O by difference
+        let glucose = measured(&[
+            (ElementSymbol::C, 40.00),
+            (ElementSymbol::H, 6.71),
+            (ElementSymbol::O, 53.29),
+        ]);
+        let empirical = glucose.to_empirical_formula(&options).unwrap();
+        assert_eq!(empirical.formula.to_string(), "CH2O");
+        assert_eq!(empirical.multiplier, 1);
+        for residual in empirical.residuals.values() {
+            assert!(residual.abs() < 0.01);
+        }
+
+        // The measured wt% do not have to sum to 100
+        let caffeine = measured(&[
+            (ElementSymbol::C, 49.48),
+            (ElementSymbol::H, 5.19),
+            (ElementSymbol::N, 28.85),
+        ]);
+        let empirical = caffeine.to_empirical_formula(&options).unwrap();
+        assert_eq!(empirical.formula.to_string(), "C4H5N2");
+        assert_abs_diff_eq!(empirical.ratios[&ElementSymbol::N], 2.0);
+
+        // The stoichiometry of a formula is reduced
+        let empirical = parse_formula("C6H12O6")
+            .unwrap()
+            .to_empirical_formula(&options)
+            .unwrap();
+        assert_eq!(empirical.formula.to_string(), "CH2O");
+        for residual in empirical.wt_residuals.values() {
+            assert_abs_diff_eq!(*residual, 0.0, epsilon = 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_empirical_formula_reference() {
+        let magnetite = measured(&[(ElementSymbol::Fe, 72.36), (ElementSymbol::O, 27.64)]);
+
+        let empirical = magnetite
+            .to_empirical_formula(&EmpiricalFormulaOptions::new())
+            .unwrap();
+        assert_eq!(empirical.formula.to_string(), "Fe3O4");
+
+        let mut options = EmpiricalFormulaOptions::new();
+        options.reference(ElementSymbol::O, 1.0);
+        let empirical = magnetite.to_empirical_formula(&options).unwrap();
+        assert_eq!(empirical.formula.to_string(), "Fe0.75O");
+        assert_eq!(empirical.multiplier, 4);
+        assert_abs_diff_eq!(empirical.ratios[&ElementSymbol::O], 1.0);
+        assert_abs_diff_eq!(
+            empirical.residuals[&ElementSymbol::Fe],
+            empirical.ratios[&ElementSymbol::Fe] - 0.75
+        );
+    }
+
+    #[test]
+    fn test_empirical_formula_errors() {
+        let options = EmpiricalFormulaOptions::new();
+
+        assert!(matches!(
+            ChemicalFormula::new().to_empirical_formula(&options),
+            Err(FormulaError::NoFormula)
+        ));
+
+        let mut reference = EmpiricalFormulaOptions::new();
+        reference.reference(ElementSymbol::O, 4.0);
+        assert!(matches!(
+            measured(&[(ElementSymbol::C, 40.0), (ElementSymbol::H, 6.7)])
+                .to_empirical_formula(&reference),
+            Err(FormulaError::ElementNotInFormula(ElementSymbol::O))
+        ));
+
+        // C:H = 1:1.37 does not fit within the tolerance up to 2
+        let mut strict = EmpiricalFormulaOptions::new();
+        strict.tolerance(0.01).max_multiplier(2);
+        assert!(matches!(
+            parse_formula("C8H11")
+                .unwrap()
+                .to_empirical_formula(&strict),
+            Err(FormulaError::NoEmpiricalFormula)
+        ));
+    }
+}
@@ -0,0 +1,1530 @@
+//! # Isotopes
+//!
+//! This module contains the `Isotope` type and the table of isotopic masses and natural abundances.
+//!
+//! An isotope is written in a formula with a mass-number prefix, e.g. `^13CO2`, `[18O]` or `^15N`,
+//! and deuterium and tritium can be written as `D` and `T`.
+//! `ChemicalFormula::molecular_weight` uses the isotopic mass for the labelled atoms and the
+//! standard atomic weight for the rest.
+//!
+//! For mass spectrometry, `ChemicalFormula` also provides `monoisotopic_mass()`, `nominal_mass()`,
+//! `most_abundant_mass()` and `mz(charge)`, which are calculated from the isotope table `ISOTOPES`.
+//! `isotope_pattern()` calculates the isotopic distribution as a list of `IsotopePeak`.
+//!
+//! # Example
+//! ```
+//! use chemical_formula::prelude::*;
+//! use approx::assert_abs_diff_eq;
+//!
+//! let formula = parse_formula("D2O").unwrap();
+//!
+//! assert_eq!(formula.isotope[&Isotope::DEUTERIUM], 2.0);
+//! assert_abs_diff_eq!(formula.molecular_weight().unwrap(), 20.027, epsilon = 1e-3);
+//! ```
+
+use crate::element::{ChemicalFormula, ElementSymbol, FormulaError, ELECTRON_MASS};
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::fmt;
+
+/// Isotope of an element, given by the element and the mass number
+///
+/// `Display` writes deuterium and tritium as `D` and `T`, and other isotopes as `^13C`.
+///
+/// # Example
+/// ```
+/// use chemical_formula::prelude::*;
+/// use approx::assert_abs_diff_eq;
+///
+/// let c13 = Isotope::new(ElementSymbol::C, 13);
+///
+/// assert_eq!(c13.to_string(), "^13C");
+/// assert_abs_diff_eq!(c13.mass().unwrap(), 13.00335483507, epsilon = 1e-9);
+/// assert_abs_diff_eq!(c13.abundance().unwrap(), 0.0107, epsilon = 1e-9);
+///
+/// assert!(Isotope::new(ElementSymbol::C, 5).mass().is_none());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Isotope {
+    pub element: ElementSymbol,
+    pub mass_number: u16,
+}
+
+impl Isotope {
+    /// Deuterium, `D` or `2H`
+    pub const DEUTERIUM: Isotope = Isotope::new(ElementSymbol::H, 2);
+
+    /// Tritium, `T` or `3H`
+    pub const TRITIUM: Isotope = Isotope::new(ElementSymbol::H, 3);
+
+    pub const fn new(element: ElementSymbol, mass_number: u16) -> Self {
+        Isotope {
+            element,
+            mass_number,
+        }
+    }
+
+    /// Mass and abundance of the isotope, `None` if the isotope is not in `ISOTOPES`
+    pub fn data(&self) -> Option<&'static IsotopeData> {
+        self.element
+            .isotopes()
+            .iter()
+            .find(|data| data.mass_number == self.mass_number)
+    }
+
+    /// Isotopic mass in unified atomic mass units
+    pub fn mass(&self) -> Option<f64> {
+        self.data().map(|data| data.mass)
+    }
+
+    /// Natural abundance as a fraction (0.0 - 1.0)
+    pub fn abundance(&self) -> Option<f64> {
+        self.data().map(|data| data.abundance)
+    }
+}
+
+impl fmt::Display for Isotope {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Isotope::DEUTERIUM => write!(f, "D"),
+            Isotope::TRITIUM => write!(f, "T"),
+            _ => write!(f, "^{}{}", self.mass_number, self.element),
+        }
+    }
+}
+
+/// Mass and natural abundance of an isotope
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IsotopeData {
+    pub mass_number: u16,
+    /// Isotopic mass in unified atomic mass units
+    pub mass: f64,
+    /// Natural abundance as a fraction (0.0 - 1.0)
+    pub abundance: f64,
+}
+
+impl ElementSymbol {
+    /// Isotopes of the element in order of mass number
+    ///
+    /// The list is empty if the element is not in `ISOTOPES`.
+    ///
+    /// # Example
+    /// ```
+    /// use chemical_formula::prelude::*;
+    ///
+    /// let mass_numbers: Vec<_> = ElementSymbol::O
+    ///     .isotopes()
+    ///     .iter()
+    ///     .map(|isotope| isotope.mass_number)
+    ///     .collect();
+    ///
+    /// assert_eq!(mass_numbers, vec![16, 17, 18]);
+    /// ```
+    pub fn isotopes(&self) -> &'static [IsotopeData] {
+        ISOTOPES
+            .get(self)
+            .map_or(&[], |isotopes| isotopes.as_slice())
+    }
+
+    /// Most abundant isotope of the element
+    ///
+    /// `None` if the element has no naturally abundant isotope in `ISOTOPES`, e.g. Tc.
+    ///
+    /// # Example
+    /// ```
+    /// use chemical_formula::prelude::*;
+    ///
+    /// assert_eq!(ElementSymbol::Pt.most_abundant_isotope().unwrap().mass_number, 195);
+    /// assert!(ElementSymbol::Tc.most_abundant_isotope().is_none());
+    /// ```
+    pub fn most_abundant_isotope(&self) -> Option<&'static IsotopeData> {
+        self.isotopes()
+            .iter()
+            .filter(|isotope| isotope.abundance > 0.)
+            .max_by(|a, b| a.abundance.total_cmp(&b.abundance))
+    }
+}
+
+/// Stoichiometry of the unlabelled atoms and of the isotope-labelled atoms of a formula
+struct Composition {
+    unlabelled: Vec<(ElementSymbol, f64)>,
+    labelled: Vec<(Isotope, f64)>,
+}
+
+impl Composition {
+    fn new(formula: &ChemicalFormula) -> Result<Self, FormulaError> {
+        formula.check_strict()?;
+
+        let formula = if formula.wt_percent.is_empty() && formula.at_percent.is_empty() {
+            formula.clone()
+        } else {
+            formula.to_molecular_formula()?
+        };
+
+        let mut unlabelled = Vec::new();
+        for (element, stoichiometry) in formula.iter_stoichiometry() {
+            let labelled: f64 = formula
+                .isotope
+                .iter()
+                .filter(|(isotope, _)| isotope.element == element)
+                .map(|(_, x)| x)
+                .sum();
+
+            let stoichiometry = stoichiometry - labelled;
+            if stoichiometry.abs() > 1e-9 {
+                unlabelled.push((element, stoichiometry));
+            }
+        }
+
+        Ok(Composition {
+            unlabelled,
+            labelled: formula.isotope.into_iter().collect(),
+        })
+    }
+
+    /// Sum of a per-isotope quantity, using the most abundant isotope for the unlabelled atoms
+    fn sum(&self, value: impl Fn(&IsotopeData) -> f64) -> Result<f64, FormulaError> {
+        let mut total = 0.0;
+
+        for (element, stoichiometry) in self.unlabelled.iter() {
+            let isotope = element
+                .most_abundant_isotope()
+                .ok_or(FormulaError::NoIsotopeData(*element))?;
+            total += value(isotope) * stoichiometry;
+        }
+
+        for (isotope, stoichiometry) in self.labelled.iter() {
+            let data = isotope
+                .data()
+                .ok_or(FormulaError::UnknownIsotope(*isotope))?;
+            total += value(data) * stoichiometry;
+        }
+
+        Ok(total)
+    }
+
+    /// Distribution over nominal masses of all isotopologues
+    fn distribution(&self) -> Result<Distribution, FormulaError> {
+        let mut distribution = Distribution::unit();
+
+        for (element, stoichiometry) in self.unlabelled.iter() {
+            if element.most_abundant_isotope().is_none() {
+                return Err(FormulaError::NoIsotopeData(*element));
+            }
+
+            let count = atom_count(*element, *stoichiometry)?;
+            distribution = distribution.convolve(&Distribution::element(*element).pow(count));
+        }
+
+        for (isotope, stoichiometry) in self.labelled.iter() {
+            let data = isotope
+                .data()
+                .ok_or(FormulaError::UnknownIsotope(*isotope))?;
+
+            let count = atom_count(isotope.element, *stoichiometry)?;
+            distribution = distribution.convolve(&Distribution::isotope(data).pow(count));
+        }
+
+        Ok(distribution)
+    }
+
+    /// Isotopologue peaks with their exact masses
+    ///
+    /// Peaks below `prune` times the most abundant peak are dropped after each convolution.
+    fn fine_distribution(&self, prune: f64) -> Result<Vec<IsotopePeak>, FormulaError> {
+        let mut peaks = vec![IsotopePeak {
+            mass: 0.0,
+            abundance: 1.0,
+        }];
+
+        for (element, stoichiometry) in self.unlabelled.iter() {
+            let isotopes: Vec<_> = element
+                .isotopes()
+                .iter()
+                .filter(|isotope| isotope.abundance > 0.)
+                .map(|isotope| IsotopePeak {
+                    mass: isotope.mass,
+                    abundance: isotope.abundance,
+                })
+                .collect();
+            if isotopes.is_empty() {
+                return Err(FormulaError::NoIsotopeData(*element));
+            }
+
+            let count = atom_count(*element, *stoichiometry)?;
+            peaks = convolve_peaks(&peaks, &pow_peaks(&isotopes, count, prune), prune);
+        }
+
+        for (isotope, stoichiometry) in self.labelled.iter() {
+            let data = isotope
+                .data()
+                .ok_or(FormulaError::UnknownIsotope(*isotope))?;
+            let mass = data.mass * atom_count(isotope.element, *stoichiometry)? as f64;
+
+            for peak in peaks.iter_mut() {
+                peak.mass += mass;
+            }
+        }
+
+        Ok(peaks)
+    }
+}
+
+/// Convolve two sets of isotopologue peaks
+///
+/// Peaks of the same mass are combined and peaks below `prune` times the most abundant peak are dropped.
+fn convolve_peaks(a: &[IsotopePeak], b: &[IsotopePeak], prune: f64) -> Vec<IsotopePeak> {
+    let mut peaks = Vec::with_capacity(a.len() * b.len());
+    for x in a.iter() {
+        for y in b.iter() {
+            peaks.push(IsotopePeak {
+                mass: x.mass + y.mass,
+                abundance: x.abundance * y.abundance,
+            });
+        }
+    }
+    peaks.sort_by(|x, y| x.mass.total_cmp(&y.mass));
+
+    // Isotopologues with the same composition are reached through different orders of the isotopes
+    let mut combined: Vec<IsotopePeak> = Vec::with_capacity(peaks.len());
+    for peak in peaks {
+        match combined.last_mut() {
+            Some(last) if peak.mass - last.mass < 1e-6 => last.abundance += peak.abundance,
+            _ => combined.push(peak),
+        }
+    }
+
+    let max = combined
+        .iter()
+        .map(|peak| peak.abundance)
+        .fold(0.0, f64::max);
+    combined.retain(|peak| peak.abundance >= max * prune);
+    combined
+}
+
+fn pow_peaks(peaks: &[IsotopePeak], mut n: u32, prune: f64) -> Vec<IsotopePeak> {
+    let mut result = vec![IsotopePeak {
+        mass: 0.0,
+        abundance: 1.0,
+    }];
+    let mut base = peaks.to_vec();
+
+    while n > 0 {
+        if n & 1 == 1 {
+            result = convolve_peaks(&result, &base, prune);
+        }
+        n >>= 1;
+        if n > 0 {
+            base = convolve_peaks(&base, &base, prune);
+        }
+    }
+
+    result
+}
+
+/// Merge peaks closer than `mass / resolution` into their abundance-weighted mean
+fn merge_peaks(peaks: Vec<IsotopePeak>, resolution: f64) -> Vec<IsotopePeak> {
+    let mut merged: Vec<IsotopePeak> = Vec::with_capacity(peaks.len());
+
+    for peak in peaks {
+        match merged.last_mut() {
+            Some(last) if peak.mass - last.mass < last.mass / resolution => {
+                let abundance = last.abundance + peak.abundance;
+                last.mass = (last.mass * last.abundance + peak.mass * peak.abundance) / abundance;
+                last.abundance = abundance;
+            }
+            _ => merged.push(peak),
+        }
+    }
+
+    merged
+}
+
+/// Number of atoms of a stoichiometry, which must be a non-negative integer
+fn atom_count(element: ElementSymbol, stoichiometry: f64) -> Result<u32, FormulaError> {
+    let count = stoichiometry.round();
+
+    if count < 0. || count > u32::MAX as f64 || (stoichiometry - count).abs() > 1e-9 {
+        return Err(FormulaError::NonIntegerStoichiometry(element));
+    }
+
+    Ok(count as u32)
+}
+
+/// Isotopic distribution binned by nominal mass
+///
+/// `peaks[i]` holds the abundance and the abundance-weighted mass of nominal mass `nominal_mass + i`.
+#[derive(Debug, Clone)]
+struct Distribution {
+    nominal_mass: u32,
+    peaks: Vec<(f64, f64)>,
+}
+
+impl Distribution {
+    /// Relative abundance below which the peaks at either end are dropped
+    const TRIM_THRESHOLD: f64 = 1e-16;
+
+    fn unit() -> Self {
+        Distribution {
+            nominal_mass: 0,
+            peaks: vec![(1.0, 0.0)],
+        }
+    }
+
+    fn element(element: ElementSymbol) -> Self {
+        let isotopes: Vec<_> = element
+            .isotopes()
+            .iter()
+            .filter(|isotope| isotope.abundance > 0.)
+            .collect();
+        let nominal_mass = isotopes
+            .first()
+            .map_or(0, |isotope| isotope.mass_number as u32);
+        let mut peaks = Vec::new();
+
+        for isotope in isotopes {
+            let index = (isotope.mass_number as u32 - nominal_mass) as usize;
+            peaks.resize(index + 1, (0.0, 0.0));
+            peaks[index] = (isotope.abundance, isotope.abundance * isotope.mass);
+        }
+
+        Distribution {
+            nominal_mass,
+            peaks,
+        }
+    }
+
+    fn isotope(data: &IsotopeData) -> Self {
+        Distribution {
+            nominal_mass: data.mass_number as u32,
+            peaks: vec![(1.0, data.mass)],
+        }
+    }
+
+    fn convolve(&self, other: &Distribution) -> Distribution {
+        let mut peaks = vec![(0.0, 0.0); self.peaks.len() + other.peaks.len() - 1];
+
+        for (i, (a, a_mass)) in self.peaks.iter().enumerate() {
+            for (j, (b, b_mass)) in other.peaks.iter().enumerate() {
+                peaks[i + j].0 += a * b;
+                peaks[i + j].1 += a_mass * b + b_mass * a;
+            }
+        }
+
+        let mut distribution = Distribution {
+            nominal_mass: self.nominal_mass + other.nominal_mass,
+            peaks,
+        };
+        distribution.trim();
+        distribution
+    }
+
+    fn pow(&self, mut n: u32) -> Distribution {
+        let mut result = Distribution::unit();
+        let mut base = self.clone();
+
+        while n > 0 {
+            if n & 1 == 1 {
+                result = result.convolve(&base);
+            }
+            n >>= 1;
+            if n > 0 {
+                base = base.convolve(&base);
+            }
+        }
+
+        result
+    }
+
+    fn trim(&mut self) {
+        let max = self.peaks.iter().map(|(a, _)| *a).fold(0.0, f64::max);
+        let threshold = max * Distribution::TRIM_THRESHOLD;
+
+        let end = self
+            .peaks
+            .iter()
+            .rposition(|(a, _)| *a > threshold)
+            .map_or(1, |i| i + 1);
+        self.peaks.truncate(end);
+
+        let start = self
+            .peaks
+            .iter()
+            .position(|(a, _)| *a > threshold)
+            .unwrap_or(0);
+        self.peaks.drain(..start);
+        self.nominal_mass += start as u32;
+    }
+
+    /// Peaks at the abundance-weighted mass of each nominal mass
+    fn peaks(&self) -> Vec<IsotopePeak> {
+        self.peaks
+            .iter()
+            .filter(|(abundance, _)| *abundance > 0.)
+            .map(|(abundance, mass)| IsotopePeak {
+                mass: mass / abundance,
+                abundance: *abundance,
+            })
+            .collect()
+    }
+
+    /// Abundance-weighted mass of the most abundant nominal mass
+    fn most_abundant_mass(&self) -> f64 {
+        let (abundance, mass) = self
+            .peaks
+            .iter()
+            .max_by(|a, b| a.0.total_cmp(&b.0))
+            .copied()
+            .unwrap_or((1.0, 0.0));
+
+        mass / abundance
+    }
+}
+
+/// Peak of an isotope pattern
+///
+/// `abundance` is the fraction of all isotopologues that fall in the peak (0.0 - 1.0).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IsotopePeak {
+    pub mass: f64,
+    pub abundance: f64,
+}
+
+/// Options of `ChemicalFormula::isotope_pattern`
+///
+/// * `fine` - Whether every isotopologue is a separate peak (fine structure), or the isotopologues are
+///   grouped by nominal mass (coarse structure). The default is `true`.
+/// * `threshold` - Peaks below this fraction of the most abundant peak are pruned. The default is `1e-6`.
+/// * `resolution` - If set, peaks closer than `mass / resolution` are merged into one peak at their
+///   abundance-weighted mean mass. The default is `None`.
+///
+/// # Example
+/// ```
+/// use chemical_formula::isotope::IsotopePatternOptions;
+///
+/// let mut options = IsotopePatternOptions::new();
+/// options.fine(false).threshold(1e-3).resolution(10000.);
+///
+/// assert_eq!(options.resolution, Some(10000.));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct IsotopePatternOptions {
+    pub fine: bool,
+    pub threshold: f64,
+    pub resolution: Option<f64>,
+}
+
+impl Default for IsotopePatternOptions {
+    fn default() -> Self {
+        IsotopePatternOptions::new()
+    }
+}
+
+impl IsotopePatternOptions {
+    /// Create options for the fine structure with a threshold of `1e-6` and no merging
+    pub fn new() -> Self {
+        IsotopePatternOptions {
+            fine: true,
+            threshold: 1e-6,
+            resolution: None,
+        }
+    }
+
+    /// Set whether the fine structure is calculated
+    pub fn fine(&mut self, fine: bool) -> &mut Self {
+        self.fine = fine;
+        self
+    }
+
+    /// Set the pruning threshold relative to the most abundant peak
+    pub fn threshold(&mut self, threshold: f64) -> &mut Self {
+        self.threshold = threshold;
+        self
+    }
+
+    /// Set the resolving power `mass / Δmass` at which peaks are merged
+    pub fn resolution(&mut self, resolution: f64) -> &mut Self {
+        self.resolution = Some(resolution);
+        self
+    }
+}
+
+impl ChemicalFormula {
+    /// Calculate the monoisotopic mass of the formula
+    ///
+    /// The mass of the most abundant isotope is used for each element, and the isotopic mass for the labelled atoms.
+    /// The electron mass is not included. Please use `mz()` for ions.
+    ///
+    /// Returns `FormulaError::NoIsotopeData` for an element without a naturally abundant isotope, e.g. Tc.
+    ///
+    /// # Example
+    /// ```
+    /// use chemical_formula::prelude::*;
+    /// use approx::assert_abs_diff_eq;
+    ///
+    /// let formula = parse_formula("C6H12O6").unwrap();
+    /// assert_abs_diff_eq!(formula.monoisotopic_mass().unwrap(), 180.06339, epsilon = 1e-5);
+    ///
+    /// let formula = parse_formula("^13C6H12O6").unwrap();
+    /// assert_abs_diff_eq!(formula.monoisotopic_mass().unwrap(), 186.08352, epsilon = 1e-5);
+    /// ```
+    pub fn monoisotopic_mass(&self) -> Result<f64, FormulaError> {
+        Composition::new(self)?.sum(|isotope| isotope.mass)
+    }
+
+    /// Calculate the nominal mass of the formula
+    ///
+    /// The mass number of the most abundant isotope is used for each element,
+    /// and the mass number for the labelled atoms.
+    ///
+    /// # Example
+    /// ```
+    /// use chemical_formula::prelude::*;
+    ///
+    /// assert_eq!(parse_formula("C6H12O6").unwrap().nominal_mass().unwrap(), 180.0);
+    /// assert_eq!(parse_formula("D2O").unwrap().nominal_mass().unwrap(), 20.0);
+    /// ```
+    pub fn nominal_mass(&self) -> Result<f64, FormulaError> {
+        Composition::new(self)?.sum(|isotope| isotope.mass_number as f64)
+    }
+
+    /// Calculate the mass of the most abundant peak of the isotopic distribution
+    ///
+    /// The isotopologues are grouped by nominal mass, and the abundance-weighted mean mass of the most abundant group
+    /// is returned. For small molecules this is the monoisotopic mass. For larger molecules or elements such as
+    /// Br or Pt the most abundant peak contains heavier isotopes.
+    ///
+    /// The stoichiometry must be integer. Otherwise `FormulaError::NonIntegerStoichiometry` is returned.
+    ///
+    /// # Example
+    /// ```
+    /// use chemical_formula::prelude::*;
+    /// use approx::assert_abs_diff_eq;
+    ///
+    /// let formula = parse_formula("H2O").unwrap();
+    /// assert_abs_diff_eq!(
+    ///     formula.most_abundant_mass().unwrap(),
+    ///     formula.monoisotopic_mass().unwrap(),
+    ///     epsilon = 1e-3
+    /// );
+    ///
+    /// // 79Br81Br is the most abundant isotopologue of Br2
+    /// let formula = parse_formula("Br2").unwrap();
+    /// assert_abs_diff_eq!(formula.most_abundant_mass().unwrap(), 159.83, epsilon = 1e-2);
+    /// ```
+    pub fn most_abundant_mass(&self) -> Result<f64, FormulaError> {
+        Ok(Composition::new(self)?.distribution()?.most_abundant_mass())
+    }
+
+    /// Calculate the m/z of the formula as an ion
+    ///
+    /// The formula is the ion itself, e.g. `C6H13O6` for [M+H]+ of glucose, and `charge` is its charge state.
+    /// The monoisotopic mass is corrected by the electron mass and divided by the absolute charge.
+    ///
+    /// Returns `FormulaError::ZeroCharge` if `charge` is zero.
+    ///
+    /// # Example
+    /// ```
+    /// use chemical_formula::prelude::*;
+    /// use approx::assert_abs_diff_eq;
+    ///
+    /// let formula = parse_formula("C6H13O6").unwrap();
+    /// assert_abs_diff_eq!(formula.mz(1).unwrap(), 181.07066, epsilon = 1e-5);
+    ///
+    /// let formula = parse_formula("C6H14O6").unwrap();
+    /// assert_abs_diff_eq!(formula.mz(2).unwrap(), 91.03897, epsilon = 1e-5);
+    /// ```
+    pub fn mz(&self, charge: i32) -> Result<f64, FormulaError> {
+        if charge == 0 {
+            return Err(FormulaError::ZeroCharge);
+        }
+
+        Ok((self.monoisotopic_mass()? - charge as f64 * ELECTRON_MASS)
+            / charge.unsigned_abs() as f64)
+    }
+
+    /// Calculate the isotope pattern of the formula
+    ///
+    /// The peaks are sorted by mass. The electron mass is not included.
+    /// Please refer to `IsotopePatternOptions` for the fine or coarse structure, pruning and merging of peaks.
+    ///
+    /// The stoichiometry must be integer. Otherwise `FormulaError::NonIntegerStoichiometry` is returned.
+    ///
+    /// # Example
+    /// ```
+    /// use chemical_formula::prelude::*;
+    /// use chemical_formula::isotope::IsotopePatternOptions;
+    /// use approx::assert_abs_diff_eq;
+    ///
+    /// let formula = parse_formula("CH2Cl2").unwrap();
+    ///
+    /// let mut options = IsotopePatternOptions::new();
+    /// options.fine(false).threshold(1e-3);
+    ///
+    /// let pattern = formula.isotope_pattern(&options).unwrap();
+    ///
+    /// // M, M+2 and M+4 from 35Cl and 37Cl, and small M+1, M+3 and M+5 peaks from 13C
+    /// assert_eq!(pattern.len(), 6);
+    /// assert_abs_diff_eq!(pattern[0].mass, 83.95336, epsilon = 1e-5);
+    /// assert_abs_diff_eq!(pattern[2].abundance / pattern[0].abundance, 0.64, epsilon = 1e-2);
+    /// ```
+    pub fn isotope_pattern(
+        &self,
+        options: &IsotopePatternOptions,
+    ) -> Result<Vec<IsotopePeak>, FormulaError> {
+        let composition = Composition::new(self)?;
+
+        let mut peaks = if options.fine {
+            // Prune intermediate results less strictly so that the final abundances are not underestimated
+            composition.fine_distribution(options.threshold * 1e-3)?
+        } else {
+            composition.distribution()?.peaks()
+        };
+
+        if let Some(resolution) = options.resolution {
+            peaks = merge_peaks(peaks, resolution);
+        }
+
+        let max = peaks.iter().map(|peak| peak.abundance).fold(0.0, f64::max);
+        peaks.retain(|peak| peak.abundance >= max * options.threshold);
+
+        Ok(peaks)
+    }
+}
+
+fn isotopes(data: &[(u16, f64, f64)]) -> Vec<IsotopeData> {
+    data.iter()
+        .map(|&(mass_number, mass, abundance)| IsotopeData {
+            mass_number,
+            mass,
+            abundance,
+        })
+        .collect()
+}
+
+/// Hashmap of isotopic masses and natural abundances
+///
+/// The key is the `ElementSymbol` enums and the value is the list of isotopes in order of mass number.
+/// All stable isotopes are listed with their natural abundance. Some common radioactive isotopes,
+/// such as `T` and `14C`, and the long-lived isotopes of the elements without a stable isotope are listed
+/// with an abundance of 0. Elements heavier than Lr are not listed.
+///
+/// # Example
+/// ```
+/// use chemical_formula::isotope::ISOTOPES;
+/// use chemical_formula::prelude::*;
+/// use approx::assert_abs_diff_eq;
+///
+/// let total: f64 = ISOTOPES[&ElementSymbol::Fe].iter().map(|isotope| isotope.abundance).sum();
+/// assert_abs_diff_eq!(total, 1.0, epsilon = 1e-6);
+/// ```
+///
+/// # Reference
+/// [Atomic Weights and Isotopic Compositions, NIST](https://www.nist.gov/pml/atomic-weights-and-isotopic-compositions-relative-atomic-masses)
+pub static ISOTOPES: Lazy<HashMap<ElementSymbol, Vec<IsotopeData>>> = Lazy::new(|| {
+    HashMap::from([
+        (
+            ElementSymbol::H,
+            isotopes(&[
+                (1, 1.00782503223, 0.999885),
+                (2, 2.01410177812, 0.000115),
+                (3, 3.0160492779, 0.0),
+            ]),
+        ),
+        (
+            ElementSymbol::He,
+            isotopes(&[
+                (3, 3.0160293201, 0.00000134),
+                (4, 4.00260325413, 0.99999866),
+            ]),
+        ),
+        (
+            ElementSymbol::Li,
+            isotopes(&[(6, 6.0151228874, 0.0759), (7, 7.0160034366, 0.9241)]),
+        ),
+        (ElementSymbol::Be, isotopes(&[(9, 9.012183065, 1.0)])),
+        (
+            ElementSymbol::B,
+            isotopes(&[(10, 10.01293695, 0.199), (11, 11.00930536, 0.801)]),
+        ),
+        (
+            ElementSymbol::C,
+            isotopes(&[
+                (12, 12.0, 0.9893),
+                (13, 13.00335483507, 0.0107),
+                (14, 14.0032419884, 0.0),
+            ]),
+        ),
+        (
+            ElementSymbol::N,
+            isotopes(&[(14, 14.00307400443, 0.99636), (15, 15.00010889888, 0.00364)]),
+        ),
+        (
+            ElementSymbol::O,
+            isotopes(&[
+                (16, 15.99491461957, 0.99757),
+                (17, 16.9991317565, 0.00038),
+                (18, 17.99915961286, 0.00205),
+            ]),
+        ),
+        (ElementSymbol::F, isotopes(&[(19, 18.99840316273, 1.0)])),
+        (
+            ElementSymbol::Ne,
+            isotopes(&[
+                (20, 19.9924401762, 0.9048),
+                (21, 20.993846685, 0.0027),
+                (22, 21.991385114, 0.0925),
+            ]),
+        ),
+        (ElementSymbol::Na, isotopes(&[(23, 22.989769282, 1.0)])),
+        (
+            ElementSymbol::Mg,
+            isotopes(&[
+                (24, 23.985041697, 0.7899),
+                (25, 24.985836976, 0.1),
+                (26, 25.982592968, 0.1101),
+            ]),
+        ),
+        (ElementSymbol::Al, isotopes(&[(27, 26.98153853, 1.0)])),
+        (
+            ElementSymbol::Si,
+            isotopes(&[
+                (28, 27.97692653465, 0.92223),
+                (29, 28.9764946649, 0.04685),
+                (30, 29.973770136, 0.03092),
+            ]),
+        ),
+        (ElementSymbol::P, isotopes(&[(31, 30.97376199842, 1.0)])),
+        (
+            ElementSymbol::S,
+            isotopes(&[
+                (32, 31.9720711744, 0.9499),
+                (33, 32.9714589098, 0.0075),
+                (34, 33.967867004, 0.0425),
+                (36, 35.96708071, 0.0001),
+            ]),
+        ),
+        (
+            ElementSymbol::Cl,
+            isotopes(&[(35, 34.968852682, 0.7576), (37, 36.965902602, 0.2424)]),
+        ),
+        (
+            ElementSymbol::Ar,
+            isotopes(&[
+                (36, 35.967545105, 0.003336),
+                (38, 37.96273211, 0.000629),
+                (40, 39.9623831237, 0.996035),
+            ]),
+        ),
+        (
+            ElementSymbol::K,
+            isotopes(&[
+                (39, 38.9637064864, 0.932581),
+                (40, 39.963998166, 0.000117),
+                (41, 40.9618252579, 0.067302),
+            ]),
+        ),
+        (
+            ElementSymbol::Ca,
+            isotopes(&[
+                (40, 39.962590863, 0.96941),
+                (42, 41.95861783, 0.00647),
+                (43, 42.95876644, 0.00135),
+                (44, 43.95548156, 0.02086),
+                (46, 45.953689, 0.00004),
+                (48, 47.95252276, 0.00187),
+            ]),
+        ),
+        (ElementSymbol::Sc, isotopes(&[(45, 44.95590828, 1.0)])),
+        (
+            ElementSymbol::Ti,
+            isotopes(&[
+                (46, 45.95262772, 0.0825),
+                (47, 46.95175879, 0.0744),
+                (48, 47.94794198, 0.7372),
+                (49, 48.94786568, 0.0541),
+                (50, 49.94478689, 0.0518),
+            ]),
+        ),
+        (
+            ElementSymbol::V,
+            isotopes(&[(50, 49.94715601, 0.0025), (51, 50.94395704, 0.9975)]),
+        ),
+        (
+            ElementSymbol::Cr,
+            isotopes(&[
+                (50, 49.94604183, 0.04345),
+                (52, 51.94050623, 0.83789),
+                (53, 52.94064815, 0.09501),
+                (54, 53.93887916, 0.02365),
+            ]),
+        ),
+        (ElementSymbol::Mn, isotopes(&[(55, 54.93804391, 1.0)])),
+        (
+            ElementSymbol::Fe,
+            isotopes(&[
+                (54, 53.93960899, 0.05845),
+                (56, 55.93493633, 0.91754),
+                (57, 56.93539284, 0.02119),
+                (58, 57.93327443, 0.00282),
+            ]),
+        ),
+        (ElementSymbol::Co, isotopes(&[(59, 58.93319429, 1.0)])),
+        (
+            ElementSymbol::Ni,
+            isotopes(&[
+                (58, 57.93534241, 0.68077),
+                (60, 59.93078588, 0.26223),
+                (61, 60.93105557, 0.011399),
+                (62, 61.92834537, 0.036346),
+                (64, 63.92796682, 0.009255),
+            ]),
+        ),
+        (
+            ElementSymbol::Cu,
+            isotopes(&[(63, 62.92959772, 0.6915), (65, 64.9277897, 0.3085)]),
+        ),
+        (
+            ElementSymbol::Zn,
+            isotopes(&[
+                (64, 63.92914201, 0.4917),
+                (66, 65.92603381, 0.2773),
+                (67, 66.92712775, 0.0404),
+                (68, 67.92484455, 0.1845),
+                (70, 69.9253192, 0.0061),
+            ]),
+        ),
+        (
+            ElementSymbol::Ga,
+            isotopes(&[(69, 68.9255735, 0.60108), (71, 70.92470258, 0.39892)]),
+        ),
+        (
+            ElementSymbol::Ge,
+            isotopes(&[
+                (70, 69.92424875, 0.2057),
+                (72, 71.922075826, 0.2745),
+                (73, 72.923458956, 0.0775),
+                (74, 73.921177761, 0.365),
+                (76, 75.921402726, 0.0773),
+            ]),
+        ),
+        (ElementSymbol::As, isotopes(&[(75, 74.92159457, 1.0)])),
+        (
+            ElementSymbol::Se,
+            isotopes(&[
+                (74, 73.922475934, 0.0089),
+                (76, 75.919213704, 0.0937),
+                (77, 76.919914154, 0.0763),
+                (78, 77.91730928, 0.2377),
+                (80, 79.9165218, 0.4961),
+                (82, 81.9166995, 0.0873),
+            ]),
+        ),
+        (
+            ElementSymbol::Br,
+            isotopes(&[(79, 78.9183376, 0.5069), (81, 80.9162897, 0.4931)]),
+        ),
+        (
+            ElementSymbol::Kr,
+            isotopes(&[
+                (78, 77.92036494, 0.00355),
+                (80, 79.91637808, 0.02286),
+                (82, 81.91348273, 0.11593),
+                (83, 82.91412716, 0.115),
+                (84, 83.9114977282, 0.56987),
+                (86, 85.9106106269, 0.17279),
+            ]),
+        ),
+        (
+            ElementSymbol::Rb,
+            isotopes(&[(85, 84.9117897379, 0.7217), (87, 86.909180531, 0.2783)]),
+        ),
+        (
+            ElementSymbol::Sr,
+            isotopes(&[
+                (84, 83.9134191, 0.0056),
+                (86, 85.9092606, 0.0986),
+                (87, 86.9088775, 0.07),
+                (88, 87.9056125, 0.8258),
+            ]),
+        ),
+        (ElementSymbol::Y, isotopes(&[(89, 88.9058403, 1.0)])),
+        (
+            ElementSymbol::Zr,
+            isotopes(&[
+                (90, 89.9046977, 0.5145),
+                (91, 90.9056396, 0.1122),
+                (92, 91.9050347, 0.1715),
+                (94, 93.9063108, 0.1738),
+                (96, 95.9082714, 0.028),
+            ]),
+        ),
+        (ElementSymbol::Nb, isotopes(&[(93, 92.906373, 1.0)])),
+        (
+            ElementSymbol::Mo,
+            isotopes(&[
+                (92, 91.90680796, 0.1453),
+                (94, 93.9050849, 0.0915),
+                (95, 94.90583877, 0.1584),
+                (96, 95.90467612, 0.1667),
+                (97, 96.90601812, 0.096),
+                (98, 97.90540482, 0.2439),
+                (100, 99.9074718, 0.0982),
+            ]),
+        ),
+        (
+            ElementSymbol::Tc,
+            isotopes(&[
+                (97, 96.9063667, 0.0),
+                (98, 97.9072124, 0.0),
+                (99, 98.9062508, 0.0),
+            ]),
+        ),
+        (
+            ElementSymbol::Ru,
+            isotopes(&[
+                (96, 95.90759025, 0.0554),
+                (98, 97.9052868, 0.0187),
+                (99, 98.9059341, 0.1276),
+                (100, 99.9042143, 0.126),
+                (101, 100.9055769, 0.1706),
+                (102, 101.9043441, 0.3155),
+                (104, 103.9054275, 0.1862),
+            ]),
+        ),
+        (ElementSymbol::Rh, isotopes(&[(103, 102.905498, 1.0)])),
+        (
+            ElementSymbol::Pd,
+            isotopes(&[
+                (102, 101.9056022, 0.0102),
+                (104, 103.9040305, 0.1114),
+                (105, 104.9050796, 0.2233),
+                (106, 105.9034804, 0.2733),
+                (108, 107.9038916, 0.2646),
+                (110, 109.9051722, 0.1172),
+            ]),
+        ),
+        (
+            ElementSymbol::Ag,
+            isotopes(&[(107, 106.9050916, 0.51839), (109, 108.9047553, 0.48161)]),
+        ),
+        (
+            ElementSymbol::Cd,
+            isotopes(&[
+                (106, 105.9064599, 0.0125),
+                (108, 107.9041834, 0.0089),
+                (110, 109.90300661, 0.1249),
+                (111, 110.90418287, 0.128),
+                (112, 111.90276287, 0.2413),
+                (113, 112.90440813, 0.1222),
+                (114, 113.90336509, 0.2873),
+                (116, 115.90476315, 0.0749),
+            ]),
+        ),
+        (
+            ElementSymbol::In,
+            isotopes(&[(113, 112.90406184, 0.0429), (115, 114.903878776, 0.9571)]),
+        ),
+        (
+            ElementSymbol::Sn,
+            isotopes(&[
+                (112, 111.90482387, 0.0097),
+                (114, 113.9027827, 0.0066),
+                (115, 114.903344699, 0.0034),
+                (116, 115.9017428, 0.1454),
+                (117, 116.90295398, 0.0768),
+                (118, 117.90160657, 0.2422),
+                (119, 118.90331117, 0.0859),
+                (120, 119.90220163, 0.3258),
+                (122, 121.9034438, 0.0463),
+                (124, 123.9052766, 0.0579),
+            ]),
+        ),
+        (
+            ElementSymbol::Sb,
+            isotopes(&[(121, 120.903812, 0.5721), (123, 122.9042132, 0.4279)]),
+        ),
+        (
+            ElementSymbol::Te,
+            isotopes(&[
+                (120, 119.9040593, 0.0009),
+                (122, 121.9030435, 0.0255),
+                (123, 122.9042698, 0.0089),
+                (124, 123.9028171, 0.0474),
+                (125, 124.9044299, 0.0707),
+                (126, 125.9033109, 0.1884),
+                (128, 127.90446128, 0.3174),
+                (130, 129.906222748, 0.3408),
+            ]),
+        ),
+        (ElementSymbol::I, isotopes(&[(127, 126.9044719, 1.0)])),
+        (
+            ElementSymbol::Xe,
+            isotopes(&[
+                (124, 123.905892, 0.000952),
+                (126, 125.9042983, 0.00089),
+                (128, 127.903531, 0.019102),
+                (129, 128.9047808611, 0.264006),
+                (130, 129.903509349, 0.04071),
+                (131, 130.90508406, 0.212324),
+                (132, 131.9041550856, 0.269086),
+                (134, 133.90539466, 0.104357),
+                (136, 135.907214484, 0.088573),
+            ]),
+        ),
+        (ElementSymbol::Cs, isotopes(&[(133, 132.905451961, 1.0)])),
+        (
+            ElementSymbol::Ba,
+            isotopes(&[
+                (130, 129.9063207, 0.00106),
+                (132, 131.9050611, 0.00101),
+                (134, 133.90450818, 0.02417),
+                (135, 134.90568838, 0.06592),
+                (136, 135.90457573, 0.07854),
+                (137, 136.90582714, 0.11232),
+                (138, 137.905247, 0.71698),
+            ]),
+        ),
+        (
+            ElementSymbol::La,
+            isotopes(&[(138, 137.9071149, 0.0008881), (139, 138.9063563, 0.9991119)]),
+        ),
+        (
+            ElementSymbol::Ce,
+            isotopes(&[
+                (136, 135.90712921, 0.00185),
+                (138, 137.905991, 0.00251),
+                (140, 139.9054431, 0.8845),
+                (142, 141.9092504, 0.11114),
+            ]),
+        ),
+        (ElementSymbol::Pr, isotopes(&[(141, 140.9076576, 1.0)])),
+        (
+            ElementSymbol::Nd,
+            isotopes(&[
+                (142, 141.907729, 0.27152),
+                (143, 142.90982, 0.12174),
+                (144, 143.910093, 0.23798),
+                (145, 144.9125793, 0.08293),
+                (146, 145.9131226, 0.17189),
+                (148, 147.9168993, 0.05756),
+                (150, 149.9209022, 0.05638),
+            ]),
+        ),
+        (
+            ElementSymbol::Pm,
+            isotopes(&[(145, 144.9127559, 0.0), (147, 146.915145, 0.0)]),
+        ),
+        (
+            ElementSymbol::Sm,
+            isotopes(&[
+                (144, 143.9120065, 0.0307),
+                (147, 146.9149044, 0.1499),
+                (148, 147.9148292, 0.1124),
+                (149, 148.9171921, 0.1382),
+                (150, 149.9172829, 0.0738),
+                (152, 151.9197397, 0.2675),
+                (154, 153.9222169, 0.2275),
+            ]),
+        ),
+        (
+            ElementSymbol::Eu,
+            isotopes(&[(151, 150.9198578, 0.4781), (153, 152.921238, 0.5219)]),
+        ),
+        (
+            ElementSymbol::Gd,
+            isotopes(&[
+                (152, 151.9197995, 0.002),
+                (154, 153.9208741, 0.0218),
+                (155, 154.9226305, 0.148),
+                (156, 155.9221312, 0.2047),
+                (157, 156.9239686, 0.1565),
+                (158, 157.9241123, 0.2484),
+                (160, 159.9270624, 0.2186),
+            ]),
+        ),
+        (ElementSymbol::Tb, isotopes(&[(159, 158.9253547, 1.0)])),
+        (
+            ElementSymbol::Dy,
+            isotopes(&[
+                (156, 155.9242847, 0.00056),
+                (158, 157.9244159, 0.00095),
+                (160, 159.9252046, 0.02329),
+                (161, 160.9269405, 0.18889),
+                (162, 161.9268056, 0.25475),
+                (163, 162.9287383, 0.24896),
+                (164, 163.9291819, 0.2826),
+            ]),
+        ),
+        (ElementSymbol::Ho, isotopes(&[(165, 164.9303288, 1.0)])),
+        (
+            ElementSymbol::Er,
+            isotopes(&[
+                (162, 161.9287884, 0.00139),
+                (164, 163.9292088, 0.01601),
+                (166, 165.9302995, 0.33503),
+                (167, 166.9320546, 0.22869),
+                (168, 167.9323767, 0.26978),
+                (170, 169.9354702, 0.1491),
+            ]),
+        ),
+        (ElementSymbol::Tm, isotopes(&[(169, 168.9342179, 1.0)])),
+        (
+            ElementSymbol::Yb,
+            isotopes(&[
+                (168, 167.9338896, 0.00123),
+                (170, 169.9347664, 0.02982),
+                (171, 170.9363302, 0.1409),
+                (172, 171.9363859, 0.2168),
+                (173, 172.9382151, 0.16103),
+                (174, 173.9388664, 0.32026),
+                (176, 175.9425764, 0.12996),
+            ]),
+        ),
+        (
+            ElementSymbol::Lu,
+            isotopes(&[(175, 174.9407752, 0.97401), (176, 175.9426897, 0.02599)]),
+        ),
+        (
+            ElementSymbol::Hf,
+            isotopes(&[
+                (174, 173.9400461, 0.0016),
+                (176, 175.9414076, 0.0526),
+                (177, 176.9432277, 0.186),
+                (178, 177.9437058, 0.2728),
+                (179, 178.9458232, 0.1362),
+                (180, 179.946557, 0.3508),
+            ]),
+        ),
+        (
+            ElementSymbol::Ta,
+            isotopes(&[(180, 179.9474648, 0.0001201), (181, 180.9479958, 0.9998799)]),
+        ),
+        (
+            ElementSymbol::W,
+            isotopes(&[
+                (180, 179.9467108, 0.0012),
+                (182, 181.94820394, 0.265),
+                (183, 182.95022275, 0.1431),
+                (184, 183.95093092, 0.3064),
+                (186, 185.9543628, 0.2843),
+            ]),
+        ),
+        (
+            ElementSymbol::Re,
+            isotopes(&[(185, 184.9529545, 0.374), (187, 186.9557501, 0.626)]),
+        ),
+        (
+            ElementSymbol::Os,
+            isotopes(&[
+                (184, 183.9524885, 0.0002),
+                (186, 185.953835, 0.0159),
+                (187, 186.9557474, 0.0196),
+                (188, 187.9558352, 0.1324),
+                (189, 188.9581442, 0.1615),
+                (190, 189.9584437, 0.2626),
+                (192, 191.961477, 0.4078),
+            ]),
+        ),
+        (
+            ElementSymbol::Ir,
+            isotopes(&[(191, 190.9605893, 0.373), (193, 192.9629216, 0.627)]),
+        ),
+        (
+            ElementSymbol::Pt,
+            isotopes(&[
+                (190, 189.9599297, 0.00012),
+                (192, 191.9610387, 0.00782),
+                (194, 193.9626809, 0.3286),
+                (195, 194.9647917, 0.3378),
+                (196, 195.96495209, 0.2521),
+                (198, 197.9678949, 0.07356),
+            ]),
+        ),
+        (ElementSymbol::Au, isotopes(&[(197, 196.96656879, 1.0)])),
+        (
+            ElementSymbol::Hg,
+            isotopes(&[
+                (196, 195.9658326, 0.0015),
+                (198, 197.9667686, 0.0997),
+                (199, 198.96828064, 0.1687),
+                (200, 199.96832659, 0.231),
+                (201, 200.97030284, 0.1318),
+                (202, 201.9706434, 0.2986),
+                (204, 203.97349398, 0.0687),
+            ]),
+        ),
+        (
+            ElementSymbol::Tl,
+            isotopes(&[(203, 202.9723446, 0.2952), (205, 204.9744278, 0.7048)]),
+        ),
+        (
+            ElementSymbol::Pb,
+            isotopes(&[
+                (204, 203.973044, 0.014),
+                (206, 205.9744657, 0.241),
+                (207, 206.9758973, 0.221),
+                (208, 207.9766525, 0.524),
+            ]),
+        ),
+        (ElementSymbol::Bi, isotopes(&[(209, 208.9803991, 1.0)])),
+        (
+            ElementSymbol::Po,
+            isotopes(&[(209, 208.9824308, 0.0), (210, 209.9828741, 0.0)]),
+        ),
+        (
+            ElementSymbol::At,
+            isotopes(&[(210, 209.9871479, 0.0), (211, 210.9874966, 0.0)]),
+        ),
+        (
+            ElementSymbol::Rn,
+            isotopes(&[
+                (211, 210.9906011, 0.0),
+                (220, 220.0113941, 0.0),
+                (222, 222.0175782, 0.0),
+            ]),
+        ),
+        (ElementSymbol::Fr, isotopes(&[(223, 223.019736, 0.0)])),
+        (
+            ElementSymbol::Ra,
+            isotopes(&[
+                (223, 223.0185023, 0.0),
+                (224, 224.020212, 0.0),
+                (226, 226.0254103, 0.0),
+                (228, 228.0310707, 0.0),
+            ]),
+        ),
+        (ElementSymbol::Ac, isotopes(&[(227, 227.0277523, 0.0)])),
+        (
+            ElementSymbol::Th,
+            isotopes(&[(230, 230.0331341, 0.0), (232, 232.0380558, 1.0)]),
+        ),
+        (ElementSymbol::Pa, isotopes(&[(231, 231.0358842, 1.0)])),
+        (
+            ElementSymbol::U,
+            isotopes(&[
+                (233, 233.0396355, 0.0),
+                (234, 234.0409523, 0.000054),
+                (235, 235.0439301, 0.007204),
+                (236, 236.0455682, 0.0),
+                (238, 238.0507884, 0.992742),
+            ]),
+        ),
+        (ElementSymbol::Np, isotopes(&[(237, 237.0481736, 0.0)])),
+        (
+            ElementSymbol::Pu,
+            isotopes(&[
+                (238, 238.0495601, 0.0),
+                (239, 239.0521636, 0.0),
+                (240, 240.0538138, 0.0),
+                (241, 241.0568517, 0.0),
+                (242, 242.0587428, 0.0),
+                (244, 244.0642053, 0.0),
+            ]),
+        ),
+        (
+            ElementSymbol::Am,
+            isotopes(&[(241, 241.0568293, 0.0), (243, 243.0613813, 0.0)]),
+        ),
+        (
+            ElementSymbol::Cm,
+            isotopes(&[
+                (243, 243.0613893, 0.0),
+                (244, 244.0627528, 0.0),
+                (245, 245.0654915, 0.0),
+                (246, 246.0672238, 0.0),
+                (247, 247.0703541, 0.0),
+                (248, 248.0723499, 0.0),
+            ]),
+        ),
+        (
+            ElementSymbol::Bk,
+            isotopes(&[(247, 247.0703073, 0.0), (249, 249.0749877, 0.0)]),
+        ),
+        (
+            ElementSymbol::Cf,
+            isotopes(&[
+                (249, 249.0748539, 0.0),
+                (250, 250.0764062, 0.0),
+                (251, 251.0795886, 0.0),
+                (252, 252.0816272, 0.0),
+            ]),
+        ),
+        (ElementSymbol::Es, isotopes(&[(252, 252.08298, 0.0)])),
+        (ElementSymbol::Fm, isotopes(&[(257, 257.0951061, 0.0)])),
+        (
+            ElementSymbol::Md,
+            isotopes(&[(258, 258.0984315, 0.0), (260, 260.10365, 0.0)]),
+        ),
+        (ElementSymbol::No, isotopes(&[(259, 259.10103, 0.0)])),
+        (ElementSymbol::Lr, isotopes(&[(262, 262.10961, 0.0)])),
+    ])
+});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_formula;
+    use approx::assert_abs_diff_eq;
+
+    #[test]
+    fn test_isotope_abundance_sum() {
+        for (element, isotopes) in ISOTOPES.iter() {
+            let total: f64 = isotopes.iter().map(|isotope| isotope.abundance).sum();
+            assert!(
+                total == 0.0 || (total - 1.0).abs() < 1e-4,
+                "{}: {}",
+                element,
+                total
+            );
+
+            for pair in isotopes.windows(2) {
+                assert!(pair[0].mass_number < pair[1].mass_number, "{}", element);
+            }
+
+            for isotope in isotopes.iter() {
+                assert!(
+                    (isotope.mass - isotope.mass_number as f64).abs() < 0.5,
+                    "{}{}",
+                    element,
+                    isotope.mass_number
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_isotope_average_mass() {
+        // The abundance-weighted mass agrees with the standard atomic weight
+        for element in [
+            ElementSymbol::H,
+            ElementSymbol::C,
+            ElementSymbol::O,
+            ElementSymbol::Cl,
+            ElementSymbol::Fe,
+            ElementSymbol::Ni,
+            ElementSymbol::Pt,
+            ElementSymbol::Ce,
+        ] {
+            let average: f64 = element
+                .isotopes()
+                .iter()
+                .map(|isotope| isotope.mass * isotope.abundance)
+                .sum();
+
+            assert!(
+                (average - element.atomic_weight()).abs() < 0.01,
+                "{}: {}",
+                element,
+                average
+            );
+        }
+    }
+
+    #[test]
+    fn test_formula_masses() {
+        let formula = parse_formula("CO2").unwrap();
+        assert_abs_diff_eq!(
+            formula.monoisotopic_mass().unwrap(),
+            43.98983,
+            epsilon = 1e-5
+        );
+        assert_abs_diff_eq!(formula.nominal_mass().unwrap(), 44.0);
+        assert_abs_diff_eq!(
+            formula.most_abundant_mass().unwrap(),
+            43.98983,
+            epsilon = 1e-3
+        );
+
+        let formula = parse_formula("C[18O]2").unwrap();
+        assert_abs_diff_eq!(
+            formula.monoisotopic_mass().unwrap(),
+            47.99832,
+            epsilon = 1e-5
+        );
+        assert_abs_diff_eq!(formula.nominal_mass().unwrap(), 48.0);
+
+        // 13C1 is the most abundant isotopologue with more than about 93 carbon atoms
+        let formula = parse_formula("C100H202").unwrap();
+        let monoisotopic_mass = formula.monoisotopic_mass().unwrap();
+        assert_abs_diff_eq!(
+            formula.most_abundant_mass().unwrap(),
+            monoisotopic_mass + 1.00336,
+            epsilon = 1e-2
+        );
+
+        // The most abundant isotope of Pt is 195Pt
+        let formula = parse_formula("Pt").unwrap();
+        assert_abs_diff_eq!(
+            formula.monoisotopic_mass().unwrap(),
+            194.96479,
+            epsilon = 1e-5
+        );
+        assert_abs_diff_eq!(
+            formula.most_abundant_mass().unwrap(),
+            194.96479,
+            epsilon = 1e-5
+        );
+    }
+
+    #[test]
+    fn test_formula_mass_errors() {
+        assert!(matches!(
+            parse_formula("TcO4-").unwrap().monoisotopic_mass(),
+            Err(FormulaError::NoIsotopeData(ElementSymbol::Tc))
+        ));
+        assert!(matches!(
+            parse_formula("Fe0.95O").unwrap().most_abundant_mass(),
+            Err(FormulaError::NonIntegerStoichiometry(ElementSymbol::Fe))
+        ));
+        assert!(matches!(
+            parse_formula("H2O").unwrap().mz(0),
+            Err(FormulaError::ZeroCharge)
+        ));
+
+        let formula = parse_formula("Fe0.95O").unwrap();
+        assert_abs_diff_eq!(
+            formula.monoisotopic_mass().unwrap(),
+            0.95 * 55.93494 + 15.99491,
+            epsilon = 1e-4
+        );
+    }
+
+    #[test]
+    fn test_isotope_pattern() {
+        let formula = parse_formula("C100H202").unwrap();
+        let mut options = IsotopePatternOptions::new();
+        options.threshold(1e-9);
+
+        let fine = formula.isotope_pattern(&options).unwrap();
+        let total: f64 = fine.iter().map(|peak| peak.abundance).sum();
+        assert_abs_diff_eq!(total, 1.0, epsilon = 1e-6);
+        assert_abs_diff_eq!(
+            fine[0].mass,
+            formula.monoisotopic_mass().unwrap(),
+            epsilon = 1e-9
+        );
+
+        // 13C1 and 2H1 are separate peaks of the fine structure
+        assert_abs_diff_eq!(fine[1].mass - fine[0].mass, 1.00335, epsilon = 1e-4);
+        assert_abs_diff_eq!(fine[2].mass - fine[0].mass, 1.00628, epsilon = 1e-4);
+
+        // Merging the fine structure at unit resolution gives the coarse structure
+        options.resolution(2000.);
+        let merged = formula.isotope_pattern(&options).unwrap();
+        options.fine(false);
+        let coarse = formula.isotope_pattern(&options).unwrap();
+
+        for (merged, coarse) in merged.iter().zip(coarse.iter()).take(5) {
+            assert_abs_diff_eq!(merged.mass, coarse.mass, epsilon = 1e-6);
+            assert_abs_diff_eq!(merged.abundance, coarse.abundance, epsilon = 1e-6);
+        }
+
+        let pattern = parse_formula("D2O")
+            .unwrap()
+            .isotope_pattern(&IsotopePatternOptions::new())
+            .unwrap();
+        assert_abs_diff_eq!(pattern[0].mass, 20.02312, epsilon = 1e-5);
+        assert_abs_diff_eq!(pattern[0].abundance, 0.99757, epsilon = 1e-5);
+    }
+}
@@ -32,35 +32,38 @@
 //!     let formula = parse_formula("H2O").unwrap();
 //!
 //!     println!("Orignal formula: {:?}", formula);
-//!     // Orignal formula: ChemicalFormula { element: {O, H}, stoichiometry: {H: 2.0, O: 1.0}, wt_percent: {} }
+//!     // Orignal formula: ChemicalFormula { element: [H, O], stoichiometry: {H: 2.0, O: 1.0}, wt_percent: {}, at_percent: {}, isotope: {}, charge: 0, strict: false }
 //!
 //!     println!("Molecular weight: {:?}", formula.molecular_weight());
 //!     // Molecular weight: Ok(18.015)
 //!
 //!     println!("Wt%: {:?}", formula.to_wt_percent().unwrap());
-//!     // Wt%: ChemicalFormula { element: {O, H}, stoichiometry: {}, wt_percent: {H: 11.19067443796836, O: 88.80932556203165} }
+//!     // Wt%: ChemicalFormula { element: [H, O], stoichiometry: {}, wt_percent: {H: 11.19067443796836, O: 88.80932556203165}, charge: 0, strict: false }
 //!
 //!     let formula = parse_formula("Pt5wt%/SiO2").unwrap();
 //!
 //!     println!("Orignal formula: {:?}", formula);
-//!     // Orignal formula: ChemicalFormula { element: {Si, O, Pt}, stoichiometry: {Si: 1.0, O: 2.0}, wt_percent: {Pt: 5.0} }
+//!     // Orignal formula: ChemicalFormula { element: [Pt, Si, O], stoichiometry: {O: 2.0, Si: 1.0}, wt_percent: {Pt: 5.0}, at_percent: {}, isotope: {}, charge: 0, strict: false }
+//!
+//!     println!("Formula: {}", formula);
+//!     // Formula: Pt5wt%/SiO2
 //!
 //!     println!(
 //!         "Molecular Formula: {:?}",
 //!         formula.to_molecular_formula().unwrap()
 //!     );
-//!     // Molecular Formula: ChemicalFormula { element: {Si, O, Pt}, stoichiometry: {Si: 1.0, Pt: 0.016209751480873558, O: 2.0}, wt_percent: {} }
+//!     // Molecular Formula: ChemicalFormula { element: [Pt, Si, O], stoichiometry: {O: 2.0, Si: 1.0, Pt: 0.016209751480873558}, wt_percent: {}, at_percent: {}, isotope: {}, charge: 0, strict: false }
 //!
 //!     println!("Wt%: {:?}", formula.to_wt_percent().unwrap());
-//!     // Wt%: ChemicalFormula { element: {Si, O, Pt}, stoichiometry: {}, wt_percent: {Pt: 5.0, Si: 44.406487692026026, O: 50.59351230797397} }
+//!     // Wt%: ChemicalFormula { element: [Pt, Si, O], stoichiometry: {}, wt_percent: {O: 50.593512307973974, Si: 44.40648769202603, Pt: 5.0}, at_percent: {}, isotope: {}, charge: 0, strict: false }
 //!
 //!     let formula = parse_formula("(Pt5wt%/SiO2)50wt%(CeO2)50wt%").unwrap();
 //!
 //!     println!("Orignal formula: {:?}", formula);
-//!     // Orignal formula: ChemicalFormula { element: {Si, Ce, Pt, O}, stoichiometry: {}, wt_percent: {Si: 22.203243846013017, O: 34.59233931398559, Pt: 2.5000000000000004, Ce: 40.70441684000139} }
+//!     // Orignal formula: ChemicalFormula { element: [Pt, Si, O, Ce], stoichiometry: {}, wt_percent: {O: 34.59233931398559, Si: 22.203243846013017, Ce: 40.70441684000139, Pt: 2.5000000000000004}, at_percent: {}, isotope: {}, charge: 0, strict: false }
 //!
 //!     println!("Wt%: {:?}", formula.to_wt_percent().unwrap());
-//!     // Wt%: ChemicalFormula { element: {Si, Ce, Pt, O}, stoichiometry: {}, wt_percent: {Si: 22.203243846013017, O: 34.59233931398559, Pt: 2.5000000000000004, Ce: 40.70441684000139} }
+//!     // Wt%: ChemicalFormula { element: [Pt, Si, O, Ce], stoichiometry: {}, wt_percent: {O: 34.59233931398559, Si: 22.203243846013017, Ce: 40.70441684000139, Pt: 2.5000000000000004}, at_percent: {}, isotope: {}, charge: 0, strict: false }
 //! }
 //! ```
 //!
@@ -74,6 +77,18 @@
 //!   ([LICENSE-MIT](https://github.com/Ameyanagi/chemical-formula-rs/blob/main/LICENSE-MIT) or <http://opensource.org/licenses/MIT>)
 //!
 //! at your option.
+pub mod catalyst;
 pub mod element;
+pub mod empirical;
+pub mod isotope;
+pub mod mineral;
+pub mod oxidation;
+pub mod oxide;
 pub mod parser;
+pub mod periodic;
 pub mod prelude;
+pub mod reaction;
+pub mod series;
+pub mod symbolic;
+pub mod tree;
+pub mod writer;
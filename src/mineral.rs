@@ -0,0 +1,312 @@
+//! # Mineral formula recalculation
+//!
+//! This module recalculates a measured composition, e.g. microprobe data, to atoms per formula unit (apfu).
+//!
+//! `ChemicalFormula::normalize_to` normalises the formula to a fixed number of atoms of an element,
+//! usually a fixed number of oxygens such as 12 O for garnet, and `normalize_to_cations` to a fixed number of cations.
+//! `ChemicalFormula::estimate_ferric_iron` splits Fe into Fe2+ and Fe3+ by charge balance
+//! for a fixed number of both oxygens and cations (Droop, 1987).
+//!
+//! Element wt% without O can be converted with `to_oxide_wt_percent(&basis)?.to_formula()`
+//! to give the O by the stoichiometry of the oxides.
+//!
+//! # Example
+//! ```
+//! use chemical_formula::prelude::*;
+//! use approx::assert_abs_diff_eq;
+//!
+//! // Microprobe analysis of almandine in oxide wt%
+//! let formula = ChemicalFormula::from_oxide_wt_percent(&[
+//!     (parse_formula("SiO2").unwrap(), 36.20),
+//!     (parse_formula("Al2O3").unwrap(), 20.48),
+//!     (parse_formula("FeO").unwrap(), 43.29),
+//! ])
+//! .unwrap();
+//!
+//! let apfu = formula.normalize_to(ElementSymbol::O, 12.0).unwrap();
+//!
+//! assert_abs_diff_eq!(apfu.stoichiometry[&ElementSymbol::Fe], 3.0, epsilon = 1e-2);
+//! assert_abs_diff_eq!(apfu.stoichiometry[&ElementSymbol::Al], 2.0, epsilon = 1e-2);
+//! assert_abs_diff_eq!(apfu.stoichiometry[&ElementSymbol::Si], 3.0, epsilon = 1e-2);
+//! ```
+
+use crate::element::{ChemicalFormula, ElementSymbol, FormulaError};
+use crate::oxide::OxideBasis;
+use std::collections::BTreeMap;
+
+/// Elements counted as anions in the cation basis
+const ANIONS: &[ElementSymbol] = &[
+    ElementSymbol::O,
+    ElementSymbol::F,
+    ElementSymbol::Cl,
+    ElementSymbol::Br,
+    ElementSymbol::I,
+];
+
+/// Fe2+ and Fe3+ returned by `ChemicalFormula::estimate_ferric_iron`
+#[derive(Debug, Clone)]
+pub struct FerricIronEstimate {
+    /// Formula normalised to the cations, with the O balancing the charge
+    pub formula: ChemicalFormula,
+    /// Fe2+ in atoms per formula unit
+    pub ferrous: f64,
+    /// Fe3+ in atoms per formula unit
+    pub ferric: f64,
+}
+
+impl FerricIronEstimate {
+    /// Fe3+ / (Fe2+ + Fe3+)
+    pub fn ferric_ratio(&self) -> f64 {
+        self.ferric / (self.ferrous + self.ferric)
+    }
+}
+
+impl ChemicalFormula {
+    /// Normalise the formula to `count` atoms of `element`, e.g. 12 O
+    ///
+    /// A formula given only by wt% terms is converted to mol as it is,
+    /// and other formulas are converted with `to_molecular_formula()` first.
+    ///
+    /// Returns `FormulaError::ElementNotInFormula` if `element` is not in the formula.
+    ///
+    /// # Example
+    /// ```
+    /// use chemical_formula::prelude::*;
+    /// use approx::assert_abs_diff_eq;
+    ///
+    /// let formula = parse_formula("Mg2SiO4").unwrap();
+    /// let apfu = formula.normalize_to(ElementSymbol::Si, 2.0).unwrap();
+    ///
+    /// assert_abs_diff_eq!(apfu.stoichiometry[&ElementSymbol::Mg], 4.0);
+    /// assert_abs_diff_eq!(apfu.stoichiometry[&ElementSymbol::O], 8.0);
+    /// ```
+    pub fn normalize_to(
+        &self,
+        element: ElementSymbol,
+        count: f64,
+    ) -> Result<ChemicalFormula, FormulaError> {
+        let moles = self.measured_composition()?.moles;
+
+        match moles.get(&element) {
+            Some(amount) if *amount > 0. => Ok(self.scaled(&moles, count / amount)),
+            _ => Err(FormulaError::ElementNotInFormula(element)),
+        }
+    }
+
+    /// Normalise the formula to `count` cations
+    ///
+    /// Every element other than O and the halogens is counted as a cation.
+    ///
+    /// Returns `FormulaError::NoFormula` if the formula has no cation.
+    ///
+    /// # Example
+    /// ```
+    /// use chemical_formula::prelude::*;
+    /// use approx::assert_abs_diff_eq;
+    ///
+    /// let formula = parse_formula("(Mg1.8Fe0.2)SiO4").unwrap();
+    /// let apfu = formula.normalize_to_cations(3.0).unwrap();
+    ///
+    /// assert_abs_diff_eq!(apfu.stoichiometry[&ElementSymbol::Fe], 0.2, epsilon = 1e-9);
+    /// assert_abs_diff_eq!(apfu.stoichiometry[&ElementSymbol::O], 4.0, epsilon = 1e-9);
+    /// ```
+    pub fn normalize_to_cations(&self, count: f64) -> Result<ChemicalFormula, FormulaError> {
+        let moles = self.measured_composition()?.moles;
+        let cations: f64 = moles
+            .iter()
+            .filter(|(element, _)| !ANIONS.contains(element))
+            .map(|(_, amount)| amount)
+            .sum();
+
+        if cations <= 0. {
+            return Err(FormulaError::NoFormula);
+        }
+
+        Ok(self.scaled(&moles, count / cations))
+    }
+
+    /// Estimate Fe2+ and Fe3+ by charge balance for `oxygens` O and `cations` cations per formula unit
+    ///
+    /// The formula is normalised to `cations` cations with all Fe as Fe2+,
+    /// and the charge missing to balance `oxygens` O is taken up by Fe3+ (Droop, 1987).
+    /// The charge of the other cations is given by their oxides in `basis`, e.g. +3 for Al in `Al2O3`.
+    /// The O of the formula is not used, and the halogens are ignored in the charge balance.
+    /// Fe3+ is limited to the range from 0 to the total Fe.
+    ///
+    /// Returns `FormulaError::ElementNotInFormula` if the formula has no Fe,
+    /// and `FormulaError::NoOxide` for a cation without an oxide in `basis`.
+    ///
+    /// # Reference
+    /// Droop, G. T. R. (1987). A general equation for estimating Fe3+ concentrations in ferromagnesian silicates
+    /// and oxides from microprobe analyses, using stoichiometric criteria. Mineralogical Magazine, 51(361), 431-435.
+    ///
+    /// # Example
+    /// ```
+    /// use chemical_formula::prelude::*;
+    /// use approx::assert_abs_diff_eq;
+    ///
+    /// // Total Fe of magnetite measured as FeO
+    /// let formula = ChemicalFormula::from_oxide_wt_percent(&[
+    ///     (parse_formula("FeO").unwrap(), 93.1),
+    /// ])
+    /// .unwrap();
+    ///
+    /// let estimate = formula.estimate_ferric_iron(4.0, 3.0, &OxideBasis::new()).unwrap();
+    ///
+    /// assert_abs_diff_eq!(estimate.ferrous, 1.0, epsilon = 1e-9);
+    /// assert_abs_diff_eq!(estimate.ferric, 2.0, epsilon = 1e-9);
+    /// assert_abs_diff_eq!(estimate.formula.stoichiometry[&ElementSymbol::O], 4.0, epsilon = 1e-9);
+    /// ```
+    pub fn estimate_ferric_iron(
+        &self,
+        oxygens: f64,
+        cations: f64,
+        basis: &OxideBasis,
+    ) -> Result<FerricIronEstimate, FormulaError> {
+        let mut formula = self.normalize_to_cations(cations)?;
+
+        let iron = match formula.stoichiometry.get(&ElementSymbol::Fe) {
+            Some(iron) if *iron > 0. => *iron,
+            _ => return Err(FormulaError::ElementNotInFormula(ElementSymbol::Fe)),
+        };
+
+        let mut charge = 0.;
+        for (element, stoichiometry) in formula.stoichiometry.iter() {
+            if ANIONS.contains(element) {
+                continue;
+            }
+            charge += stoichiometry * cation_charge(basis, *element)?;
+        }
+
+        let ferric = (2. * oxygens - charge).clamp(0., iron);
+        formula.stoichiometry.remove(&ElementSymbol::O);
+        formula.add_element(ElementSymbol::O, (charge + ferric) / 2.);
+
+        Ok(FerricIronEstimate {
+            formula,
+            ferrous: iron - ferric,
+            ferric,
+        })
+    }
+
+    fn scaled(&self, moles: &BTreeMap<ElementSymbol, f64>, factor: f64) -> ChemicalFormula {
+        let mut formula = ChemicalFormula::new();
+
+        for element in self.element.iter() {
+            if let Some(amount) = moles.get(element) {
+                formula.add_element(*element, amount * factor);
+            }
+        }
+
+        formula
+    }
+}
+
+/// Charge of a cation from its oxide, with Fe as Fe2+
+fn cation_charge(basis: &OxideBasis, element: ElementSymbol) -> Result<f64, FormulaError> {
+    if element == ElementSymbol::Fe {
+        return Ok(2.);
+    }
+
+    let oxide = basis.get(element).ok_or(FormulaError::NoOxide(element))?;
+    let count = oxide
+        .stoichiometry
+        .get(&element)
+        .copied()
+        .ok_or(FormulaError::ElementNotInFormula(element))?;
+
+    Ok(2. * oxide.stoichiometry.get(&ElementSymbol::O).unwrap_or(&0.) / count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_formula;
+    use approx::assert_abs_diff_eq;
+
+    #[test]
+    fn test_normalize_to() {
+        let garnet = parse_formula("Ca3Fe2Si3O12").unwrap();
+
+        let apfu = garnet.normalize_to(ElementSymbol::O, 24.0).unwrap();
+        assert_abs_diff_eq!(apfu.stoichiometry[&ElementSymbol::Ca], 6.0, epsilon = 1e-9);
+        assert_eq!(apfu.to_string(), "Ca6Fe4Si6O24");
+
+        let apfu = garnet.normalize_to_cations(4.0).unwrap();
+        assert_abs_diff_eq!(apfu.stoichiometry[&ElementSymbol::Si], 1.5, epsilon = 1e-9);
+        assert_abs_diff_eq!(apfu.stoichiometry[&ElementSymbol::O], 6.0, epsilon = 1e-9);
+
+        // Element wt% without O is completed with the O of the oxides
+        let mut measured = ChemicalFormula::new();
+        measured
+            .add_wt_percent(ElementSymbol::Mg, 34.55)
+            .add_wt_percent(ElementSymbol::Si, 19.96);
+        let formula = measured
+            .to_oxide_wt_percent(&OxideBasis::new())
+            .unwrap()
+            .to_formula()
+            .unwrap();
+        let apfu = formula.normalize_to(ElementSymbol::O, 4.0).unwrap();
+        assert_abs_diff_eq!(apfu.stoichiometry[&ElementSymbol::Mg], 2.0, epsilon = 1e-3);
+        assert_abs_diff_eq!(apfu.stoichiometry[&ElementSymbol::Si], 1.0, epsilon = 1e-3);
+
+        assert!(matches!(
+            measured.normalize_to(ElementSymbol::O, 4.0),
+            Err(FormulaError::ElementNotInFormula(ElementSymbol::O))
+        ));
+        assert!(matches!(
+            parse_formula("O2").unwrap().normalize_to_cations(1.0),
+            Err(FormulaError::NoFormula)
+        ));
+    }
+
+    #[test]
+    fn test_estimate_ferric_iron() {
+        // Andradite with the total Fe as FeO
+        let oxides = parse_formula("Ca3Fe2Si3O12")
+            .unwrap()
+            .to_oxide_wt_percent(
+                OxideBasis::new().oxide(ElementSymbol::Fe, parse_formula("FeO").unwrap()),
+            )
+            .unwrap();
+        let estimate = oxides
+            .to_formula()
+            .unwrap()
+            .estimate_ferric_iron(12.0, 8.0, &OxideBasis::new())
+            .unwrap();
+        assert_abs_diff_eq!(estimate.ferric, 2.0, epsilon = 1e-9);
+        assert_abs_diff_eq!(estimate.ferrous, 0.0, epsilon = 1e-9);
+        assert_abs_diff_eq!(estimate.ferric_ratio(), 1.0, epsilon = 1e-9);
+        assert_abs_diff_eq!(
+            estimate.formula.stoichiometry[&ElementSymbol::O],
+            12.0,
+            epsilon = 1e-9
+        );
+
+        // Almandine has no Fe3+, and the cation excess of an analysis is not balanced by negative Fe3+
+        let estimate = parse_formula("Fe3Al2Si3O12")
+            .unwrap()
+            .estimate_ferric_iron(12.0, 8.0, &OxideBasis::new())
+            .unwrap();
+        assert_abs_diff_eq!(estimate.ferric, 0.0, epsilon = 1e-9);
+        let estimate = parse_formula("Fe3Al2Si3O12")
+            .unwrap()
+            .estimate_ferric_iron(11.0, 8.0, &OxideBasis::new())
+            .unwrap();
+        assert_abs_diff_eq!(estimate.ferric, 0.0);
+        assert_abs_diff_eq!(estimate.ferrous, 3.0, epsilon = 1e-9);
+
+        assert!(matches!(
+            parse_formula("Mg2SiO4")
+                .unwrap()
+                .estimate_ferric_iron(4.0, 3.0, &OxideBasis::new()),
+            Err(FormulaError::ElementNotInFormula(ElementSymbol::Fe))
+        ));
+        assert!(matches!(
+            parse_formula("PtFe2O4")
+                .unwrap()
+                .estimate_ferric_iron(4.0, 3.0, &OxideBasis::new()),
+            Err(FormulaError::NoOxide(ElementSymbol::Pt))
+        ));
+    }
+}
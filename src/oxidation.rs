@@ -0,0 +1,535 @@
+//! # Oxidation states
+//!
+//! This module assigns oxidation states to the elements of a formula by charge balance.
+//!
+//! `OxidationStateTable` holds the oxidation states allowed for each element, with the common oxidation states
+//! of `ElementSymbol::oxidation_states()` as defaults, e.g. +2, +3, +4, +6 and +7 for Mn.
+//! Fe and Co also take +4 as in perovskites such as `SrFeO3` and `La0.6Sr0.4Co0.2Fe0.8O3`, with +3 as the most common state.
+//! `ChemicalFormula::assign_oxidation_states` enumerates the assignments that balance the charge of the formula,
+//! e.g. Fe2+ and 2 Fe3+ for `Fe3O4` or Mn3+ for `LaMnO3`, and returns `FormulaError::NoOxidationStates`
+//! for a formula that cannot be balanced, which is usually a typo.
+//!
+//! # Example
+//! ```
+//! use chemical_formula::prelude::*;
+//!
+//! let table = OxidationStateTable::new();
+//!
+//! let assignments = parse_formula("LaMnO3").unwrap().assign_oxidation_states(&table).unwrap();
+//! assert_eq!(assignments[0].average(ElementSymbol::Mn), Some(3.0));
+//!
+//! assert!(matches!(
+//!     parse_formula("LaMnO4").unwrap().assign_oxidation_states(&table),
+//!     Err(FormulaError::NoOxidationStates)
+//! ));
+//! ```
+
+use crate::element::{ChemicalFormula, ElementSymbol, FormulaError};
+use crate::periodic::ELEMENTS;
+use std::collections::BTreeMap;
+
+/// Largest multiplier tried to make a non-integer stoichiometry integer
+const MAX_MULTIPLIER: u32 = 100;
+
+/// Default largest number of assignments returned by `assign_oxidation_states`
+const MAX_ASSIGNMENTS: usize = 100;
+
+/// Default states that replace `ElementSymbol::oxidation_states()` for oxides, the most common first
+const OXIDE_STATES: &[(ElementSymbol, &[i32])] = &[
+    (ElementSymbol::Fe, &[3, 2, 4]),
+    (ElementSymbol::Co, &[3, 2, 4]),
+];
+
+/// Oxidation states allowed for each element
+///
+/// # Example
+/// ```
+/// use chemical_formula::prelude::*;
+///
+/// let mut table = OxidationStateTable::new();
+/// assert_eq!(table.get(ElementSymbol::Fe), Some(&[3, 2, 4][..]));
+///
+/// // Allow peroxide
+/// table.states(ElementSymbol::O, vec![-2, -1]);
+/// assert_eq!(table.get(ElementSymbol::O), Some(&[-2, -1][..]));
+///
+/// // Only the best assignment
+/// table.max_assignments(1);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OxidationStateTable {
+    pub states: BTreeMap<ElementSymbol, Vec<i32>>,
+    /// Largest number of assignments returned by `ChemicalFormula::assign_oxidation_states`. The default is 100.
+    pub max_assignments: usize,
+}
+
+impl Default for OxidationStateTable {
+    fn default() -> Self {
+        OxidationStateTable::new()
+    }
+}
+
+impl OxidationStateTable {
+    /// Create a table with the common oxidation states of `ElementSymbol::oxidation_states()`
+    ///
+    /// Fe and Co take +3, +2 and +4 in this order.
+    pub fn new() -> Self {
+        let mut states: BTreeMap<ElementSymbol, Vec<i32>> = ELEMENTS
+            .iter()
+            .filter(|data| !data.oxidation_states.is_empty())
+            .map(|data| (data.symbol, data.oxidation_states.to_vec()))
+            .collect();
+        for (element, oxide_states) in OXIDE_STATES {
+            states.insert(*element, oxide_states.to_vec());
+        }
+
+        OxidationStateTable {
+            states,
+            max_assignments: MAX_ASSIGNMENTS,
+        }
+    }
+
+    /// Create a table without oxidation states
+    pub fn empty() -> Self {
+        OxidationStateTable {
+            states: BTreeMap::new(),
+            max_assignments: MAX_ASSIGNMENTS,
+        }
+    }
+
+    /// Set the oxidation states of an element, the most common first
+    pub fn states(&mut self, element: ElementSymbol, states: Vec<i32>) -> &mut Self {
+        self.states.insert(element, states);
+        self
+    }
+
+    /// Set the largest number of assignments returned by `ChemicalFormula::assign_oxidation_states`
+    pub fn max_assignments(&mut self, max_assignments: usize) -> &mut Self {
+        self.max_assignments = max_assignments;
+        self
+    }
+
+    /// Oxidation states of an element
+    pub fn get(&self, element: ElementSymbol) -> Option<&[i32]> {
+        self.states.get(&element).map(|states| states.as_slice())
+    }
+}
+
+/// Oxidation states of the elements that balance the charge of a formula
+#[derive(Debug, Clone, PartialEq)]
+pub struct OxidationStateAssignment {
+    /// Number of atoms per formula unit in each oxidation state, in ascending order of the state
+    pub states: BTreeMap<ElementSymbol, Vec<(i32, f64)>>,
+}
+
+impl OxidationStateAssignment {
+    /// Average oxidation state of an element
+    pub fn average(&self, element: ElementSymbol) -> Option<f64> {
+        let states = self.states.get(&element)?;
+        let count: f64 = states.iter().map(|(_, count)| count).sum();
+
+        Some(
+            states
+                .iter()
+                .map(|(state, count)| *state as f64 * count)
+                .sum::<f64>()
+                / count,
+        )
+    }
+
+    /// Whether an element is in more than one oxidation state
+    pub fn is_mixed_valence(&self) -> bool {
+        self.states.values().any(|states| states.len() > 1)
+    }
+}
+
+/// Element with its integer count and oxidation states for the search
+struct Site<'a> {
+    element: ElementSymbol,
+    count: i64,
+    states: &'a [i32],
+}
+
+impl ChemicalFormula {
+    /// Enumerate the oxidation state assignments that balance the charge of the formula
+    ///
+    /// Each element takes one oxidation state of `table`, or a mix of two, e.g. Fe2+ and Fe3+ in `Fe3O4`.
+    /// A non-integer stoichiometry, e.g. `La0.7Sr0.3MnO3`, is multiplied up to 100 times to make it integer,
+    /// so the mixed valence is resolved to 0.01 atoms. A neutral formula of a single element is given the oxidation state 0.
+    /// The charge of an ion, e.g. `SO4^2-`, is balanced. wt% and at% terms are converted with `to_molecular_formula()`.
+    ///
+    /// Only the assignments with the fewest mixed-valence elements are returned, so the search stops
+    /// as soon as the charge can be balanced. They are sorted with the smallest spread of the mixed states first,
+    /// then the most common states of `table`, then the higher states on the less electronegative elements,
+    /// e.g. Ni2+, Co3+ and Mn4+ in `Li3NiMnCoO6`, and truncated to `table.max_assignments`.
+    ///
+    /// Returns `FormulaError::UnknownOxidationStates` for an element without oxidation states in `table`,
+    /// `FormulaError::NonIntegerStoichiometry` if the stoichiometry cannot be made integer,
+    /// and `FormulaError::NoOxidationStates` if the charge cannot be balanced.
+    ///
+    /// # Example
+    /// ```
+    /// use chemical_formula::prelude::*;
+    ///
+    /// let table = OxidationStateTable::new();
+    ///
+    /// let assignments = parse_formula("Fe3O4").unwrap().assign_oxidation_states(&table).unwrap();
+    /// assert_eq!(assignments[0].states[&ElementSymbol::Fe], vec![(2, 1.0), (3, 2.0)]);
+    /// assert!(assignments[0].is_mixed_valence());
+    ///
+    /// let assignments = parse_formula("La0.7Sr0.3MnO3").unwrap().assign_oxidation_states(&table).unwrap();
+    /// assert_eq!(assignments[0].states[&ElementSymbol::Mn], vec![(3, 0.7), (4, 0.3)]);
+    /// ```
+    pub fn assign_oxidation_states(
+        &self,
+        table: &OxidationStateTable,
+    ) -> Result<Vec<OxidationStateAssignment>, FormulaError> {
+        let formula = if self.wt_percent.is_empty() && self.at_percent.is_empty() {
+            self.clone()
+        } else {
+            self.to_molecular_formula()?
+        };
+
+        let stoichiometry: Vec<(ElementSymbol, f64)> = formula
+            .stoichiometry
+            .iter()
+            .filter(|(_, stoichiometry)| **stoichiometry > 0.)
+            .map(|(element, stoichiometry)| (*element, *stoichiometry))
+            .collect();
+
+        if stoichiometry.is_empty() {
+            return Err(FormulaError::NoFormula);
+        }
+
+        let multiplier = (1..=MAX_MULTIPLIER)
+            .find(|multiplier| {
+                stoichiometry.iter().all(|(_, stoichiometry)| {
+                    let scaled = stoichiometry * *multiplier as f64;
+                    (scaled - scaled.round()).abs() < 1e-6
+                })
+            })
+            .ok_or_else(|| {
+                let (element, _) = stoichiometry
+                    .iter()
+                    .find(|(_, stoichiometry)| stoichiometry.fract() != 0.)
+                    .copied()
+                    .unwrap_or(stoichiometry[0]);
+                FormulaError::NonIntegerStoichiometry(element)
+            })?;
+
+        if stoichiometry.len() == 1 && formula.charge == 0 {
+            let (element, count) = stoichiometry[0];
+            return Ok(vec![OxidationStateAssignment {
+                states: BTreeMap::from([(element, vec![(0, count)])]),
+            }]);
+        }
+
+        let sites = stoichiometry
+            .iter()
+            .map(|(element, stoichiometry)| {
+                let states = table
+                    .get(*element)
+                    .filter(|states| !states.is_empty())
+                    .ok_or(FormulaError::UnknownOxidationStates(*element))?;
+                Ok(Site {
+                    element: *element,
+                    count: (stoichiometry * multiplier as f64).round() as i64,
+                    states,
+                })
+            })
+            .collect::<Result<Vec<Site>, FormulaError>>()?;
+
+        // Range of the charge of the sites from each index to the end
+        let mut bounds = vec![(0, 0); sites.len() + 1];
+        for (i, site) in sites.iter().enumerate().rev() {
+            let min = *site.states.iter().min().unwrap() as i64;
+            let max = *site.states.iter().max().unwrap() as i64;
+            bounds[i] = (
+                bounds[i + 1].0 + min * site.count,
+                bounds[i + 1].1 + max * site.count,
+            );
+        }
+
+        let mut solutions = Vec::new();
+        for mixed in 0..=sites.len() {
+            search(
+                &sites,
+                &bounds,
+                0,
+                formula.charge as i64 * multiplier as i64,
+                mixed,
+                &mut Vec::new(),
+                &mut solutions,
+            );
+            if !solutions.is_empty() {
+                break;
+            }
+        }
+
+        if solutions.is_empty() {
+            return Err(FormulaError::NoOxidationStates);
+        }
+
+        let rank = |solution: &Vec<Vec<(usize, i64)>>| {
+            let spread: i32 = solution
+                .iter()
+                .zip(sites.iter())
+                .map(|(states, site)| {
+                    let states: Vec<i32> = states.iter().map(|(j, _)| site.states[*j]).collect();
+                    states.iter().max().unwrap() - states.iter().min().unwrap()
+                })
+                .sum();
+            let rarity: i64 = solution
+                .iter()
+                .flatten()
+                .map(|(j, count)| *j as i64 * count)
+                .sum();
+            // Lower if the higher states are on the less electronegative elements
+            let electronegativity: f64 = solution
+                .iter()
+                .zip(sites.iter())
+                .flat_map(|(states, site)| {
+                    let electronegativity = site
+                        .element
+                        .data()
+                        .and_then(|data| data.electronegativity)
+                        .unwrap_or(0.);
+                    states.iter().map(move |(j, count)| {
+                        (site.states[*j] as i64 * count) as f64 * electronegativity
+                    })
+                })
+                .sum();
+            ((spread, rarity), electronegativity)
+        };
+        let mut solutions: Vec<_> = solutions
+            .into_iter()
+            .map(|solution| (rank(&solution), solution))
+            .collect();
+        solutions.sort_by(
+            |((a, a_electronegativity), _), ((b, b_electronegativity), _)| {
+                a.cmp(b)
+                    .then(a_electronegativity.total_cmp(b_electronegativity))
+            },
+        );
+        solutions.truncate(table.max_assignments);
+
+        Ok(solutions
+            .into_iter()
+            .map(|(_, solution)| {
+                let states = solution
+                    .into_iter()
+                    .zip(sites.iter())
+                    .map(|(states, site)| {
+                        let mut states: Vec<(i32, f64)> = states
+                            .into_iter()
+                            .map(|(j, count)| (site.states[j], count as f64 / multiplier as f64))
+                            .collect();
+                        states.sort_by_key(|(state, _)| *state);
+                        (site.element, states)
+                    })
+                    .collect();
+                OxidationStateAssignment { states }
+            })
+            .collect())
+    }
+}
+
+/// Depth-first search over the sites for the assignments with the total charge `target`
+///
+/// Each assignment is a list of `(index of the state, count)` for each site,
+/// with a mix of two states on at most `mixed` sites.
+fn search(
+    sites: &[Site],
+    bounds: &[(i64, i64)],
+    index: usize,
+    target: i64,
+    mixed: usize,
+    current: &mut Vec<Vec<(usize, i64)>>,
+    solutions: &mut Vec<Vec<Vec<(usize, i64)>>>,
+) {
+    let Some(site) = sites.get(index) else {
+        if target == 0 {
+            solutions.push(current.clone());
+        }
+        return;
+    };
+
+    let (min, max) = bounds[index + 1];
+    let mut visit = |states: Vec<(usize, i64)>, charge: i64, mixed: usize| {
+        let remaining = target - charge;
+        if remaining >= min && remaining <= max {
+            current.push(states);
+            search(
+                sites,
+                bounds,
+                index + 1,
+                remaining,
+                mixed,
+                current,
+                solutions,
+            );
+            current.pop();
+        }
+    };
+
+    for (j, state) in site.states.iter().enumerate() {
+        visit(vec![(j, site.count)], *state as i64 * site.count, mixed);
+    }
+
+    if mixed == 0 {
+        return;
+    }
+
+    // Mix of two states, where the count in the first state is fixed by the charge if this is the last site
+    for (j, first) in site.states.iter().enumerate() {
+        for (k, second) in site.states.iter().enumerate().skip(j + 1) {
+            for count in 1..site.count {
+                let charge = *first as i64 * count + *second as i64 * (site.count - count);
+                visit(vec![(j, count), (k, site.count - count)], charge, mixed - 1);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_formula;
+    use approx::assert_abs_diff_eq;
+
+    fn assign(formula: &str) -> Result<Vec<OxidationStateAssignment>, FormulaError> {
+        parse_formula(formula)
+            .unwrap()
+            .assign_oxidation_states(&OxidationStateTable::new())
+    }
+
+    #[test]
+    fn test_assign_oxidation_states() {
+        for (formula, element, average) in [
+            ("LaMnO3", ElementSymbol::Mn, 3.0),
+            ("SrTiO3", ElementSymbol::Ti, 4.0),
+            ("LiCoO2", ElementSymbol::Co, 3.0),
+            ("KMnO4", ElementSymbol::Mn, 7.0),
+            ("NiFe2O4", ElementSymbol::Fe, 3.0),
+            ("CeO1.9", ElementSymbol::Ce, 3.8),
+            ("SO4^2-", ElementSymbol::S, 6.0),
+            ("NH4+", ElementSymbol::N, -3.0),
+            ("Pt", ElementSymbol::Pt, 0.0),
+            ("Fe3+", ElementSymbol::Fe, 3.0),
+        ] {
+            let assignments = assign(formula).unwrap();
+            assert_abs_diff_eq!(
+                assignments[0].average(element).unwrap(),
+                average,
+                epsilon = 1e-9
+            );
+        }
+
+        // 2 Fe2+ Fe4+ is also balanced, but has a larger spread
+        let assignments = assign("Fe3O4").unwrap();
+        assert_eq!(assignments.len(), 2);
+        assert_eq!(
+            assignments[0].states[&ElementSymbol::Fe],
+            vec![(2, 1.0), (3, 2.0)]
+        );
+        assert_eq!(assignments[0].states[&ElementSymbol::O], vec![(-2, 4.0)]);
+
+        // Mn2+ Mn3+2 is preferred to Mn2+2 Mn4+
+        let assignments = assign("Mn3O4").unwrap();
+        assert_eq!(
+            assignments[0].states[&ElementSymbol::Mn],
+            vec![(2, 1.0), (3, 2.0)]
+        );
+        assert!(assignments.len() > 1);
+
+        // Cu2+ Fe3+ is preferred to mixed valence
+        let assignments = assign("CuFe2O4").unwrap();
+        assert!(!assignments[0].is_mixed_valence());
+        assert_eq!(assignments[0].average(ElementSymbol::Cu), Some(2.0));
+
+        let assignments = assign("BaO2").unwrap_err();
+        assert!(matches!(assignments, FormulaError::NoOxidationStates));
+
+        let mut table = OxidationStateTable::new();
+        table.states(ElementSymbol::O, vec![-2, -1]);
+        let assignments = parse_formula("BaO2")
+            .unwrap()
+            .assign_oxidation_states(&table)
+            .unwrap();
+        assert_eq!(assignments[0].average(ElementSymbol::O), Some(-1.0));
+    }
+
+    #[test]
+    fn test_ferrite_perovskite() {
+        let assignments = assign("SrFeO3").unwrap();
+        assert_eq!(assignments[0].states[&ElementSymbol::Fe], vec![(4, 1.0)]);
+
+        // LSCF cathode with Co and Fe between +3 and +4
+        let assignments = assign("La0.6Sr0.4Co0.2Fe0.8O3").unwrap();
+        let co = assignments[0].average(ElementSymbol::Co).unwrap();
+        let fe = assignments[0].average(ElementSymbol::Fe).unwrap();
+        assert!((3.0..=4.0).contains(&co) && (3.0..=4.0).contains(&fe));
+        assert_abs_diff_eq!(0.2 * co + 0.8 * fe, 3.4, epsilon = 1e-9);
+
+        let assignments = assign("LaCoO3").unwrap();
+        assert_eq!(assignments[0].states[&ElementSymbol::Co], vec![(3, 1.0)]);
+    }
+
+    #[test]
+    fn test_mixed_metal_oxide() {
+        // NMC111 cathode, Ni2+ Co3+ Mn4+ with the small excess of charge on one element
+        let assignments = assign("LiNi0.33Mn0.33Co0.33O2").unwrap();
+        assert!(assignments.len() <= OxidationStateTable::new().max_assignments);
+        for (element, state) in [
+            (ElementSymbol::Li, 1.0),
+            (ElementSymbol::Ni, 2.0),
+            (ElementSymbol::Co, 3.0),
+            (ElementSymbol::Mn, 4.0),
+        ] {
+            assert_abs_diff_eq!(
+                assignments[0].average(element).unwrap(),
+                state,
+                epsilon = 0.1
+            );
+        }
+
+        let mut table = OxidationStateTable::new();
+        table.max_assignments(1);
+        let assignments = parse_formula("LiNi0.33Mn0.33Co0.33O2")
+            .unwrap()
+            .assign_oxidation_states(&table)
+            .unwrap();
+        assert_eq!(assignments.len(), 1);
+
+        // Exact 1/3 stoichiometry needs no mixed valence
+        let assignments = assign("Li3NiMnCoO6").unwrap();
+        assert!(!assignments[0].is_mixed_valence());
+        assert_eq!(assignments[0].average(ElementSymbol::Ni), Some(2.0));
+        assert_eq!(assignments[0].average(ElementSymbol::Co), Some(3.0));
+        assert_eq!(assignments[0].average(ElementSymbol::Mn), Some(4.0));
+    }
+
+    #[test]
+    fn test_oxidation_state_errors() {
+        // Typos of perovskite and spinel formulas
+        for formula in ["LaMnO4", "MgAl2O3", "SrTiO4"] {
+            assert!(matches!(
+                assign(formula),
+                Err(FormulaError::NoOxidationStates)
+            ));
+        }
+
+        assert!(matches!(
+            assign("NeF2"),
+            Err(FormulaError::UnknownOxidationStates(ElementSymbol::Ne))
+        ));
+        assert!(matches!(
+            assign("Fe0.333O"),
+            Err(FormulaError::NonIntegerStoichiometry(ElementSymbol::Fe))
+        ));
+        assert!(matches!(
+            ChemicalFormula::new().assign_oxidation_states(&OxidationStateTable::new()),
+            Err(FormulaError::NoFormula)
+        ));
+    }
+}
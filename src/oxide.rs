@@ -0,0 +1,394 @@
+//! # Oxide basis
+//!
+//! This module converts a composition between the element basis and the oxide basis.
+//!
+//! Compositions of rocks, glasses and ceramics are reported as oxide wt%, e.g. `SiO2 72.1, Al2O3 13.5, Na2O 4.2`.
+//! `OxideBasis` holds the oxide used for each element, e.g. Fe as `FeO` or `Fe2O3`, with defaults for the common elements.
+//! `ChemicalFormula::to_oxide_wt_percent` reports a formula as oxide wt% with the oxygen by stoichiometry,
+//! and `ChemicalFormula::from_oxide_wt_percent` gives the formula per 100 g from oxide wt%.
+//!
+//! # Example
+//! ```
+//! use chemical_formula::prelude::*;
+//! use approx::assert_abs_diff_eq;
+//!
+//! let formula = parse_formula("NaAlSi3O8").unwrap();
+//! let composition = formula.to_oxide_wt_percent(&OxideBasis::new()).unwrap();
+//!
+//! let oxides: Vec<String> = composition
+//!     .oxides
+//!     .iter()
+//!     .map(|oxide| oxide.oxide.to_string())
+//!     .collect();
+//! assert_eq!(oxides, vec!["Na2O", "Al2O3", "SiO2"]);
+//! assert_abs_diff_eq!(composition.oxides[2].wt_percent, 68.74, epsilon = 1e-2);
+//! assert_abs_diff_eq!(composition.total(), 100.0, epsilon = 1e-9);
+//! ```
+
+use crate::element::{ChemicalFormula, ElementSymbol, FormulaError};
+use std::collections::BTreeMap;
+
+/// Default oxide of each element as the count of the element and of O
+const DEFAULT_OXIDES: &[(ElementSymbol, f64, f64)] = &[
+    (ElementSymbol::H, 2., 1.),
+    (ElementSymbol::Li, 2., 1.),
+    (ElementSymbol::Be, 1., 1.),
+    (ElementSymbol::B, 2., 3.),
+    (ElementSymbol::C, 1., 2.),
+    (ElementSymbol::Na, 2., 1.),
+    (ElementSymbol::Mg, 1., 1.),
+    (ElementSymbol::Al, 2., 3.),
+    (ElementSymbol::Si, 1., 2.),
+    (ElementSymbol::P, 2., 5.),
+    (ElementSymbol::S, 1., 3.),
+    (ElementSymbol::K, 2., 1.),
+    (ElementSymbol::Ca, 1., 1.),
+    (ElementSymbol::Sc, 2., 3.),
+    (ElementSymbol::Ti, 1., 2.),
+    (ElementSymbol::V, 2., 5.),
+    (ElementSymbol::Cr, 2., 3.),
+    (ElementSymbol::Mn, 1., 1.),
+    (ElementSymbol::Fe, 2., 3.),
+    (ElementSymbol::Co, 1., 1.),
+    (ElementSymbol::Ni, 1., 1.),
+    (ElementSymbol::Cu, 1., 1.),
+    (ElementSymbol::Zn, 1., 1.),
+    (ElementSymbol::Ga, 2., 3.),
+    (ElementSymbol::Ge, 1., 2.),
+    (ElementSymbol::As, 2., 5.),
+    (ElementSymbol::Rb, 2., 1.),
+    (ElementSymbol::Sr, 1., 1.),
+    (ElementSymbol::Y, 2., 3.),
+    (ElementSymbol::Zr, 1., 2.),
+    (ElementSymbol::Nb, 2., 5.),
+    (ElementSymbol::Mo, 1., 3.),
+    (ElementSymbol::Ag, 2., 1.),
+    (ElementSymbol::Cd, 1., 1.),
+    (ElementSymbol::In, 2., 3.),
+    (ElementSymbol::Sn, 1., 2.),
+    (ElementSymbol::Sb, 2., 3.),
+    (ElementSymbol::Cs, 2., 1.),
+    (ElementSymbol::Ba, 1., 1.),
+    (ElementSymbol::La, 2., 3.),
+    (ElementSymbol::Ce, 1., 2.),
+    (ElementSymbol::Pr, 6., 11.),
+    (ElementSymbol::Nd, 2., 3.),
+    (ElementSymbol::Sm, 2., 3.),
+    (ElementSymbol::Eu, 2., 3.),
+    (ElementSymbol::Gd, 2., 3.),
+    (ElementSymbol::Tb, 4., 7.),
+    (ElementSymbol::Dy, 2., 3.),
+    (ElementSymbol::Ho, 2., 3.),
+    (ElementSymbol::Er, 2., 3.),
+    (ElementSymbol::Tm, 2., 3.),
+    (ElementSymbol::Yb, 2., 3.),
+    (ElementSymbol::Lu, 2., 3.),
+    (ElementSymbol::Hf, 1., 2.),
+    (ElementSymbol::Ta, 2., 5.),
+    (ElementSymbol::W, 1., 3.),
+    (ElementSymbol::Pb, 1., 1.),
+    (ElementSymbol::Bi, 2., 3.),
+    (ElementSymbol::Th, 1., 2.),
+    (ElementSymbol::U, 1., 2.),
+];
+
+/// Oxide used for each element in the oxide basis
+///
+/// `new()` has the oxides commonly used in geochemistry and glass chemistry,
+/// e.g. `SiO2`, `Al2O3`, `Na2O`, `MnO` and `Fe2O3`. `oxide()` overrides the oxide of an element,
+/// and an element can be reported as itself, e.g. F or Cl, by setting the element alone as its oxide.
+///
+/// # Example
+/// ```
+/// use chemical_formula::prelude::*;
+///
+/// let mut basis = OxideBasis::new();
+/// basis.oxide(ElementSymbol::Fe, parse_formula("FeO").unwrap());
+///
+/// assert_eq!(basis.get(ElementSymbol::Fe).unwrap().to_string(), "FeO");
+/// assert_eq!(basis.get(ElementSymbol::Si).unwrap().to_string(), "SiO2");
+/// assert!(basis.get(ElementSymbol::Pt).is_none());
+/// ```
+#[derive(Debug, Clone)]
+pub struct OxideBasis {
+    pub oxides: BTreeMap<ElementSymbol, ChemicalFormula>,
+}
+
+impl Default for OxideBasis {
+    fn default() -> Self {
+        OxideBasis::new()
+    }
+}
+
+impl OxideBasis {
+    /// Create a basis with the default oxides
+    pub fn new() -> Self {
+        let oxides = DEFAULT_OXIDES
+            .iter()
+            .map(|(element, count, oxygen)| {
+                let mut oxide = ChemicalFormula::new();
+                oxide
+                    .add_element(*element, *count)
+                    .add_element(ElementSymbol::O, *oxygen);
+                (*element, oxide)
+            })
+            .collect();
+
+        OxideBasis { oxides }
+    }
+
+    /// Create a basis without oxides
+    pub fn empty() -> Self {
+        OxideBasis {
+            oxides: BTreeMap::new(),
+        }
+    }
+
+    /// Set the oxide of an element
+    pub fn oxide(&mut self, element: ElementSymbol, oxide: ChemicalFormula) -> &mut Self {
+        self.oxides.insert(element, oxide);
+        self
+    }
+
+    /// Oxide of an element
+    pub fn get(&self, element: ElementSymbol) -> Option<&ChemicalFormula> {
+        self.oxides.get(&element)
+    }
+}
+
+/// wt% of an oxide in `OxideComposition`
+#[derive(Debug, Clone)]
+pub struct OxideWtPercent {
+    /// Element reported as the oxide
+    pub element: ElementSymbol,
+    pub oxide: ChemicalFormula,
+    pub wt_percent: f64,
+}
+
+/// Composition on the oxide basis returned by `ChemicalFormula::to_oxide_wt_percent`
+#[derive(Debug, Clone)]
+pub struct OxideComposition {
+    /// Oxides in the element order of the formula
+    pub oxides: Vec<OxideWtPercent>,
+    /// wt% of O in the formula minus the wt% of O by the stoichiometry of the oxides
+    pub excess_oxygen: f64,
+}
+
+impl OxideComposition {
+    /// Sum of the oxide wt%
+    pub fn total(&self) -> f64 {
+        self.oxides.iter().map(|oxide| oxide.wt_percent).sum()
+    }
+
+    /// Create a formula from the oxides
+    ///
+    /// This is `ChemicalFormula::from_oxide_wt_percent` of the oxides, so the O is given by stoichiometry.
+    pub fn to_formula(&self) -> Result<ChemicalFormula, FormulaError> {
+        let oxides: Vec<(ChemicalFormula, f64)> = self
+            .oxides
+            .iter()
+            .map(|oxide| (oxide.oxide.clone(), oxide.wt_percent))
+            .collect();
+
+        ChemicalFormula::from_oxide_wt_percent(&oxides)
+    }
+}
+
+impl ChemicalFormula {
+    /// Report the composition as oxide wt%
+    ///
+    /// Each element other than O is converted to the oxide of `basis`, and the oxygen is given by the stoichiometry of the oxides.
+    /// The O of the formula is not used for the oxides. The difference is reported as `OxideComposition::excess_oxygen`,
+    /// so the oxides sum to 100 minus the excess oxygen.
+    /// A formula given only by wt% terms, e.g. ICP results without O, is converted as it is,
+    /// and the oxides sum to the analytical total.
+    ///
+    /// Returns `FormulaError::NoOxide` for an element without an oxide in `basis`,
+    /// and `FormulaError::ElementNotInFormula` if the oxide of an element does not contain it.
+    ///
+    /// # Example
+    /// ```
+    /// use chemical_formula::prelude::*;
+    /// use approx::assert_abs_diff_eq;
+    ///
+    /// let mut measured = ChemicalFormula::new();
+    /// measured
+    ///     .add_wt_percent(ElementSymbol::Fe, 20.0)
+    ///     .add_wt_percent(ElementSymbol::Si, 20.0);
+    ///
+    /// let mut basis = OxideBasis::new();
+    /// let composition = measured.to_oxide_wt_percent(&basis).unwrap();
+    /// assert_abs_diff_eq!(composition.oxides[0].wt_percent, 28.59, epsilon = 1e-2);
+    ///
+    /// basis.oxide(ElementSymbol::Fe, parse_formula("FeO").unwrap());
+    /// let composition = measured.to_oxide_wt_percent(&basis).unwrap();
+    /// assert_abs_diff_eq!(composition.oxides[0].wt_percent, 25.73, epsilon = 1e-2);
+    /// assert_abs_diff_eq!(composition.oxides[1].wt_percent, 42.79, epsilon = 1e-2);
+    /// ```
+    pub fn to_oxide_wt_percent(
+        &self,
+        basis: &OxideBasis,
+    ) -> Result<OxideComposition, FormulaError> {
+        let wt_percent = self.measured_composition()?.wt_percent;
+
+        let mut oxides = Vec::new();
+        let mut oxygen = 0.;
+
+        for element in self.element.iter() {
+            if *element == ElementSymbol::O {
+                continue;
+            }
+            let Some(wt_ratio) = wt_percent.get(element) else {
+                continue;
+            };
+
+            let oxide = basis.get(*element).ok_or(FormulaError::NoOxide(*element))?;
+            let count = oxide
+                .stoichiometry
+                .get(element)
+                .copied()
+                .ok_or(FormulaError::ElementNotInFormula(*element))?;
+
+            // mol of the oxide per 100 g of the formula
+            let moles = wt_ratio / (count * element.atomic_weight());
+            oxygen += moles
+                * oxide.stoichiometry.get(&ElementSymbol::O).unwrap_or(&0.)
+                * ElementSymbol::O.atomic_weight();
+
+            oxides.push(OxideWtPercent {
+                element: *element,
+                oxide: oxide.clone(),
+                wt_percent: moles * oxide.molecular_weight()?,
+            });
+        }
+
+        Ok(OxideComposition {
+            oxides,
+            excess_oxygen: wt_percent.get(&ElementSymbol::O).unwrap_or(&0.) - oxygen,
+        })
+    }
+
+    /// Create a formula from oxide wt%
+    ///
+    /// The stoichiometry is the mol of each element in 100 g of the oxides, so it is not normalised.
+    /// `to_wt_percent()` gives the element wt% normalised to the sum of the oxides.
+    ///
+    /// # Example
+    /// ```
+    /// use chemical_formula::prelude::*;
+    /// use approx::assert_abs_diff_eq;
+    ///
+    /// let formula = ChemicalFormula::from_oxide_wt_percent(&[
+    ///     (parse_formula("SiO2").unwrap(), 72.1),
+    ///     (parse_formula("Al2O3").unwrap(), 13.5),
+    ///     (parse_formula("Na2O").unwrap(), 14.4),
+    /// ])
+    /// .unwrap();
+    ///
+    /// assert_abs_diff_eq!(formula.stoichiometry[&ElementSymbol::Si], 72.1 / 60.083, epsilon = 1e-3);
+    ///
+    /// let composition = formula.to_oxide_wt_percent(&OxideBasis::new()).unwrap();
+    /// assert_abs_diff_eq!(composition.oxides[0].wt_percent, 72.1, epsilon = 1e-9);
+    /// assert_abs_diff_eq!(composition.excess_oxygen, 0.0, epsilon = 1e-9);
+    /// ```
+    pub fn from_oxide_wt_percent(
+        oxides: &[(ChemicalFormula, f64)],
+    ) -> Result<ChemicalFormula, FormulaError> {
+        let mut formula = ChemicalFormula::new();
+
+        for (oxide, wt_ratio) in oxides.iter() {
+            let moles = wt_ratio / oxide.molecular_weight()?;
+            formula.add_formula(oxide.clone().try_multiply(moles)?);
+        }
+
+        Ok(formula)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_formula;
+    use approx::assert_abs_diff_eq;
+
+    #[test]
+    fn test_oxide_wt_percent() {
+        let basis = OxideBasis::new();
+
+        // Every default oxide is converted to itself
+        for oxide in basis.oxides.values() {
+            let composition = oxide.to_oxide_wt_percent(&basis).unwrap();
+            assert_eq!(composition.oxides.len(), 1);
+            assert_abs_diff_eq!(composition.total(), 100.0, epsilon = 1e-9);
+            assert_abs_diff_eq!(composition.excess_oxygen, 0.0, epsilon = 1e-9);
+        }
+
+        // Fe3O4 has more O than FeO and less than Fe2O3
+        let magnetite = parse_formula("Fe3O4").unwrap();
+        let composition = magnetite.to_oxide_wt_percent(&basis).unwrap();
+        assert!(composition.excess_oxygen < 0.);
+        assert_abs_diff_eq!(
+            composition.total() + composition.excess_oxygen,
+            100.0,
+            epsilon = 1e-9
+        );
+
+        let mut ferrous = OxideBasis::new();
+        ferrous.oxide(ElementSymbol::Fe, parse_formula("FeO").unwrap());
+        let composition = magnetite.to_oxide_wt_percent(&ferrous).unwrap();
+        assert!(composition.excess_oxygen > 0.);
+
+        // An element reported as itself
+        let mut basis = OxideBasis::new();
+        basis.oxide(ElementSymbol::F, parse_formula("F").unwrap());
+        let composition = parse_formula("CaF2")
+            .unwrap()
+            .to_oxide_wt_percent(&basis)
+            .unwrap();
+        assert_eq!(composition.oxides[1].oxide.to_string(), "F");
+        assert_abs_diff_eq!(
+            composition.total() + composition.excess_oxygen,
+            100.0,
+            epsilon = 1e-9
+        );
+    }
+
+    #[test]
+    fn test_from_oxide_wt_percent() {
+        let oxides = [
+            (parse_formula("SiO2").unwrap(), 50.0),
+            (parse_formula("FeO").unwrap(), 10.0),
+            (parse_formula("Fe2O3").unwrap(), 5.0),
+            (parse_formula("MgO").unwrap(), 35.0),
+        ];
+        let formula = ChemicalFormula::from_oxide_wt_percent(&oxides).unwrap();
+        assert_abs_diff_eq!(
+            formula.stoichiometry[&ElementSymbol::Fe],
+            10.0 / 71.844 + 2. * 5.0 / 159.687,
+            epsilon = 1e-3
+        );
+
+        let composition = formula.to_oxide_wt_percent(&OxideBasis::new()).unwrap();
+        assert_abs_diff_eq!(composition.total(), 100.0 - composition.excess_oxygen);
+        assert_abs_diff_eq!(composition.oxides[0].wt_percent, 50.0, epsilon = 1e-9);
+        // FeO and Fe2O3 are reported as Fe2O3 with the O of FeO missing
+        assert!(composition.excess_oxygen < 0.);
+    }
+
+    #[test]
+    fn test_oxide_errors() {
+        assert!(matches!(
+            parse_formula("PtO2")
+                .unwrap()
+                .to_oxide_wt_percent(&OxideBasis::new()),
+            Err(FormulaError::NoOxide(ElementSymbol::Pt))
+        ));
+
+        let mut basis = OxideBasis::empty();
+        basis.oxide(ElementSymbol::Si, parse_formula("Al2O3").unwrap());
+        assert!(matches!(
+            parse_formula("SiO2").unwrap().to_oxide_wt_percent(&basis),
+            Err(FormulaError::ElementNotInFormula(ElementSymbol::Si))
+        ));
+    }
+}
@@ -17,118 +17,841 @@
 //! assert_eq!(formula.stoichiometry[&ElementSymbol::Si], 1.0);
 //! ```
 
-use crate::element::{ChemicalFormula, ElementSymbol, FormulaError};
+use crate::element::{ChemicalFormula, ElementSymbol, FormulaError, UnknownElement};
+use crate::isotope::Isotope;
+use crate::symbolic::{LinearExpression, SymbolicFormula};
+use crate::tree::{Amount, Bracket, FormulaNode, FormulaTree, MassFractionUnit, Separator};
 use pest::Parser;
 use pest_derive::Parser;
 
+use pest::error::{ErrorVariant, InputLocation};
 use pest::iterators::Pair;
 use std::error::Error;
+use std::fmt;
+use std::ops::Range;
 
 #[derive(Parser)]
 #[grammar = "formula.pest"]
 pub struct ChemicalFormulaParser {}
 
-/// A recursive function to parse the chemical formula
-fn parse_formula_pairs(pair: Pair<Rule>) -> ChemicalFormula {
+/// The kind of token the parser was expecting when it failed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Expected {
+    ElementSymbol,
+    Number,
+    OpeningBracket,
+    ClosingBracket(char),
+    Charge,
+    EndOfInput,
+    ReactionArrow,
+}
+
+impl fmt::Display for Expected {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Expected::ElementSymbol => write!(f, "element symbol"),
+            Expected::Number => write!(f, "number"),
+            Expected::OpeningBracket => write!(f, "opening bracket"),
+            Expected::ClosingBracket(c) => write!(f, "closing bracket `{}`", c),
+            Expected::Charge => write!(f, "charge"),
+            Expected::EndOfInput => write!(f, "end of input"),
+            Expected::ReactionArrow => write!(f, "reaction arrow `->`"),
+        }
+    }
+}
+
+/// The reason why a formula could not be parsed
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseErrorKind {
+    /// The input did not match the grammar
+    UnexpectedToken {
+        found: Option<char>,
+        expected: Vec<Expected>,
+    },
+    /// A number token could not be converted to `f64`
+    InvalidNumber(String),
+    /// An element symbol token is not a known element
+    UnknownElement(UnknownElement),
+    /// A mass number is not a known isotope of the element
+    UnknownIsotope(Isotope),
+    /// An isotope is given by wt%, at% or mol%, which is not supported
+    IsotopePercent(Isotope),
+    /// A group has a wt% larger than 100 in total
+    WeightPercentOverflow,
+    /// A group has an at% larger than 100 in total
+    AtomicPercentOverflow,
+    /// A group has a mol% larger than 100 in total
+    MolPercentOverflow,
+    /// The stoichiometry has a variable, which needs `parse_symbolic_formula`
+    UnresolvedVariable(String),
+    /// A `.` can be read as a decimal point or as an adduct dot,
+    /// e.g. `Fe.5O` or a decimal of at least 2 followed by an element such as `Fe2.5O4`
+    AmbiguousDot,
+    /// A number before an element where the formula cannot have a stoichiometry,
+    /// e.g. `13CO2`, which is written `^13CO2` or `[13C]O2` for the isotope
+    MassNumberPrefix(Isotope),
+    /// A number before a single charge sign of a monatomic ion can be read as the stoichiometry
+    /// or as the charge, e.g. `Cl2-`
+    AmbiguousCharge,
+}
+
+impl fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseErrorKind::UnexpectedToken { found, expected } => {
+                match found {
+                    Some(c) => write!(f, "unexpected `{}`", c)?,
+                    None => write!(f, "unexpected end of input")?,
+                }
+
+                for (i, e) in expected.iter().enumerate() {
+                    if i == 0 {
+                        write!(f, ", expected {}", e)?;
+                    } else if i == expected.len() - 1 {
+                        write!(f, " or {}", e)?;
+                    } else {
+                        write!(f, ", {}", e)?;
+                    }
+                }
+                Ok(())
+            }
+            ParseErrorKind::InvalidNumber(s) => write!(f, "invalid number `{}`", s),
+            ParseErrorKind::UnknownElement(err) => write!(f, "{}", err),
+            ParseErrorKind::UnknownIsotope(isotope) => write!(f, "unknown isotope `{}`", isotope),
+            ParseErrorKind::IsotopePercent(isotope) => {
+                write!(
+                    f,
+                    "wt%, at% or mol% of isotope `{}` is not supported",
+                    isotope
+                )
+            }
+            ParseErrorKind::WeightPercentOverflow => write!(f, "weight percent overflow"),
+            ParseErrorKind::AtomicPercentOverflow => write!(f, "atomic percent overflow"),
+            ParseErrorKind::MolPercentOverflow => write!(f, "mol percent overflow"),
+            ParseErrorKind::UnresolvedVariable(variable) => write!(
+                f,
+                "variable `{}` has no value, use `parse_symbolic_formula`",
+                variable
+            ),
+            ParseErrorKind::AmbiguousDot => write!(
+                f,
+                "ambiguous `.`, write an adduct with `·` or `*`, or a decimal with a leading digit and in brackets, e.g. `(Fe2.5)O4`"
+            ),
+            ParseErrorKind::MassNumberPrefix(isotope) => write!(
+                f,
+                "a number before `{}` is not a mass number, write `{}` or `[{}{}]` for the isotope",
+                isotope.element, isotope, isotope.mass_number, isotope.element
+            ),
+            ParseErrorKind::AmbiguousCharge => write!(
+                f,
+                "ambiguous charge, write the charge after `^`, e.g. `Cl2^-` or `Cl^2-`"
+            ),
+        }
+    }
+}
+
+/// Error returned by `parse_formula`
+///
+/// The error carries the byte span of the offending token in the input.
+/// The `Display` implementation renders the input with a caret under the span.
+///
+/// # Example
+/// ```
+/// use chemical_formula::prelude::*;
+/// use chemical_formula::parser::{Expected, ParseErrorKind};
+///
+/// let err = parse_formula("SiO2)").unwrap_err();
+///
+/// assert_eq!(err.span, 4..5);
+/// assert!(matches!(
+///     err.kind,
+///     ParseErrorKind::UnexpectedToken { found: Some(')'), .. }
+/// ));
+///
+/// assert_eq!(
+///     err.to_string(),
+///     "unexpected `)`, expected element symbol, opening bracket, charge or end of input at byte 4\n\
+///      SiO2)\n    ^"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub kind: ParseErrorKind,
+    pub span: Range<usize>,
+    pub input: String,
+}
+
+impl ParseError {
+    pub(crate) fn new(kind: ParseErrorKind, span: Range<usize>, input: &str) -> Self {
+        ParseError {
+            kind,
+            span,
+            input: input.to_string(),
+        }
+    }
+
+    fn from_pest(err: pest::error::Error<Rule>, input: &str) -> Self {
+        let span = match err.location {
+            InputLocation::Pos(pos) => {
+                pos..input[pos..]
+                    .chars()
+                    .next()
+                    .map_or(pos, |c| pos + c.len_utf8())
+            }
+            InputLocation::Span((start, end)) => start..end,
+        };
+
+        let mut expected = Vec::new();
+
+        if let ErrorVariant::ParsingError { positives, .. } = &err.variant {
+            for rule in positives {
+                let items: &[Expected] = match rule {
+                    Rule::element
+                    | Rule::element_symbol
+                    | Rule::isotope
+                    | Rule::hydrogen_isotope
+                    | Rule::bracket_element
+                    | Rule::bracket_isotope => &[Expected::ElementSymbol],
+                    Rule::number
+                    | Rule::hydrate_count
+                    | Rule::weight_percent
+                    | Rule::weight_fraction
+                    | Rule::ppm
+                    | Rule::ppb
+                    | Rule::mg_per_g
+                    | Rule::atomic_percent
+                    | Rule::mol_percent
+                    | Rule::linear_expression
+                    | Rule::linear_term
+                    | Rule::stoichiometry
+                    | Rule::mass_number => &[Expected::Number],
+                    Rule::group => &[Expected::OpeningBracket],
+                    Rule::close_paren => &[Expected::ClosingBracket(')')],
+                    Rule::close_bracket => &[Expected::ClosingBracket(']')],
+                    Rule::close_brace => &[Expected::ClosingBracket('}')],
+                    Rule::charge | Rule::charge_number | Rule::charge_sign => &[Expected::Charge],
+                    Rule::EOI => &[Expected::EndOfInput],
+                    Rule::formula => &[
+                        Expected::ElementSymbol,
+                        Expected::OpeningBracket,
+                        Expected::EndOfInput,
+                    ],
+                    Rule::separator
+                    | Rule::expr
+                    | Rule::adduct
+                    | Rule::adduct_separator
+                    | Rule::linear_sign
+                    | Rule::variable => &[],
+                };
+
+                for item in items {
+                    if !expected.contains(item) {
+                        expected.push(*item);
+                    }
+                }
+            }
+        }
+        expected.sort();
+
+        ParseError::new(
+            ParseErrorKind::UnexpectedToken {
+                found: input[span.start..].chars().next(),
+                expected,
+            },
+            span,
+            input,
+        )
+    }
+
+    /// Byte offset of the offending token
+    pub fn offset(&self) -> usize {
+        self.span.start
+    }
+
+    /// Render the input with a caret line pointing at the offending token
+    ///
+    /// # Example
+    /// ```
+    /// use chemical_formula::prelude::*;
+    ///
+    /// let err = parse_formula("(SiO2").unwrap_err();
+    /// assert_eq!(err.caret(), "(SiO2\n     ^");
+    /// ```
+    pub fn caret(&self) -> String {
+        let padding = self.input[..self.span.start].chars().count();
+        let width = self.input[self.span.clone()].chars().count().max(1);
+
+        format!(
+            "{}\n{}{}",
+            self.input,
+            " ".repeat(padding),
+            "^".repeat(width)
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} at byte {}\n{}",
+            self.kind,
+            self.span.start,
+            self.caret()
+        )
+    }
+}
+
+impl From<ParseError> for FormulaError {
+    fn from(err: ParseError) -> Self {
+        FormulaError::ParseError(Box::new(err))
+    }
+}
+
+fn parse_number(pair: Pair<Rule>, input: &str) -> Result<f64, ParseError> {
+    let span = pair.as_span();
+
+    span.as_str().parse().map_err(|_| {
+        ParseError::new(
+            ParseErrorKind::InvalidNumber(span.as_str().to_string()),
+            span.start()..span.end(),
+            input,
+        )
+    })
+}
+
+fn parse_stoichiometry(pair: Pair<Rule>, input: &str) -> Result<Amount, ParseError> {
+    let stoichiometry = match pair.into_inner().next() {
+        Some(stoichiometry) => stoichiometry,
+        None => return Ok(Amount::Implicit),
+    };
+
+    // ppm, ppb, mg/g and weight fraction keep their unit and are converted to wt% when flattening
+    let amount: fn(f64) -> Amount = match stoichiometry.as_rule() {
+        Rule::number => {
+            check_decimal_stoichiometry(stoichiometry.as_span(), input)?;
+            return Ok(Amount::Stoichiometry(parse_number(stoichiometry, input)?));
+        }
+        Rule::hydrate_count => {
+            return Ok(Amount::Stoichiometry(parse_number(stoichiometry, input)?))
+        }
+        Rule::linear_expression => {
+            return Ok(Amount::Symbolic(parse_linear_expression(
+                stoichiometry,
+                input,
+            )?))
+        }
+        Rule::weight_percent => Amount::WeightPercent,
+        Rule::weight_fraction => |value| Amount::MassFraction {
+            value,
+            unit: MassFractionUnit::WeightFraction,
+        },
+        Rule::ppm => |value| Amount::MassFraction {
+            value,
+            unit: MassFractionUnit::Ppm,
+        },
+        Rule::ppb => |value| Amount::MassFraction {
+            value,
+            unit: MassFractionUnit::Ppb,
+        },
+        Rule::mg_per_g => |value| Amount::MassFraction {
+            value,
+            unit: MassFractionUnit::MgPerG,
+        },
+        Rule::atomic_percent => Amount::AtomicPercent,
+        Rule::mol_percent => Amount::MolPercent,
+        rule => return Err(unexpected_rule(rule, stoichiometry.as_span(), input)),
+    };
+
+    let span = stoichiometry.as_span();
+    let number = stoichiometry.into_inner().next().ok_or_else(|| {
+        ParseError::new(
+            ParseErrorKind::UnexpectedToken {
+                found: span.as_str().chars().next(),
+                expected: vec![Expected::Number],
+            },
+            span.start()..span.end(),
+            input,
+        )
+    })?;
+    Ok(amount(parse_number(number, input)?))
+}
+
+/// Parse the `linear_expression` rule, e.g. `1-x`, `2-δ` or `0.5+2y`
+fn parse_linear_expression(pair: Pair<Rule>, input: &str) -> Result<LinearExpression, ParseError> {
+    let mut expression = LinearExpression::default();
+    let mut sign = 1.;
+
+    for p in pair.into_inner() {
+        match p.as_rule() {
+            Rule::linear_sign => sign = if p.as_str() == "-" { -1. } else { 1. },
+            Rule::number => expression.constant += sign * parse_number(p, input)?,
+            Rule::linear_term => {
+                let mut coefficient = 1.;
+                let mut variable = "";
+
+                for q in p.into_inner() {
+                    match q.as_rule() {
+                        Rule::number => coefficient = parse_number(q, input)?,
+                        Rule::variable => variable = q.as_str(),
+                        rule => return Err(unexpected_rule(rule, q.as_span(), input)),
+                    }
+                }
+                expression.add_term(variable, sign * coefficient);
+            }
+            rule => return Err(unexpected_rule(rule, p.as_span(), input)),
+        }
+    }
+
+    Ok(expression)
+}
+
+/// Error for a pair that the grammar should never produce at this position
+fn unexpected_rule(rule: Rule, span: pest::Span, input: &str) -> ParseError {
+    let expected: &[Expected] = match rule {
+        Rule::number
+        | Rule::hydrate_count
+        | Rule::weight_percent
+        | Rule::weight_fraction
+        | Rule::ppm
+        | Rule::ppb
+        | Rule::mg_per_g
+        | Rule::atomic_percent
+        | Rule::mol_percent
+        | Rule::linear_expression
+        | Rule::linear_term
+        | Rule::linear_sign
+        | Rule::variable => &[Expected::Number],
+        _ => &[Expected::ElementSymbol, Expected::OpeningBracket],
+    };
+
+    ParseError::new(
+        ParseErrorKind::UnexpectedToken {
+            found: span.as_str().chars().next(),
+            expected: expected.to_vec(),
+        },
+        span.start()..span.end(),
+        input,
+    )
+}
+
+fn parse_element_symbol(pair: Pair<Rule>, input: &str) -> Result<ElementSymbol, ParseError> {
+    let span = pair.as_span();
+
+    span.as_str().parse().map_err(|err| {
+        ParseError::new(
+            ParseErrorKind::UnknownElement(err),
+            span.start()..span.end(),
+            input,
+        )
+    })
+}
+
+/// Parse the `isotope` or `bracket_isotope` rule into the element and the mass number
+///
+/// `D` and `T` are read as `2H` and `3H`. The isotope must be listed in `ISOTOPES`.
+fn parse_isotope(
+    pair: Pair<Rule>,
+    input: &str,
+) -> Result<(ElementSymbol, Option<u16>), ParseError> {
+    let span = pair.as_span();
+    let mut isotope = Isotope::new(ElementSymbol::H, 1);
+
+    for p in pair.into_inner() {
+        match p.as_rule() {
+            Rule::hydrogen_isotope if p.as_str() == "D" => isotope = Isotope::DEUTERIUM,
+            Rule::hydrogen_isotope => isotope = Isotope::TRITIUM,
+            Rule::mass_number => {
+                isotope.mass_number = p.as_str().parse().map_err(|_| {
+                    ParseError::new(
+                        ParseErrorKind::InvalidNumber(p.as_str().to_string()),
+                        p.as_span().start()..p.as_span().end(),
+                        input,
+                    )
+                })?;
+            }
+            Rule::element_symbol => isotope.element = parse_element_symbol(p, input)?,
+            rule => return Err(unexpected_rule(rule, p.as_span(), input)),
+        }
+    }
+
+    if isotope.data().is_none() {
+        return Err(ParseError::new(
+            ParseErrorKind::UnknownIsotope(isotope),
+            span.start()..span.end(),
+            input,
+        ));
+    }
+
+    Ok((isotope.element, Some(isotope.mass_number)))
+}
+
+fn parse_separator(s: &str) -> Separator {
+    match s {
+        "/" => Separator::Slash,
+        "@" => Separator::At,
+        "." => Separator::Dot,
+        "*" => Separator::Asterisk,
+        "·" | "•" | "∙" => Separator::MiddleDot,
+        _ => Separator::Whitespace,
+    }
+}
+
+/// A recursive function to build the formula tree
+fn parse_formula_pairs(pair: Pair<Rule>, input: &str) -> Result<Option<FormulaNode>, ParseError> {
+    let pair_span = pair.as_span();
+    let span = pair_span.start()..pair_span.end();
+
     match pair.as_rule() {
-        Rule::formula => pair
-            .into_inner()
-            .map(|x| parse_formula_pairs(x))
-            .fold(&mut ChemicalFormula::new(), |acc, x| acc.add_formula(&x))
-            .to_owned(),
         Rule::group => {
-            let mut formula = ChemicalFormula::new();
+            let mut bracket = Bracket::Round;
+            let mut children = FormulaTree::default();
+            let mut amount = Amount::Implicit;
+
             for p in pair.into_inner() {
                 match p.as_rule() {
-                    Rule::element => {
-                        formula.add_formula(&parse_formula_pairs(p));
-                    }
-                    Rule::stoichiometry => {
-                        let stoichiometry = p.into_inner().next();
-
-                        if stoichiometry.is_none() {
-                            return formula.to_owned();
-                        }
-
-                        let stoichiometry = stoichiometry.unwrap();
-
-                        match stoichiometry.as_rule() {
-                            Rule::number => {
-                                formula.multiply(stoichiometry.as_str().parse().unwrap());
-                            }
-                            Rule::weight_percent => {
-                                formula
-                                    .multiply_wt_percent(
-                                        stoichiometry
-                                            .into_inner()
-                                            .next()
-                                            .unwrap()
-                                            .as_str()
-                                            .parse()
-                                            .unwrap(),
-                                    )
-                                    .unwrap();
-                            }
-                            _ => unreachable!(),
-                        }
+                    Rule::element
+                    | Rule::bracket_element
+                    | Rule::group
+                    | Rule::separator
+                    | Rule::adduct => {
+                        children.nodes.extend(parse_formula_pairs(p, input)?);
                     }
-                    _ => unreachable!(),
+                    Rule::close_paren => bracket = Bracket::Round,
+                    Rule::close_bracket => bracket = Bracket::Square,
+                    Rule::close_brace => bracket = Bracket::Curly,
+                    Rule::stoichiometry => amount = parse_stoichiometry(p, input)?,
+                    rule => return Err(unexpected_rule(rule, p.as_span(), input)),
                 }
             }
 
-            formula.to_owned()
+            Ok(Some(FormulaNode::Group {
+                bracket,
+                children,
+                amount,
+                span,
+            }))
         }
-        Rule::element => {
+        Rule::element | Rule::bracket_element => {
             let mut rule = pair.into_inner();
-            let element = rule.next().unwrap();
-            let stoichiometry = rule.next().unwrap().into_inner().next();
+            let (element, stoichiometry) = match (rule.next(), rule.next()) {
+                (Some(element), Some(stoichiometry)) => (element, stoichiometry),
+                _ => return Err(unexpected_rule(Rule::element, pair_span, input)),
+            };
+            let (symbol, mass_number) = match element.as_rule() {
+                Rule::isotope | Rule::bracket_isotope => parse_isotope(element, input)?,
+                _ => (parse_element_symbol(element, input)?, None),
+            };
+            let amount = parse_stoichiometry(stoichiometry, input)?;
 
-            if stoichiometry.is_none() {
-                return ChemicalFormula::new()
-                    .add_element(ElementSymbol::from_str(element.as_str()), 1.)
-                    .to_owned();
+            if let (
+                Some(mass_number),
+                Amount::WeightPercent(_)
+                | Amount::MassFraction { .. }
+                | Amount::AtomicPercent(_)
+                | Amount::MolPercent(_),
+            ) = (mass_number, &amount)
+            {
+                return Err(ParseError::new(
+                    ParseErrorKind::IsotopePercent(Isotope::new(symbol, mass_number)),
+                    span,
+                    input,
+                ));
             }
 
-            let stoichiometry = stoichiometry.unwrap();
+            Ok(Some(FormulaNode::Element {
+                symbol,
+                mass_number,
+                amount,
+                span,
+            }))
+        }
+        Rule::separator => Ok(Some(FormulaNode::Separator {
+            separator: parse_separator(pair.as_str()),
+            span,
+        })),
+        Rule::adduct => {
+            let mut separator = Separator::Dot;
+            let mut coefficient = Amount::Implicit;
+            let mut children = FormulaTree::default();
 
-            match stoichiometry.as_rule() {
-                Rule::number => ChemicalFormula::new()
-                    .add_element(
-                        ElementSymbol::from_str(element.as_str()),
-                        stoichiometry.as_str().parse().unwrap_or(1.0),
-                    )
-                    .to_owned(),
-                Rule::weight_percent => ChemicalFormula::new()
-                    .add_wt_percent(
-                        ElementSymbol::from_str(element.as_str()),
-                        stoichiometry
-                            .into_inner()
-                            .next()
-                            .unwrap()
-                            .as_str()
-                            .parse()
-                            .unwrap(),
-                    )
-                    .to_owned(),
-                _ => {
-                    unreachable!()
+            for p in pair.into_inner() {
+                match p.as_rule() {
+                    Rule::adduct_separator => {
+                        separator = parse_separator(p.as_str());
+                        check_adduct_dot(p.as_span(), input)?;
+                    }
+                    Rule::number => coefficient = Amount::Stoichiometry(parse_number(p, input)?),
+                    Rule::linear_expression => {
+                        coefficient = Amount::Symbolic(parse_linear_expression(p, input)?)
+                    }
+                    Rule::element | Rule::group => {
+                        children.nodes.extend(parse_formula_pairs(p, input)?);
+                    }
+                    rule => return Err(unexpected_rule(rule, p.as_span(), input)),
                 }
             }
+
+            Ok(Some(FormulaNode::Adduct {
+                separator,
+                coefficient,
+                children,
+                span,
+            }))
         }
-        Rule::EOI => ChemicalFormula::new(),
-        Rule::number
-        | Rule::separator
-        | Rule::expr
-        | Rule::weight_percent
-        | Rule::stoichiometry
-        | Rule::element_symbol => {
-            unreachable!();
+        Rule::EOI => Ok(None),
+        rule => Err(unexpected_rule(rule, pair.as_span(), input)),
+    }
+}
+
+/// Check that a `.` adduct separator cannot be read as a decimal point
+///
+/// A `.` after a digit cannot start a number, e.g. in `CaSO4.0.5H2O`, and neither can a `.` without a digit after it,
+/// e.g. in `CuSO4.H2O`. A `.` between an element or a bracket and a digit, e.g. in `Fe.5O` or `H2O.2NH3`,
+/// is either a number without its leading `0` or an adduct dot, and is rejected as `ParseErrorKind::AmbiguousDot`.
+fn check_adduct_dot(span: pest::Span, input: &str) -> Result<(), ParseError> {
+    if span.as_str() != "." {
+        return Ok(());
+    }
+
+    let before = input[..span.start()].chars().next_back();
+    let digits = input[span.end()..]
+        .bytes()
+        .take_while(|b| b.is_ascii_digit())
+        .count();
+
+    match before {
+        Some(c) if digits > 0 && !c.is_ascii_digit() && !c.is_whitespace() => Err(ParseError::new(
+            ParseErrorKind::AmbiguousDot,
+            span.start()..span.end() + digits,
+            input,
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Check that a decimal stoichiometry cannot be read as an integer and an adduct
+///
+/// A decimal of at least 2 followed by an element or a bracket, e.g. `Fe2.5O4` or `Na2.4Fe(SO4)2`,
+/// can also be read as an adduct, e.g. `Fe2·5O4`, and is rejected as `ParseErrorKind::AmbiguousDot`.
+/// A decimal below 2, e.g. `La0.7Sr0.3MnO3` or `Sr2Fe1.5MoO6`, and a decimal before a closing bracket,
+/// e.g. `(Fe2.5)O4`, are kept. Water after an integer is read as a hydrate by the grammar, e.g. `CuSO4.5H2O`.
+fn check_decimal_stoichiometry(span: pest::Span, input: &str) -> Result<(), ParseError> {
+    let integer = match span.as_str().split_once('.') {
+        Some((integer, _)) => integer,
+        None => return Ok(()),
+    };
+
+    let followed = input[span.end()..]
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_uppercase() || "([{^".contains(c));
+
+    if followed && integer.parse::<u64>().map_or(true, |integer| integer >= 2) {
+        let dot = span.start() + integer.len();
+        return Err(ParseError::new(
+            ParseErrorKind::AmbiguousDot,
+            dot..span.end(),
+            input,
+        ));
+    }
+
+    Ok(())
+}
+
+/// Parse the `charge` rule
+///
+/// Returns the charge and whether it was written as a single sign without a number or `^`.
+fn parse_charge(pair: Pair<Rule>, input: &str) -> Result<(i32, bool), ParseError> {
+    let caret = pair.as_str().starts_with('^');
+    let mut sign = 1;
+    let mut sign_count = 0;
+    let mut number = None;
+
+    for p in pair.into_inner() {
+        match p.as_rule() {
+            Rule::charge_sign => {
+                sign = if p.as_str().starts_with('-') { -1 } else { 1 };
+                sign_count = p.as_str().len() as i32;
+            }
+            Rule::charge_number => {
+                let span = p.as_span();
+                number = Some(span.as_str().parse::<i32>().map_err(|_| {
+                    ParseError::new(
+                        ParseErrorKind::InvalidNumber(span.as_str().to_string()),
+                        span.start()..span.end(),
+                        input,
+                    )
+                })?);
+            }
+            rule => return Err(unexpected_rule(rule, p.as_span(), input)),
+        }
+    }
+
+    match number {
+        Some(number) => Ok((sign * number, false)),
+        None => Ok((sign * sign_count, sign_count == 1 && !caret)),
+    }
+}
+
+/// Read the stoichiometry written before a single charge sign as the charge number
+///
+/// `Fe3+` and `[Fe(CN)6]4-` write the magnitude of the charge where a stoichiometry would be.
+/// This applies to a formula ending with a square-bracket complex, and to a monatomic ion
+/// whose charge is one of the common oxidation states of `ElementSymbol::oxidation_states()`, e.g. `Fe3+` or `O2-`.
+/// A monatomic ion with another number, e.g. `Cl2-` or `Ag2+`, is rejected as `ParseErrorKind::AmbiguousCharge`.
+/// Otherwise the sign is a single charge, e.g. `NH4+`.
+fn charge_from_stoichiometry(
+    tree: &mut FormulaTree,
+    sign: i32,
+    input: &str,
+) -> Result<Option<i32>, ParseError> {
+    let monatomic = tree
+        .nodes
+        .iter()
+        .filter(|node| !matches!(node, FormulaNode::Separator { .. }))
+        .count()
+        == 1;
+
+    let (amount, symbol, span) = match tree.nodes.last_mut() {
+        Some(FormulaNode::Element {
+            amount,
+            symbol,
+            span,
+            ..
+        }) if monatomic => (amount, Some(*symbol), span.clone()),
+        Some(FormulaNode::Group {
+            bracket: Bracket::Square,
+            amount,
+            span,
+            ..
+        }) => (amount, None, span.clone()),
+        _ => return Ok(None),
+    };
+
+    let n = match *amount {
+        Amount::Stoichiometry(n) if n >= 1. && n.fract() == 0. && n <= i32::MAX as f64 => n as i32,
+        _ => return Ok(None),
+    };
+
+    // `Cl2-` can be Cl2 with charge -1 or Cl with charge -2
+    if let Some(symbol) = symbol {
+        if n > 1 && !symbol.oxidation_states().contains(&(sign * n)) {
+            return Err(ParseError::new(
+                ParseErrorKind::AmbiguousCharge,
+                span,
+                input,
+            ));
         }
     }
+
+    *amount = Amount::Implicit;
+    Ok(Some(n))
+}
+
+/// Error for a mass number written without `^` or brackets at the position of a parse error, e.g. `13CO2`
+///
+/// Only a known isotope is reported, so that a misplaced number is still reported as an unexpected token.
+fn mass_number_prefix(span: &Range<usize>, input: &str) -> Option<ParseError> {
+    let rest = &input[span.start..];
+    let digits = rest.bytes().take_while(|b| b.is_ascii_digit()).count();
+    let mass_number: u16 = rest[..digits].parse().ok()?;
+
+    // Two-letter symbols first, e.g. `13Cl` is not `13C` followed by `l`
+    let (element, len) = [2, 1].into_iter().find_map(|len| {
+        let symbol = rest[digits..].get(..len)?;
+        symbol
+            .parse::<ElementSymbol>()
+            .ok()
+            .map(|element| (element, len))
+    })?;
+
+    let isotope = Isotope::new(element, mass_number);
+    isotope.data()?;
+
+    Some(ParseError::new(
+        ParseErrorKind::MassNumberPrefix(isotope),
+        span.start..span.start + digits + len,
+        input,
+    ))
+}
+
+/// Parse a chemical formula into a `FormulaTree`
+///
+/// The tree keeps the groups, separators and the stoichiometry or wt% of each node.
+/// Please refer to the `tree` module for more details.
+///
+/// # Example
+///
+/// ```
+/// use chemical_formula::prelude::*;
+/// use chemical_formula::tree::{Amount, FormulaNode};
+///
+/// let tree = parse_formula_tree("Pt5wt%/SiO2").unwrap();
+///
+/// assert_eq!(tree.components().len(), 2);
+/// assert_eq!(tree.nodes[0].amount(), Some(Amount::WeightPercent(5.0)));
+/// ```
+pub fn parse_formula_tree(s: &str) -> Result<FormulaTree, ParseError> {
+    let pairs = ChemicalFormulaParser::parse(Rule::formula, s).map_err(|err| {
+        let err = ParseError::from_pest(err, s);
+        mass_number_prefix(&err.span, s).unwrap_or(err)
+    })?;
+
+    let mut tree = FormulaTree::default();
+    let mut charge = None;
+    for pair in pairs.flat_map(|pair| pair.into_inner()) {
+        if pair.as_rule() == Rule::charge {
+            charge = Some(parse_charge(pair, s)?);
+            continue;
+        }
+
+        tree.nodes.extend(parse_formula_pairs(pair, s)?);
+    }
+
+    if let Some((charge, single_sign)) = charge {
+        tree.charge = if single_sign {
+            charge * charge_from_stoichiometry(&mut tree, charge, s)?.unwrap_or(1)
+        } else {
+            charge
+        };
+    }
+
+    Ok(tree)
 }
 
 /// Parse a chemical formula from a string
 ///
+/// Malformed input is reported as a `ParseError` carrying the byte span of the offending token.
+///
+/// A net charge can be written at the end of the formula as `NH4+`, `SO4^2-`, `Fe3+`, `Fe+3` or `[Fe(CN)6]4-`.
+/// A single sign after a square-bracket complex takes the preceding number as the charge.
+/// So does a single sign after a monatomic ion, if the number is a common oxidation state of the element,
+/// so `O2-` is the oxide ion. Other numbers, e.g. `Cl2-` or `Ag2+`, are rejected as `ParseErrorKind::AmbiguousCharge`.
+/// Use `^` to be explicit, e.g. `O2^-` for the superoxide ion, `Ag^2+` or `S2^2-` for the disulfide ion.
+/// Whitespace after the charge is ignored.
+///
+/// Hydrates and adducts are written with `·`, `•`, `∙`, `*` or `.` and an optional leading coefficient,
+/// e.g. `CuSO4·5H2O` or `Ni(NO3)2*6H2O`. The coefficient multiplies the adduct part up to the next separator.
+/// A `.` is an adduct dot after a complete number, e.g. `CaSO4.0.5H2O`, before an element, e.g. `CuSO4.H2O`,
+/// and between an integer and water, e.g. `CuSO4.5H2O` or `Ni(NO3)2.6H2O`.
+/// Otherwise a `.` between digits is a decimal point, e.g. `Sr2Fe1.5MoO6` or `La0.7Sr0.3MnO3`.
+/// A `.` that can be read both ways is rejected as `ParseErrorKind::AmbiguousDot`: a `.` between an element
+/// or a bracket and a number, e.g. `Fe.5O`, and a decimal of at least 2 followed by an element, e.g. `Fe2.5O4`.
+/// Write the adduct with `·` or `*`, or the decimal in brackets, e.g. `(Fe2.5)O4`.
+///
+/// Isotopes are written with a mass-number prefix, e.g. `^13CO2`, `C[18O]2` or `^15NH3`, and deuterium and tritium as `D` and `T`.
+/// The prefix needs `^` unless it directly follows `[`, since a leading number such as `2H2O` is a coefficient,
+/// see `parse_reaction` for species with coefficients. A plain prefix of a known isotope, e.g. `13CO2`,
+/// is rejected as `ParseErrorKind::MassNumberPrefix`, which suggests `^13C` and `[13C]`.
+/// The isotope must be listed in `ISOTOPES`, and wt%, at% or mol% of an isotope is not supported.
+///
+/// Besides wt%, a mass fraction can be written in `ppm`, `ppb`, `mg/g` or as a weight fraction with `wt`,
+/// e.g. `Pd500ppm/TiO2` or `Na0.001wt/Al2O3`. These are converted to wt%, so `Pd500ppm/TiO2` is the same as `Pd0.05wt%/TiO2`.
+/// `parse_formula_tree` keeps the unit as written in `Amount::MassFraction`.
+///
 /// # Example
 ///
 /// ```
@@ -140,19 +863,57 @@ fn parse_formula_pairs(pair: Pair<Rule>) -> ChemicalFormula {
 ///
 /// assert_eq!(formula.stoichiometry[&ElementSymbol::O], 2.0);
 /// assert_eq!(formula.stoichiometry[&ElementSymbol::Si], 1.0);
+///
+/// let err = parse_formula("Pt5.wt%").unwrap_err();
+/// assert_eq!(err.offset(), 4);
 /// ```
-pub fn parse_formula(s: &str) -> Result<ChemicalFormula, Box<dyn Error>> {
-    let mut pairs = ChemicalFormulaParser::parse(Rule::formula, s)?;
+pub fn parse_formula(s: &str) -> Result<ChemicalFormula, ParseError> {
+    parse_formula_tree(s)?
+        .flatten_with_span()
+        // wt%, at% and mol% of a group and variables are the only steps of flattening that can fail
+        .map_err(|(err, span)| {
+            let kind = match err {
+                FormulaError::AtomicPercentOverflow => ParseErrorKind::AtomicPercentOverflow,
+                FormulaError::MolPercentOverflow => ParseErrorKind::MolPercentOverflow,
+                FormulaError::UnresolvedVariable(variable) => {
+                    ParseErrorKind::UnresolvedVariable(variable)
+                }
+                _ => ParseErrorKind::WeightPercentOverflow,
+            };
+            ParseError::new(kind, span, s)
+        })
+}
 
-    Ok(parse_formula_pairs(pairs.next().unwrap()))
+/// Parse a formula whose stoichiometry has variables, e.g. `Li1-xCoO2`, `La1-xSrxMnO3` or `CeO2-δ`
+///
+/// A stoichiometry or the coefficient of an adduct can be a linear expression in `x`, `y`, `z` and `δ`,
+/// such as `1-x`, `x`, `2-δ` or `0.5+2y`. The formula is evaluated for given values with `SymbolicFormula::evaluate`.
+/// `parse_formula` rejects such a formula with `ParseErrorKind::UnresolvedVariable`.
+///
+/// # Example
+///
+/// ```
+/// use chemical_formula::prelude::*;
+/// use std::collections::HashMap;
+///
+/// let formula = parse_symbolic_formula("Ba(Zr1-yYy)O3-δ").unwrap();
+/// assert_eq!(formula.variables().into_iter().collect::<Vec<_>>(), vec!["y", "δ"]);
+///
+/// let evaluated = formula.evaluate(&HashMap::from([("y", 0.2), ("δ", 0.1)])).unwrap();
+/// assert_eq!(evaluated.stoichiometry[&ElementSymbol::Y], 0.2);
+/// assert_eq!(evaluated.stoichiometry[&ElementSymbol::O], 2.9);
+/// ```
+pub fn parse_symbolic_formula(s: &str) -> Result<SymbolicFormula, ParseError> {
+    Ok(SymbolicFormula::new(parse_formula_tree(s)?))
 }
 
 #[cfg(test)]
+#[allow(non_snake_case)]
 mod tests {
     use crate::element::ElementSymbol;
 
     use super::*;
-    use approx::{assert_abs_diff_eq, assert_abs_diff_ne};
+    use approx::assert_abs_diff_eq;
     const TOL: f64 = 1e-10;
 
     #[test]
@@ -222,4 +983,332 @@ mod tests {
             epsilon = TOL
         );
     }
+
+    #[test]
+    fn test_parse_error_span() {
+        let err = parse_formula("SiO2)").unwrap_err();
+        assert_eq!(err.span, 4..5);
+
+        let err = parse_formula("(SiO2").unwrap_err();
+        assert_eq!(err.offset(), 5);
+        match err.kind {
+            ParseErrorKind::UnexpectedToken { found, expected } => {
+                assert_eq!(found, None);
+                assert!(expected.contains(&Expected::ClosingBracket(')')));
+            }
+            _ => panic!("unexpected error kind"),
+        }
+
+        let err = parse_formula("Ptwt%").unwrap_err();
+        match err.kind {
+            ParseErrorKind::UnexpectedToken { expected, .. } => {
+                assert!(expected.contains(&Expected::Number));
+            }
+            _ => panic!("unexpected error kind"),
+        }
+
+        let err = parse_formula("(Pt150wt%)50wt%").unwrap_err();
+        assert_eq!(err.kind, ParseErrorKind::WeightPercentOverflow);
+        assert_eq!(err.span, 0..15);
+    }
+
+    #[test]
+    fn test_parse_malformed_formula_does_not_panic() {
+        for formula_str in [
+            "Pt5.wt%", "SiO2)", "(SiO2)+-", "Pt+-", "Xx", "[Fe(CN", "wt%",
+        ] {
+            assert!(parse_formula(formula_str).is_err(), "{}", formula_str);
+        }
+
+        let formula = parse_formula("((SiO2)2)").unwrap();
+        assert_abs_diff_eq!(formula.stoichiometry[&ElementSymbol::Si], 2.0);
+
+        let formula = parse_formula("(SiO2)").unwrap();
+        assert_abs_diff_eq!(formula.stoichiometry[&ElementSymbol::O], 2.0);
+    }
+
+    #[test]
+    fn test_chmical_formula_parser_charge() {
+        for (formula_str, expected_charge, expected_stoichiometry) in [
+            ("SO4^2-", -2, (ElementSymbol::O, 4.0)),
+            ("Fe3+", 3, (ElementSymbol::Fe, 1.0)),
+            ("Fe+3", 3, (ElementSymbol::Fe, 1.0)),
+            ("NH4+", 1, (ElementSymbol::H, 4.0)),
+            ("[Fe(CN)6]4-", -4, (ElementSymbol::C, 6.0)),
+            ("O2^-", -1, (ElementSymbol::O, 2.0)),
+            ("PO4---", -3, (ElementSymbol::O, 4.0)),
+            ("Cl-", -1, (ElementSymbol::Cl, 1.0)),
+            ("O2-", -2, (ElementSymbol::O, 1.0)),
+            ("Mn7+", 7, (ElementSymbol::Mn, 1.0)),
+            ("Cl2^-", -1, (ElementSymbol::Cl, 2.0)),
+            ("Ag^2+", 2, (ElementSymbol::Ag, 1.0)),
+            ("Na1+", 1, (ElementSymbol::Na, 1.0)),
+            ("Hg2^2+", 2, (ElementSymbol::Hg, 2.0)),
+            ("Li+ ", 1, (ElementSymbol::Li, 1.0)),
+            ("SO4^2-\n", -2, (ElementSymbol::O, 4.0)),
+            ("SiO2 ", 0, (ElementSymbol::O, 2.0)),
+        ] {
+            let formula = parse_formula(formula_str).unwrap();
+            let (element, stoichiometry) = expected_stoichiometry;
+
+            assert_eq!(formula.charge, expected_charge, "{}", formula_str);
+            assert_abs_diff_eq!(formula.stoichiometry[&element], stoichiometry);
+        }
+
+        let formula = parse_formula("H2O").unwrap();
+        assert!(formula.is_neutral());
+
+        // Not a common oxidation state, so the number can be the stoichiometry or the charge
+        for formula_str in ["Cl2-", "Na2+", "Ag2+", "Cu3+"] {
+            let err = parse_formula(formula_str).unwrap_err();
+            assert_eq!(err.kind, ParseErrorKind::AmbiguousCharge, "{}", formula_str);
+            assert_eq!(err.span, 0..3);
+        }
+    }
+
+    #[test]
+    fn test_chmical_formula_parser_hydrate() {
+        for (formula_str, expected_molecular_weight) in [
+            ("CuSO4·5H2O", 249.677),
+            ("CuSO4*5H2O", 249.677),
+            ("CuSO4•5H2O", 249.677),
+            ("H2PtCl6·6H2O", 517.890),
+            ("Ni(NO3)2·6H2O", 290.791),
+            ("CuSO4.5H2O", 249.677),
+            ("Ni(NO3)2.6H2O", 290.791),
+            ("CuSO4 . 5H2O", 249.677),
+            ("(CuSO4·5H2O)2", 499.354),
+        ] {
+            let formula = parse_formula(formula_str).unwrap();
+            assert_abs_diff_eq!(
+                formula.molecular_weight().unwrap(),
+                expected_molecular_weight,
+                epsilon = 1e-3
+            );
+        }
+
+        let formula = parse_formula("CuSO4•5H2O*2NH3").unwrap();
+        assert_abs_diff_eq!(formula.stoichiometry[&ElementSymbol::H], 16.0);
+        assert_abs_diff_eq!(formula.stoichiometry[&ElementSymbol::N], 2.0);
+
+        let formula = parse_formula("CuSO4.H2O").unwrap();
+        assert_abs_diff_eq!(formula.stoichiometry[&ElementSymbol::O], 5.0);
+
+        // Hemihydrate with a decimal coefficient
+        let formula = parse_formula("CaSO4.0.5H2O").unwrap();
+        assert_abs_diff_eq!(formula.stoichiometry[&ElementSymbol::H], 1.0);
+        assert_abs_diff_eq!(formula.stoichiometry[&ElementSymbol::O], 4.5);
+
+        // Decimal stoichiometry is not read as an adduct
+        for (formula_str, element, expected_stoichiometry) in [
+            ("Sr2Fe1.5MoO6", ElementSymbol::Fe, 1.5),
+            ("Sr2Fe1.5MoO6", ElementSymbol::Mo, 1.0),
+            ("Sr2Fe1.5MoO6", ElementSymbol::O, 6.0),
+            ("La0.7Sr0.3MnO3", ElementSymbol::La, 0.7),
+            ("La0.7Sr0.3MnO3", ElementSymbol::Sr, 0.3),
+            ("La0.7Sr0.3MnO3", ElementSymbol::O, 3.0),
+            ("Fe1.5O", ElementSymbol::Fe, 1.5),
+            ("(Fe2.5)O4", ElementSymbol::Fe, 2.5),
+            ("Pt2.5wt%/SiO2", ElementSymbol::Si, 1.0),
+        ] {
+            let formula = parse_formula(formula_str).unwrap();
+            assert_abs_diff_eq!(formula.stoichiometry[&element], expected_stoichiometry);
+        }
+
+        // Hydrate of an integer count
+        let formula = parse_formula("CuSO4.5H2O").unwrap();
+        assert_abs_diff_eq!(formula.stoichiometry[&ElementSymbol::H], 10.0);
+        assert_abs_diff_eq!(formula.stoichiometry[&ElementSymbol::O], 9.0);
+        let formula = parse_formula("Ni(NO3)2.6H2O").unwrap();
+        assert_abs_diff_eq!(formula.stoichiometry[&ElementSymbol::N], 2.0);
+        assert_abs_diff_eq!(formula.stoichiometry[&ElementSymbol::O], 12.0);
+
+        for formula_str in [
+            "Fe.5O",
+            "H2O.2NH3",
+            "(SiO2).5H2O",
+            "Fe2.5O4",
+            "CuSO4.5NH3",
+            "Na2.4Fe(SO4)2",
+        ] {
+            let err = parse_formula(formula_str).unwrap_err();
+            assert_eq!(err.kind, ParseErrorKind::AmbiguousDot, "{}", formula_str);
+        }
+        assert_eq!(parse_formula("Fe.5O").unwrap_err().span, 2..4);
+        assert_eq!(parse_formula("Fe2.5O4").unwrap_err().span, 3..5);
+    }
+
+    #[test]
+    fn test_chmical_formula_parser_isotope() {
+        let c13 = Isotope::new(ElementSymbol::C, 13);
+        let o18 = Isotope::new(ElementSymbol::O, 18);
+        let n15 = Isotope::new(ElementSymbol::N, 15);
+
+        for (formula_str, expected_isotope, expected_stoichiometry) in [
+            ("D2O", Isotope::DEUTERIUM, 2.0),
+            ("[2H]2O", Isotope::DEUTERIUM, 2.0),
+            ("T2", Isotope::TRITIUM, 2.0),
+            ("CH3OD", Isotope::DEUTERIUM, 1.0),
+            ("^13CO2", c13, 1.0),
+            ("[13C]O2", c13, 1.0),
+            ("C[18O]2", o18, 2.0),
+            ("^15NH3", n15, 1.0),
+        ] {
+            let formula = parse_formula(formula_str).unwrap();
+            assert_eq!(formula.isotope.len(), 1, "{}", formula_str);
+            assert_abs_diff_eq!(formula.isotope[&expected_isotope], expected_stoichiometry);
+        }
+
+        // Element symbols starting with D or T are not isotopes
+        for formula_str in ["Db", "Ds2", "Tc", "Ti"] {
+            assert!(parse_formula(formula_str).unwrap().isotope.is_empty());
+        }
+
+        let formula = parse_formula("CH3OD").unwrap();
+        assert_abs_diff_eq!(formula.stoichiometry[&ElementSymbol::H], 4.0);
+        assert_abs_diff_eq!(
+            formula.molecular_weight().unwrap(),
+            parse_formula("CH4O").unwrap().molecular_weight().unwrap() + 1.0061,
+            epsilon = 1e-4
+        );
+
+        let err = parse_formula("C[5H]3").unwrap_err();
+        assert!(matches!(err.kind, ParseErrorKind::UnknownIsotope(_)));
+        assert_eq!(err.span, 2..4);
+
+        // A leading number is not a mass number, and the error suggests `^` or brackets
+        for (formula_str, isotope, span) in [
+            ("2H2O", Isotope::DEUTERIUM, 0..2),
+            ("13CO2", c13, 0..3),
+            ("Pt/13CO2", c13, 3..6),
+        ] {
+            let err = parse_formula(formula_str).unwrap_err();
+            assert_eq!(err.kind, ParseErrorKind::MassNumberPrefix(isotope));
+            assert_eq!(err.span, span);
+        }
+        assert!(parse_formula("13CO2")
+            .unwrap_err()
+            .to_string()
+            .starts_with("a number before `C` is not a mass number, write `^13C` or `[13C]`"));
+        assert!(matches!(
+            parse_formula("5CO2").unwrap_err().kind,
+            ParseErrorKind::UnexpectedToken { .. }
+        ));
+
+        let err = parse_formula("^13C5wt%/SiO2").unwrap_err();
+        assert_eq!(err.kind, ParseErrorKind::IsotopePercent(c13));
+    }
+
+    #[test]
+    fn test_chmical_formula_parser_at_mol_percent() {
+        // 2 Pt atoms per 98 atoms of Al2O3, which has 5 atoms per formula unit
+        let formula = parse_formula("Pt2at%/Al2O3")
+            .unwrap()
+            .to_molecular_formula()
+            .unwrap();
+        assert_abs_diff_eq!(
+            formula.stoichiometry[&ElementSymbol::Pt],
+            2. * 5. / 98.,
+            epsilon = TOL
+        );
+        assert_abs_diff_eq!(formula.stoichiometry[&ElementSymbol::Al], 2.0);
+        assert_abs_diff_eq!(
+            formula.to_at_percent().unwrap().at_percent[&ElementSymbol::Pt],
+            2.0,
+            epsilon = TOL
+        );
+
+        let formula = parse_formula("(CeO2)20mol%(ZrO2)80mol%").unwrap();
+        assert_abs_diff_eq!(
+            formula.stoichiometry[&ElementSymbol::Ce],
+            0.2,
+            epsilon = TOL
+        );
+        assert_abs_diff_eq!(
+            formula.stoichiometry[&ElementSymbol::Zr],
+            0.8,
+            epsilon = TOL
+        );
+        assert_abs_diff_eq!(formula.stoichiometry[&ElementSymbol::O], 2.0, epsilon = TOL);
+
+        // 8 mol% Y2O3 per 92 mol% ZrO2
+        let formula = parse_formula("(Y2O3)8mol%/ZrO2").unwrap();
+        assert_abs_diff_eq!(
+            formula.stoichiometry[&ElementSymbol::Y],
+            16. / 92.,
+            epsilon = TOL
+        );
+        assert_abs_diff_eq!(
+            formula.stoichiometry[&ElementSymbol::Zr],
+            1.0,
+            epsilon = TOL
+        );
+
+        // wt% and at% on the same support
+        let formula = parse_formula("Pt1wt%Sn1at%/Al2O3").unwrap();
+        let wt_percent = formula.to_wt_percent().unwrap().wt_percent;
+        assert_abs_diff_eq!(wt_percent[&ElementSymbol::Pt], 1.0, epsilon = TOL);
+        let at_percent = formula.to_at_percent().unwrap().at_percent;
+        assert_abs_diff_eq!(at_percent[&ElementSymbol::Sn], 1.0, epsilon = TOL);
+
+        let err = parse_formula("(Pt60at%Au50at%)50at%").unwrap_err();
+        assert_eq!(err.kind, ParseErrorKind::AtomicPercentOverflow);
+
+        let err = parse_formula("(CeO2)60mol%(ZrO2)50mol%").unwrap_err();
+        assert_eq!(err.kind, ParseErrorKind::MolPercentOverflow);
+
+        let err = parse_formula("(Y2O3)100mol%/ZrO2").unwrap_err();
+        assert_eq!(err.kind, ParseErrorKind::MolPercentOverflow);
+    }
+
+    #[test]
+    fn test_parse_unresolved_variable() {
+        let err = parse_formula("Li1-xCoO2").unwrap_err();
+        assert_eq!(
+            err.kind,
+            ParseErrorKind::UnresolvedVariable("x".to_string())
+        );
+        assert_eq!(err.span, 0..5);
+
+        let tree = parse_formula_tree("Li1-xCoO2").unwrap();
+        assert_eq!(tree.to_string(), "Li1-xCoO2");
+    }
+
+    #[test]
+    fn test_chmical_formula_parser_mass_fraction_units() {
+        let expected = parse_formula("Pd0.05wt%/TiO2").unwrap();
+
+        for formula_str in [
+            "Pd500ppm/TiO2",
+            "Pd500000ppb/TiO2",
+            "Pd0.5mg/g/TiO2",
+            "Pd0.0005wt/TiO2",
+        ] {
+            let formula = parse_formula(formula_str).unwrap();
+            assert_abs_diff_eq!(
+                formula.wt_percent[&ElementSymbol::Pd],
+                expected.wt_percent[&ElementSymbol::Pd],
+                epsilon = TOL
+            );
+            assert_eq!(formula.stoichiometry, expected.stoichiometry);
+
+            // The tree keeps the unit as written
+            let tree = parse_formula_tree(formula_str).unwrap();
+            assert_eq!(tree.to_string(), formula_str);
+            assert!(matches!(
+                tree.nodes[0].amount(),
+                Some(Amount::MassFraction { .. })
+            ));
+        }
+
+        let formula = parse_formula("(PdCl2)1000ppm/TiO2").unwrap();
+        let ppm = formula.to_ppm().unwrap();
+        assert_abs_diff_eq!(
+            ppm[&ElementSymbol::Pd],
+            1000.0 * ElementSymbol::Pd.atomic_weight()
+                / parse_formula("PdCl2").unwrap().molecular_weight().unwrap(),
+            epsilon = 1e-6
+        );
+        assert_abs_diff_eq!(ppm.values().sum::<f64>(), 1e6, epsilon = 1e-6);
+    }
 }
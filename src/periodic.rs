@@ -0,0 +1,2552 @@
+//! # Periodic table
+//!
+//! This module contains the properties of each element in `ELEMENTS`,
+//! e.g. the name, group, period, block, category, electronegativity, radii, oxidation states, density and melting point.
+//!
+//! `ElementSymbol::data()` gives the `ElementData` of an element, and the properties are also available as methods
+//! on `ElementSymbol`, e.g. `ElementSymbol::Fe.name()`.
+//!
+//! `ElementSymbol::iter()` iterates over all elements in order of atomic number, and `range`, `in_period`, `in_group`,
+//! `in_block` and `in_category` select the elements of a range or a part of the periodic table, e.g. the lanthanides.
+//!
+//! # Example
+//! ```
+//! use chemical_formula::prelude::*;
+//!
+//! let fe = ElementSymbol::Fe;
+//!
+//! assert_eq!(fe.name(), Some("Iron"));
+//! assert_eq!(fe.group(), Some(8));
+//! assert_eq!(fe.period(), Some(4));
+//! assert_eq!(fe.block(), Some(Block::D));
+//! assert_eq!(fe.category(), Some(ElementCategory::TransitionMetal));
+//! assert_eq!(fe.electronegativity(), Some(1.83));
+//! assert_eq!(fe.oxidation_states(), &[3, 2]);
+//! ```
+
+use crate::element::ElementSymbol;
+use std::fmt;
+use std::ops::RangeBounds;
+
+/// Block of the periodic table
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Block {
+    S,
+    P,
+    D,
+    F,
+}
+
+impl fmt::Display for Block {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Block::S => write!(f, "s"),
+            Block::P => write!(f, "p"),
+            Block::D => write!(f, "d"),
+            Block::F => write!(f, "f"),
+        }
+    }
+}
+
+/// Category of an element
+///
+/// The elements from Mt onwards, whose chemical properties are not known, are `Unknown`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ElementCategory {
+    AlkaliMetal,
+    AlkalineEarthMetal,
+    TransitionMetal,
+    PostTransitionMetal,
+    Metalloid,
+    Nonmetal,
+    Halogen,
+    NobleGas,
+    Lanthanide,
+    Actinide,
+    Unknown,
+}
+
+/// Shannon effective ionic radius of an ion
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IonicRadius {
+    pub charge: i32,
+    /// Radius in pm
+    pub radius: f64,
+}
+
+/// Properties of an element
+///
+/// A property that is not known or not defined, e.g. the electronegativity of He or the melting point of C,
+/// which sublimes at atmospheric pressure, is `None`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ElementData {
+    pub symbol: ElementSymbol,
+    pub name: &'static str,
+    pub atomic_number: u8,
+    /// IUPAC group (1 - 18), `None` for the f-block elements from Ce to Lu and from Th to Lr
+    pub group: Option<u8>,
+    pub period: u8,
+    pub block: Block,
+    pub category: ElementCategory,
+    /// Pauling electronegativity
+    pub electronegativity: Option<f64>,
+    /// Single-bond covalent radius in pm
+    pub covalent_radius: Option<f64>,
+    /// Ionic radius of the most common ion in 6-fold coordination
+    pub ionic_radius: Option<IonicRadius>,
+    /// Metallic radius for 12-fold coordination in pm
+    pub metallic_radius: Option<f64>,
+    /// Common oxidation states, the most common first
+    pub oxidation_states: &'static [i32],
+    /// Density near room temperature in g/cm³, for gases at 0 °C and 101.325 kPa
+    pub density: Option<f64>,
+    /// Melting point at 101.325 kPa in K
+    pub melting_point: Option<f64>,
+}
+
+impl ElementSymbol {
+    /// Properties of the element, `None` for `ElementSymbol::None`
+    ///
+    /// # Example
+    /// ```
+    /// use chemical_formula::prelude::*;
+    ///
+    /// let data = ElementSymbol::Pt.data().unwrap();
+    /// assert_eq!(data.atomic_number, 78);
+    /// assert_eq!(data.metallic_radius, Some(138.5));
+    ///
+    /// assert!(ElementSymbol::None.data().is_none());
+    /// ```
+    pub fn data(&self) -> Option<&'static ElementData> {
+        match *self {
+            ElementSymbol::None => None,
+            element => ELEMENTS.get(element as usize - 1),
+        }
+    }
+
+    /// Name of the element in English, e.g. `Iron`
+    pub fn name(&self) -> Option<&'static str> {
+        self.data().map(|data| data.name)
+    }
+
+    /// IUPAC group (1 - 18), `None` for the f-block elements from Ce to Lu and from Th to Lr
+    pub fn group(&self) -> Option<u8> {
+        self.data().and_then(|data| data.group)
+    }
+
+    /// Period (1 - 7)
+    pub fn period(&self) -> Option<u8> {
+        self.data().map(|data| data.period)
+    }
+
+    /// Block of the periodic table
+    pub fn block(&self) -> Option<Block> {
+        self.data().map(|data| data.block)
+    }
+
+    /// Category of the element, e.g. `ElementCategory::Lanthanide`
+    pub fn category(&self) -> Option<ElementCategory> {
+        self.data().map(|data| data.category)
+    }
+
+    /// Pauling electronegativity
+    pub fn electronegativity(&self) -> Option<f64> {
+        self.data().and_then(|data| data.electronegativity)
+    }
+
+    /// Single-bond covalent radius in pm
+    pub fn covalent_radius(&self) -> Option<f64> {
+        self.data().and_then(|data| data.covalent_radius)
+    }
+
+    /// Ionic radius of the most common ion in 6-fold coordination
+    pub fn ionic_radius(&self) -> Option<IonicRadius> {
+        self.data().and_then(|data| data.ionic_radius)
+    }
+
+    /// Metallic radius for 12-fold coordination in pm
+    pub fn metallic_radius(&self) -> Option<f64> {
+        self.data().and_then(|data| data.metallic_radius)
+    }
+
+    /// Common oxidation states, the most common first
+    ///
+    /// The list is empty for the noble gases that do not form compounds and for the elements from Mt onwards.
+    pub fn oxidation_states(&self) -> &'static [i32] {
+        self.data().map_or(&[], |data| data.oxidation_states)
+    }
+
+    /// Density near room temperature in g/cm³, for gases at 0 °C and 101.325 kPa
+    pub fn density(&self) -> Option<f64> {
+        self.data().and_then(|data| data.density)
+    }
+
+    /// Melting point at 101.325 kPa in K
+    pub fn melting_point(&self) -> Option<f64> {
+        self.data().and_then(|data| data.melting_point)
+    }
+
+    /// Iterate over all elements from H to Og, without `ElementSymbol::None`
+    ///
+    /// # Example
+    /// ```
+    /// use chemical_formula::prelude::*;
+    ///
+    /// assert_eq!(ElementSymbol::iter().count(), 118);
+    /// assert_eq!(ElementSymbol::iter().next(), Some(ElementSymbol::H));
+    /// ```
+    pub fn iter() -> impl Iterator<Item = ElementSymbol> {
+        ELEMENTS.iter().map(|data| data.symbol)
+    }
+
+    /// Iterate over the elements in a range of atomic numbers
+    ///
+    /// # Example
+    /// ```
+    /// use chemical_formula::prelude::*;
+    ///
+    /// let elements: Vec<_> = ElementSymbol::range(ElementSymbol::Fe..=ElementSymbol::Ni).collect();
+    /// assert_eq!(elements, vec![ElementSymbol::Fe, ElementSymbol::Co, ElementSymbol::Ni]);
+    ///
+    /// assert_eq!(ElementSymbol::range(ElementSymbol::Fe..ElementSymbol::Ni).count(), 2);
+    /// assert_eq!(ElementSymbol::range(ElementSymbol::Fl..).count(), 5);
+    /// ```
+    pub fn range(range: impl RangeBounds<ElementSymbol>) -> impl Iterator<Item = ElementSymbol> {
+        ElementSymbol::iter().filter(move |element| range.contains(element))
+    }
+
+    /// Iterate over the elements of a period
+    ///
+    /// # Example
+    /// ```
+    /// use chemical_formula::prelude::*;
+    ///
+    /// assert_eq!(ElementSymbol::in_period(4).count(), 18);
+    /// assert_eq!(ElementSymbol::in_period(4).last(), Some(ElementSymbol::Kr));
+    /// ```
+    pub fn in_period(period: u8) -> impl Iterator<Item = ElementSymbol> {
+        ElementSymbol::iter().filter(move |element| element.period() == Some(period))
+    }
+
+    /// Iterate over the elements of an IUPAC group
+    ///
+    /// # Example
+    /// ```
+    /// use chemical_formula::prelude::*;
+    ///
+    /// let platinum_group: Vec<_> = ElementSymbol::in_group(10).collect();
+    /// assert_eq!(
+    ///     platinum_group,
+    ///     vec![ElementSymbol::Ni, ElementSymbol::Pd, ElementSymbol::Pt, ElementSymbol::Ds]
+    /// );
+    /// ```
+    pub fn in_group(group: u8) -> impl Iterator<Item = ElementSymbol> {
+        ElementSymbol::iter().filter(move |element| element.group() == Some(group))
+    }
+
+    /// Iterate over the elements of a block
+    pub fn in_block(block: Block) -> impl Iterator<Item = ElementSymbol> {
+        ElementSymbol::iter().filter(move |element| element.block() == Some(block))
+    }
+
+    /// Iterate over the elements of a category
+    ///
+    /// # Example
+    /// ```
+    /// use chemical_formula::prelude::*;
+    ///
+    /// let lanthanides: Vec<_> = ElementSymbol::in_category(ElementCategory::Lanthanide).collect();
+    /// assert_eq!(lanthanides.len(), 15);
+    /// assert_eq!(lanthanides.first(), Some(&ElementSymbol::La));
+    /// assert_eq!(lanthanides.last(), Some(&ElementSymbol::Lu));
+    /// ```
+    pub fn in_category(category: ElementCategory) -> impl Iterator<Item = ElementSymbol> {
+        ElementSymbol::iter().filter(move |element| element.category() == Some(category))
+    }
+}
+
+/// Properties of the elements in order of atomic number
+///
+/// # Example
+/// ```
+/// use chemical_formula::periodic::ELEMENTS;
+/// use chemical_formula::prelude::*;
+///
+/// let lanthanides = ELEMENTS
+///     .iter()
+///     .filter(|data| data.category == ElementCategory::Lanthanide)
+///     .count();
+/// assert_eq!(lanthanides, 15);
+/// ```
+///
+/// # Reference
+/// - Covalent radii: Cordero, B. et al. (2008). Covalent radii revisited. Dalton Transactions, (21), 2832-2838.
+/// - Ionic radii: Shannon, R. D. (1976). Revised effective ionic radii and systematic studies of interatomic distances
+///   in halides and chalcogenides. Acta Crystallographica Section A, 32(5), 751-767.
+/// - Electronegativity, metallic radii, density and melting point: CRC Handbook of Chemistry and Physics
+pub static ELEMENTS: [ElementData; 118] = [
+    ElementData {
+        symbol: ElementSymbol::H,
+        name: "Hydrogen",
+        atomic_number: 1,
+        group: Some(1),
+        period: 1,
+        block: Block::S,
+        category: ElementCategory::Nonmetal,
+        electronegativity: Some(2.2),
+        covalent_radius: Some(31.0),
+        ionic_radius: None,
+        metallic_radius: None,
+        oxidation_states: &[1, -1],
+        density: Some(8.988e-05),
+        melting_point: Some(13.99),
+    },
+    ElementData {
+        symbol: ElementSymbol::He,
+        name: "Helium",
+        atomic_number: 2,
+        group: Some(18),
+        period: 1,
+        block: Block::S,
+        category: ElementCategory::NobleGas,
+        electronegativity: None,
+        covalent_radius: Some(28.0),
+        ionic_radius: None,
+        metallic_radius: None,
+        oxidation_states: &[],
+        density: Some(0.0001785),
+        melting_point: None,
+    },
+    ElementData {
+        symbol: ElementSymbol::Li,
+        name: "Lithium",
+        atomic_number: 3,
+        group: Some(1),
+        period: 2,
+        block: Block::S,
+        category: ElementCategory::AlkaliMetal,
+        electronegativity: Some(0.98),
+        covalent_radius: Some(128.0),
+        ionic_radius: Some(IonicRadius {
+            charge: 1,
+            radius: 76.0,
+        }),
+        metallic_radius: Some(152.0),
+        oxidation_states: &[1],
+        density: Some(0.534),
+        melting_point: Some(453.65),
+    },
+    ElementData {
+        symbol: ElementSymbol::Be,
+        name: "Beryllium",
+        atomic_number: 4,
+        group: Some(2),
+        period: 2,
+        block: Block::S,
+        category: ElementCategory::AlkalineEarthMetal,
+        electronegativity: Some(1.57),
+        covalent_radius: Some(96.0),
+        ionic_radius: Some(IonicRadius {
+            charge: 2,
+            radius: 45.0,
+        }),
+        metallic_radius: Some(112.0),
+        oxidation_states: &[2],
+        density: Some(1.85),
+        melting_point: Some(1560.0),
+    },
+    ElementData {
+        symbol: ElementSymbol::B,
+        name: "Boron",
+        atomic_number: 5,
+        group: Some(13),
+        period: 2,
+        block: Block::P,
+        category: ElementCategory::Metalloid,
+        electronegativity: Some(2.04),
+        covalent_radius: Some(84.0),
+        ionic_radius: Some(IonicRadius {
+            charge: 3,
+            radius: 27.0,
+        }),
+        metallic_radius: None,
+        oxidation_states: &[3],
+        density: Some(2.34),
+        melting_point: Some(2349.0),
+    },
+    ElementData {
+        symbol: ElementSymbol::C,
+        name: "Carbon",
+        atomic_number: 6,
+        group: Some(14),
+        period: 2,
+        block: Block::P,
+        category: ElementCategory::Nonmetal,
+        electronegativity: Some(2.55),
+        covalent_radius: Some(76.0),
+        ionic_radius: Some(IonicRadius {
+            charge: 4,
+            radius: 16.0,
+        }),
+        metallic_radius: None,
+        oxidation_states: &[4, -4, 2],
+        density: Some(2.267),
+        melting_point: None,
+    },
+    ElementData {
+        symbol: ElementSymbol::N,
+        name: "Nitrogen",
+        atomic_number: 7,
+        group: Some(15),
+        period: 2,
+        block: Block::P,
+        category: ElementCategory::Nonmetal,
+        electronegativity: Some(3.04),
+        covalent_radius: Some(71.0),
+        ionic_radius: Some(IonicRadius {
+            charge: -3,
+            radius: 146.0,
+        }),
+        metallic_radius: None,
+        oxidation_states: &[-3, 5, 3],
+        density: Some(0.0012506),
+        melting_point: Some(63.15),
+    },
+    ElementData {
+        symbol: ElementSymbol::O,
+        name: "Oxygen",
+        atomic_number: 8,
+        group: Some(16),
+        period: 2,
+        block: Block::P,
+        category: ElementCategory::Nonmetal,
+        electronegativity: Some(3.44),
+        covalent_radius: Some(66.0),
+        ionic_radius: Some(IonicRadius {
+            charge: -2,
+            radius: 140.0,
+        }),
+        metallic_radius: None,
+        oxidation_states: &[-2],
+        density: Some(0.001429),
+        melting_point: Some(54.36),
+    },
+    ElementData {
+        symbol: ElementSymbol::F,
+        name: "Fluorine",
+        atomic_number: 9,
+        group: Some(17),
+        period: 2,
+        block: Block::P,
+        category: ElementCategory::Halogen,
+        electronegativity: Some(3.98),
+        covalent_radius: Some(57.0),
+        ionic_radius: Some(IonicRadius {
+            charge: -1,
+            radius: 133.0,
+        }),
+        metallic_radius: None,
+        oxidation_states: &[-1],
+        density: Some(0.001696),
+        melting_point: Some(53.48),
+    },
+    ElementData {
+        symbol: ElementSymbol::Ne,
+        name: "Neon",
+        atomic_number: 10,
+        group: Some(18),
+        period: 2,
+        block: Block::P,
+        category: ElementCategory::NobleGas,
+        electronegativity: None,
+        covalent_radius: Some(58.0),
+        ionic_radius: None,
+        metallic_radius: None,
+        oxidation_states: &[],
+        density: Some(0.0008999),
+        melting_point: Some(24.56),
+    },
+    ElementData {
+        symbol: ElementSymbol::Na,
+        name: "Sodium",
+        atomic_number: 11,
+        group: Some(1),
+        period: 3,
+        block: Block::S,
+        category: ElementCategory::AlkaliMetal,
+        electronegativity: Some(0.93),
+        covalent_radius: Some(166.0),
+        ionic_radius: Some(IonicRadius {
+            charge: 1,
+            radius: 102.0,
+        }),
+        metallic_radius: Some(186.0),
+        oxidation_states: &[1],
+        density: Some(0.968),
+        melting_point: Some(370.944),
+    },
+    ElementData {
+        symbol: ElementSymbol::Mg,
+        name: "Magnesium",
+        atomic_number: 12,
+        group: Some(2),
+        period: 3,
+        block: Block::S,
+        category: ElementCategory::AlkalineEarthMetal,
+        electronegativity: Some(1.31),
+        covalent_radius: Some(141.0),
+        ionic_radius: Some(IonicRadius {
+            charge: 2,
+            radius: 72.0,
+        }),
+        metallic_radius: Some(160.0),
+        oxidation_states: &[2],
+        density: Some(1.738),
+        melting_point: Some(923.0),
+    },
+    ElementData {
+        symbol: ElementSymbol::Al,
+        name: "Aluminium",
+        atomic_number: 13,
+        group: Some(13),
+        period: 3,
+        block: Block::P,
+        category: ElementCategory::PostTransitionMetal,
+        electronegativity: Some(1.61),
+        covalent_radius: Some(121.0),
+        ionic_radius: Some(IonicRadius {
+            charge: 3,
+            radius: 53.5,
+        }),
+        metallic_radius: Some(143.0),
+        oxidation_states: &[3],
+        density: Some(2.7),
+        melting_point: Some(933.47),
+    },
+    ElementData {
+        symbol: ElementSymbol::Si,
+        name: "Silicon",
+        atomic_number: 14,
+        group: Some(14),
+        period: 3,
+        block: Block::P,
+        category: ElementCategory::Metalloid,
+        electronegativity: Some(1.9),
+        covalent_radius: Some(111.0),
+        ionic_radius: Some(IonicRadius {
+            charge: 4,
+            radius: 40.0,
+        }),
+        metallic_radius: None,
+        oxidation_states: &[4, -4],
+        density: Some(2.329),
+        melting_point: Some(1687.0),
+    },
+    ElementData {
+        symbol: ElementSymbol::P,
+        name: "Phosphorus",
+        atomic_number: 15,
+        group: Some(15),
+        period: 3,
+        block: Block::P,
+        category: ElementCategory::Nonmetal,
+        electronegativity: Some(2.19),
+        covalent_radius: Some(107.0),
+        ionic_radius: Some(IonicRadius {
+            charge: 5,
+            radius: 38.0,
+        }),
+        metallic_radius: None,
+        oxidation_states: &[5, -3, 3],
+        density: Some(1.823),
+        melting_point: Some(317.3),
+    },
+    ElementData {
+        symbol: ElementSymbol::S,
+        name: "Sulfur",
+        atomic_number: 16,
+        group: Some(16),
+        period: 3,
+        block: Block::P,
+        category: ElementCategory::Nonmetal,
+        electronegativity: Some(2.58),
+        covalent_radius: Some(105.0),
+        ionic_radius: Some(IonicRadius {
+            charge: -2,
+            radius: 184.0,
+        }),
+        metallic_radius: None,
+        oxidation_states: &[6, -2, 4],
+        density: Some(2.07),
+        melting_point: Some(388.36),
+    },
+    ElementData {
+        symbol: ElementSymbol::Cl,
+        name: "Chlorine",
+        atomic_number: 17,
+        group: Some(17),
+        period: 3,
+        block: Block::P,
+        category: ElementCategory::Halogen,
+        electronegativity: Some(3.16),
+        covalent_radius: Some(102.0),
+        ionic_radius: Some(IonicRadius {
+            charge: -1,
+            radius: 181.0,
+        }),
+        metallic_radius: None,
+        oxidation_states: &[-1, 7, 5, 3, 1],
+        density: Some(0.003214),
+        melting_point: Some(171.6),
+    },
+    ElementData {
+        symbol: ElementSymbol::Ar,
+        name: "Argon",
+        atomic_number: 18,
+        group: Some(18),
+        period: 3,
+        block: Block::P,
+        category: ElementCategory::NobleGas,
+        electronegativity: None,
+        covalent_radius: Some(106.0),
+        ionic_radius: None,
+        metallic_radius: None,
+        oxidation_states: &[],
+        density: Some(0.0017837),
+        melting_point: Some(83.81),
+    },
+    ElementData {
+        symbol: ElementSymbol::K,
+        name: "Potassium",
+        atomic_number: 19,
+        group: Some(1),
+        period: 4,
+        block: Block::S,
+        category: ElementCategory::AlkaliMetal,
+        electronegativity: Some(0.82),
+        covalent_radius: Some(203.0),
+        ionic_radius: Some(IonicRadius {
+            charge: 1,
+            radius: 138.0,
+        }),
+        metallic_radius: Some(227.0),
+        oxidation_states: &[1],
+        density: Some(0.862),
+        melting_point: Some(336.7),
+    },
+    ElementData {
+        symbol: ElementSymbol::Ca,
+        name: "Calcium",
+        atomic_number: 20,
+        group: Some(2),
+        period: 4,
+        block: Block::S,
+        category: ElementCategory::AlkalineEarthMetal,
+        electronegativity: Some(1.0),
+        covalent_radius: Some(176.0),
+        ionic_radius: Some(IonicRadius {
+            charge: 2,
+            radius: 100.0,
+        }),
+        metallic_radius: Some(197.0),
+        oxidation_states: &[2],
+        density: Some(1.55),
+        melting_point: Some(1115.0),
+    },
+    ElementData {
+        symbol: ElementSymbol::Sc,
+        name: "Scandium",
+        atomic_number: 21,
+        group: Some(3),
+        period: 4,
+        block: Block::D,
+        category: ElementCategory::TransitionMetal,
+        electronegativity: Some(1.36),
+        covalent_radius: Some(170.0),
+        ionic_radius: Some(IonicRadius {
+            charge: 3,
+            radius: 74.5,
+        }),
+        metallic_radius: Some(162.0),
+        oxidation_states: &[3],
+        density: Some(2.985),
+        melting_point: Some(1814.0),
+    },
+    ElementData {
+        symbol: ElementSymbol::Ti,
+        name: "Titanium",
+        atomic_number: 22,
+        group: Some(4),
+        period: 4,
+        block: Block::D,
+        category: ElementCategory::TransitionMetal,
+        electronegativity: Some(1.54),
+        covalent_radius: Some(160.0),
+        ionic_radius: Some(IonicRadius {
+            charge: 4,
+            radius: 60.5,
+        }),
+        metallic_radius: Some(147.0),
+        oxidation_states: &[4, 3, 2],
+        density: Some(4.506),
+        melting_point: Some(1941.0),
+    },
+    ElementData {
+        symbol: ElementSymbol::V,
+        name: "Vanadium",
+        atomic_number: 23,
+        group: Some(5),
+        period: 4,
+        block: Block::D,
+        category: ElementCategory::TransitionMetal,
+        electronegativity: Some(1.63),
+        covalent_radius: Some(153.0),
+        ionic_radius: Some(IonicRadius {
+            charge: 5,
+            radius: 54.0,
+        }),
+        metallic_radius: Some(134.0),
+        oxidation_states: &[5, 4, 3, 2],
+        density: Some(6.0),
+        melting_point: Some(2183.0),
+    },
+    ElementData {
+        symbol: ElementSymbol::Cr,
+        name: "Chromium",
+        atomic_number: 24,
+        group: Some(6),
+        period: 4,
+        block: Block::D,
+        category: ElementCategory::TransitionMetal,
+        electronegativity: Some(1.66),
+        covalent_radius: Some(139.0),
+        ionic_radius: Some(IonicRadius {
+            charge: 3,
+            radius: 61.5,
+        }),
+        metallic_radius: Some(128.0),
+        oxidation_states: &[3, 6, 2],
+        density: Some(7.19),
+        melting_point: Some(2180.0),
+    },
+    ElementData {
+        symbol: ElementSymbol::Mn,
+        name: "Manganese",
+        atomic_number: 25,
+        group: Some(7),
+        period: 4,
+        block: Block::D,
+        category: ElementCategory::TransitionMetal,
+        electronegativity: Some(1.55),
+        covalent_radius: Some(139.0),
+        ionic_radius: Some(IonicRadius {
+            charge: 2,
+            radius: 83.0,
+        }),
+        metallic_radius: Some(127.0),
+        oxidation_states: &[2, 3, 4, 7, 6],
+        density: Some(7.21),
+        melting_point: Some(1519.0),
+    },
+    ElementData {
+        symbol: ElementSymbol::Fe,
+        name: "Iron",
+        atomic_number: 26,
+        group: Some(8),
+        period: 4,
+        block: Block::D,
+        category: ElementCategory::TransitionMetal,
+        electronegativity: Some(1.83),
+        covalent_radius: Some(132.0),
+        ionic_radius: Some(IonicRadius {
+            charge: 3,
+            radius: 64.5,
+        }),
+        metallic_radius: Some(126.0),
+        oxidation_states: &[3, 2],
+        density: Some(7.874),
+        melting_point: Some(1811.0),
+    },
+    ElementData {
+        symbol: ElementSymbol::Co,
+        name: "Cobalt",
+        atomic_number: 27,
+        group: Some(9),
+        period: 4,
+        block: Block::D,
+        category: ElementCategory::TransitionMetal,
+        electronegativity: Some(1.88),
+        covalent_radius: Some(126.0),
+        ionic_radius: Some(IonicRadius {
+            charge: 2,
+            radius: 74.5,
+        }),
+        metallic_radius: Some(125.0),
+        oxidation_states: &[2, 3, 4],
+        density: Some(8.9),
+        melting_point: Some(1768.0),
+    },
+    ElementData {
+        symbol: ElementSymbol::Ni,
+        name: "Nickel",
+        atomic_number: 28,
+        group: Some(10),
+        period: 4,
+        block: Block::D,
+        category: ElementCategory::TransitionMetal,
+        electronegativity: Some(1.91),
+        covalent_radius: Some(124.0),
+        ionic_radius: Some(IonicRadius {
+            charge: 2,
+            radius: 69.0,
+        }),
+        metallic_radius: Some(124.0),
+        oxidation_states: &[2, 3],
+        density: Some(8.908),
+        melting_point: Some(1728.0),
+    },
+    ElementData {
+        symbol: ElementSymbol::Cu,
+        name: "Copper",
+        atomic_number: 29,
+        group: Some(11),
+        period: 4,
+        block: Block::D,
+        category: ElementCategory::TransitionMetal,
+        electronegativity: Some(1.9),
+        covalent_radius: Some(132.0),
+        ionic_radius: Some(IonicRadius {
+            charge: 2,
+            radius: 73.0,
+        }),
+        metallic_radius: Some(128.0),
+        oxidation_states: &[2, 1],
+        density: Some(8.96),
+        melting_point: Some(1357.77),
+    },
+    ElementData {
+        symbol: ElementSymbol::Zn,
+        name: "Zinc",
+        atomic_number: 30,
+        group: Some(12),
+        period: 4,
+        block: Block::D,
+        category: ElementCategory::TransitionMetal,
+        electronegativity: Some(1.65),
+        covalent_radius: Some(122.0),
+        ionic_radius: Some(IonicRadius {
+            charge: 2,
+            radius: 74.0,
+        }),
+        metallic_radius: Some(134.0),
+        oxidation_states: &[2],
+        density: Some(7.14),
+        melting_point: Some(692.68),
+    },
+    ElementData {
+        symbol: ElementSymbol::Ga,
+        name: "Gallium",
+        atomic_number: 31,
+        group: Some(13),
+        period: 4,
+        block: Block::P,
+        category: ElementCategory::PostTransitionMetal,
+        electronegativity: Some(1.81),
+        covalent_radius: Some(122.0),
+        ionic_radius: Some(IonicRadius {
+            charge: 3,
+            radius: 62.0,
+        }),
+        metallic_radius: Some(135.0),
+        oxidation_states: &[3],
+        density: Some(5.91),
+        melting_point: Some(302.9146),
+    },
+    ElementData {
+        symbol: ElementSymbol::Ge,
+        name: "Germanium",
+        atomic_number: 32,
+        group: Some(14),
+        period: 4,
+        block: Block::P,
+        category: ElementCategory::Metalloid,
+        electronegativity: Some(2.01),
+        covalent_radius: Some(120.0),
+        ionic_radius: Some(IonicRadius {
+            charge: 4,
+            radius: 53.0,
+        }),
+        metallic_radius: None,
+        oxidation_states: &[4, 2],
+        density: Some(5.323),
+        melting_point: Some(1211.4),
+    },
+    ElementData {
+        symbol: ElementSymbol::As,
+        name: "Arsenic",
+        atomic_number: 33,
+        group: Some(15),
+        period: 4,
+        block: Block::P,
+        category: ElementCategory::Metalloid,
+        electronegativity: Some(2.18),
+        covalent_radius: Some(119.0),
+        ionic_radius: Some(IonicRadius {
+            charge: 5,
+            radius: 46.0,
+        }),
+        metallic_radius: None,
+        oxidation_states: &[5, 3, -3],
+        density: Some(5.727),
+        melting_point: None,
+    },
+    ElementData {
+        symbol: ElementSymbol::Se,
+        name: "Selenium",
+        atomic_number: 34,
+        group: Some(16),
+        period: 4,
+        block: Block::P,
+        category: ElementCategory::Nonmetal,
+        electronegativity: Some(2.55),
+        covalent_radius: Some(120.0),
+        ionic_radius: Some(IonicRadius {
+            charge: -2,
+            radius: 198.0,
+        }),
+        metallic_radius: None,
+        oxidation_states: &[-2, 4, 6],
+        density: Some(4.81),
+        melting_point: Some(494.0),
+    },
+    ElementData {
+        symbol: ElementSymbol::Br,
+        name: "Bromine",
+        atomic_number: 35,
+        group: Some(17),
+        period: 4,
+        block: Block::P,
+        category: ElementCategory::Halogen,
+        electronegativity: Some(2.96),
+        covalent_radius: Some(120.0),
+        ionic_radius: Some(IonicRadius {
+            charge: -1,
+            radius: 196.0,
+        }),
+        metallic_radius: None,
+        oxidation_states: &[-1, 5, 1],
+        density: Some(3.1028),
+        melting_point: Some(265.8),
+    },
+    ElementData {
+        symbol: ElementSymbol::Kr,
+        name: "Krypton",
+        atomic_number: 36,
+        group: Some(18),
+        period: 4,
+        block: Block::P,
+        category: ElementCategory::NobleGas,
+        electronegativity: Some(3.0),
+        covalent_radius: Some(116.0),
+        ionic_radius: None,
+        metallic_radius: None,
+        oxidation_states: &[2],
+        density: Some(0.003733),
+        melting_point: Some(115.78),
+    },
+    ElementData {
+        symbol: ElementSymbol::Rb,
+        name: "Rubidium",
+        atomic_number: 37,
+        group: Some(1),
+        period: 5,
+        block: Block::S,
+        category: ElementCategory::AlkaliMetal,
+        electronegativity: Some(0.82),
+        covalent_radius: Some(220.0),
+        ionic_radius: Some(IonicRadius {
+            charge: 1,
+            radius: 152.0,
+        }),
+        metallic_radius: Some(248.0),
+        oxidation_states: &[1],
+        density: Some(1.532),
+        melting_point: Some(312.45),
+    },
+    ElementData {
+        symbol: ElementSymbol::Sr,
+        name: "Strontium",
+        atomic_number: 38,
+        group: Some(2),
+        period: 5,
+        block: Block::S,
+        category: ElementCategory::AlkalineEarthMetal,
+        electronegativity: Some(0.95),
+        covalent_radius: Some(195.0),
+        ionic_radius: Some(IonicRadius {
+            charge: 2,
+            radius: 118.0,
+        }),
+        metallic_radius: Some(215.0),
+        oxidation_states: &[2],
+        density: Some(2.64),
+        melting_point: Some(1050.0),
+    },
+    ElementData {
+        symbol: ElementSymbol::Y,
+        name: "Yttrium",
+        atomic_number: 39,
+        group: Some(3),
+        period: 5,
+        block: Block::D,
+        category: ElementCategory::TransitionMetal,
+        electronegativity: Some(1.22),
+        covalent_radius: Some(190.0),
+        ionic_radius: Some(IonicRadius {
+            charge: 3,
+            radius: 90.0,
+        }),
+        metallic_radius: Some(180.0),
+        oxidation_states: &[3],
+        density: Some(4.472),
+        melting_point: Some(1799.0),
+    },
+    ElementData {
+        symbol: ElementSymbol::Zr,
+        name: "Zirconium",
+        atomic_number: 40,
+        group: Some(4),
+        period: 5,
+        block: Block::D,
+        category: ElementCategory::TransitionMetal,
+        electronegativity: Some(1.33),
+        covalent_radius: Some(175.0),
+        ionic_radius: Some(IonicRadius {
+            charge: 4,
+            radius: 72.0,
+        }),
+        metallic_radius: Some(160.0),
+        oxidation_states: &[4],
+        density: Some(6.52),
+        melting_point: Some(2128.0),
+    },
+    ElementData {
+        symbol: ElementSymbol::Nb,
+        name: "Niobium",
+        atomic_number: 41,
+        group: Some(5),
+        period: 5,
+        block: Block::D,
+        category: ElementCategory::TransitionMetal,
+        electronegativity: Some(1.6),
+        covalent_radius: Some(164.0),
+        ionic_radius: Some(IonicRadius {
+            charge: 5,
+            radius: 64.0,
+        }),
+        metallic_radius: Some(146.0),
+        oxidation_states: &[5, 4],
+        density: Some(8.57),
+        melting_point: Some(2750.0),
+    },
+    ElementData {
+        symbol: ElementSymbol::Mo,
+        name: "Molybdenum",
+        atomic_number: 42,
+        group: Some(6),
+        period: 5,
+        block: Block::D,
+        category: ElementCategory::TransitionMetal,
+        electronegativity: Some(2.16),
+        covalent_radius: Some(154.0),
+        ionic_radius: Some(IonicRadius {
+            charge: 6,
+            radius: 59.0,
+        }),
+        metallic_radius: Some(139.0),
+        oxidation_states: &[6, 4],
+        density: Some(10.28),
+        melting_point: Some(2896.0),
+    },
+    ElementData {
+        symbol: ElementSymbol::Tc,
+        name: "Technetium",
+        atomic_number: 43,
+        group: Some(7),
+        period: 5,
+        block: Block::D,
+        category: ElementCategory::TransitionMetal,
+        electronegativity: Some(1.9),
+        covalent_radius: Some(147.0),
+        ionic_radius: Some(IonicRadius {
+            charge: 4,
+            radius: 64.5,
+        }),
+        metallic_radius: Some(136.0),
+        oxidation_states: &[7, 4],
+        density: Some(11.0),
+        melting_point: Some(2430.0),
+    },
+    ElementData {
+        symbol: ElementSymbol::Ru,
+        name: "Ruthenium",
+        atomic_number: 44,
+        group: Some(8),
+        period: 5,
+        block: Block::D,
+        category: ElementCategory::TransitionMetal,
+        electronegativity: Some(2.2),
+        covalent_radius: Some(146.0),
+        ionic_radius: Some(IonicRadius {
+            charge: 3,
+            radius: 68.0,
+        }),
+        metallic_radius: Some(134.0),
+        oxidation_states: &[3, 4],
+        density: Some(12.45),
+        melting_point: Some(2607.0),
+    },
+    ElementData {
+        symbol: ElementSymbol::Rh,
+        name: "Rhodium",
+        atomic_number: 45,
+        group: Some(9),
+        period: 5,
+        block: Block::D,
+        category: ElementCategory::TransitionMetal,
+        electronegativity: Some(2.28),
+        covalent_radius: Some(142.0),
+        ionic_radius: Some(IonicRadius {
+            charge: 3,
+            radius: 66.5,
+        }),
+        metallic_radius: Some(134.0),
+        oxidation_states: &[3],
+        density: Some(12.41),
+        melting_point: Some(2237.0),
+    },
+    ElementData {
+        symbol: ElementSymbol::Pd,
+        name: "Palladium",
+        atomic_number: 46,
+        group: Some(10),
+        period: 5,
+        block: Block::D,
+        category: ElementCategory::TransitionMetal,
+        electronegativity: Some(2.2),
+        covalent_radius: Some(139.0),
+        ionic_radius: Some(IonicRadius {
+            charge: 2,
+            radius: 86.0,
+        }),
+        metallic_radius: Some(137.0),
+        oxidation_states: &[2, 4],
+        density: Some(12.023),
+        melting_point: Some(1828.05),
+    },
+    ElementData {
+        symbol: ElementSymbol::Ag,
+        name: "Silver",
+        atomic_number: 47,
+        group: Some(11),
+        period: 5,
+        block: Block::D,
+        category: ElementCategory::TransitionMetal,
+        electronegativity: Some(1.93),
+        covalent_radius: Some(145.0),
+        ionic_radius: Some(IonicRadius {
+            charge: 1,
+            radius: 115.0,
+        }),
+        metallic_radius: Some(144.0),
+        oxidation_states: &[1],
+        density: Some(10.49),
+        melting_point: Some(1234.93),
+    },
+    ElementData {
+        symbol: ElementSymbol::Cd,
+        name: "Cadmium",
+        atomic_number: 48,
+        group: Some(12),
+        period: 5,
+        block: Block::D,
+        category: ElementCategory::TransitionMetal,
+        electronegativity: Some(1.69),
+        covalent_radius: Some(144.0),
+        ionic_radius: Some(IonicRadius {
+            charge: 2,
+            radius: 95.0,
+        }),
+        metallic_radius: Some(151.0),
+        oxidation_states: &[2],
+        density: Some(8.65),
+        melting_point: Some(594.22),
+    },
+    ElementData {
+        symbol: ElementSymbol::In,
+        name: "Indium",
+        atomic_number: 49,
+        group: Some(13),
+        period: 5,
+        block: Block::P,
+        category: ElementCategory::PostTransitionMetal,
+        electronegativity: Some(1.78),
+        covalent_radius: Some(142.0),
+        ionic_radius: Some(IonicRadius {
+            charge: 3,
+            radius: 80.0,
+        }),
+        metallic_radius: Some(167.0),
+        oxidation_states: &[3],
+        density: Some(7.31),
+        melting_point: Some(429.75),
+    },
+    ElementData {
+        symbol: ElementSymbol::Sn,
+        name: "Tin",
+        atomic_number: 50,
+        group: Some(14),
+        period: 5,
+        block: Block::P,
+        category: ElementCategory::PostTransitionMetal,
+        electronegativity: Some(1.96),
+        covalent_radius: Some(139.0),
+        ionic_radius: Some(IonicRadius {
+            charge: 4,
+            radius: 69.0,
+        }),
+        metallic_radius: Some(158.0),
+        oxidation_states: &[4, 2],
+        density: Some(7.265),
+        melting_point: Some(505.08),
+    },
+    ElementData {
+        symbol: ElementSymbol::Sb,
+        name: "Antimony",
+        atomic_number: 51,
+        group: Some(15),
+        period: 5,
+        block: Block::P,
+        category: ElementCategory::Metalloid,
+        electronegativity: Some(2.05),
+        covalent_radius: Some(139.0),
+        ionic_radius: Some(IonicRadius {
+            charge: 3,
+            radius: 76.0,
+        }),
+        metallic_radius: None,
+        oxidation_states: &[3, 5],
+        density: Some(6.697),
+        melting_point: Some(903.78),
+    },
+    ElementData {
+        symbol: ElementSymbol::Te,
+        name: "Tellurium",
+        atomic_number: 52,
+        group: Some(16),
+        period: 5,
+        block: Block::P,
+        category: ElementCategory::Metalloid,
+        electronegativity: Some(2.1),
+        covalent_radius: Some(138.0),
+        ionic_radius: Some(IonicRadius {
+            charge: -2,
+            radius: 221.0,
+        }),
+        metallic_radius: None,
+        oxidation_states: &[-2, 4, 6],
+        density: Some(6.24),
+        melting_point: Some(722.66),
+    },
+    ElementData {
+        symbol: ElementSymbol::I,
+        name: "Iodine",
+        atomic_number: 53,
+        group: Some(17),
+        period: 5,
+        block: Block::P,
+        category: ElementCategory::Halogen,
+        electronegativity: Some(2.66),
+        covalent_radius: Some(139.0),
+        ionic_radius: Some(IonicRadius {
+            charge: -1,
+            radius: 220.0,
+        }),
+        metallic_radius: None,
+        oxidation_states: &[-1, 5, 7],
+        density: Some(4.933),
+        melting_point: Some(386.85),
+    },
+    ElementData {
+        symbol: ElementSymbol::Xe,
+        name: "Xenon",
+        atomic_number: 54,
+        group: Some(18),
+        period: 5,
+        block: Block::P,
+        category: ElementCategory::NobleGas,
+        electronegativity: Some(2.6),
+        covalent_radius: Some(140.0),
+        ionic_radius: None,
+        metallic_radius: None,
+        oxidation_states: &[2, 4, 6],
+        density: Some(0.005894),
+        melting_point: Some(161.4),
+    },
+    ElementData {
+        symbol: ElementSymbol::Cs,
+        name: "Caesium",
+        atomic_number: 55,
+        group: Some(1),
+        period: 6,
+        block: Block::S,
+        category: ElementCategory::AlkaliMetal,
+        electronegativity: Some(0.79),
+        covalent_radius: Some(244.0),
+        ionic_radius: Some(IonicRadius {
+            charge: 1,
+            radius: 167.0,
+        }),
+        metallic_radius: Some(265.0),
+        oxidation_states: &[1],
+        density: Some(1.93),
+        melting_point: Some(301.59),
+    },
+    ElementData {
+        symbol: ElementSymbol::Ba,
+        name: "Barium",
+        atomic_number: 56,
+        group: Some(2),
+        period: 6,
+        block: Block::S,
+        category: ElementCategory::AlkalineEarthMetal,
+        electronegativity: Some(0.89),
+        covalent_radius: Some(215.0),
+        ionic_radius: Some(IonicRadius {
+            charge: 2,
+            radius: 135.0,
+        }),
+        metallic_radius: Some(222.0),
+        oxidation_states: &[2],
+        density: Some(3.51),
+        melting_point: Some(1000.0),
+    },
+    ElementData {
+        symbol: ElementSymbol::La,
+        name: "Lanthanum",
+        atomic_number: 57,
+        group: Some(3),
+        period: 6,
+        block: Block::D,
+        category: ElementCategory::Lanthanide,
+        electronegativity: Some(1.1),
+        covalent_radius: Some(207.0),
+        ionic_radius: Some(IonicRadius {
+            charge: 3,
+            radius: 103.2,
+        }),
+        metallic_radius: Some(187.0),
+        oxidation_states: &[3],
+        density: Some(6.162),
+        melting_point: Some(1193.0),
+    },
+    ElementData {
+        symbol: ElementSymbol::Ce,
+        name: "Cerium",
+        atomic_number: 58,
+        group: None,
+        period: 6,
+        block: Block::F,
+        category: ElementCategory::Lanthanide,
+        electronegativity: Some(1.12),
+        covalent_radius: Some(204.0),
+        ionic_radius: Some(IonicRadius {
+            charge: 3,
+            radius: 101.0,
+        }),
+        metallic_radius: Some(181.8),
+        oxidation_states: &[3, 4],
+        density: Some(6.77),
+        melting_point: Some(1068.0),
+    },
+    ElementData {
+        symbol: ElementSymbol::Pr,
+        name: "Praseodymium",
+        atomic_number: 59,
+        group: None,
+        period: 6,
+        block: Block::F,
+        category: ElementCategory::Lanthanide,
+        electronegativity: Some(1.13),
+        covalent_radius: Some(203.0),
+        ionic_radius: Some(IonicRadius {
+            charge: 3,
+            radius: 99.0,
+        }),
+        metallic_radius: Some(182.4),
+        oxidation_states: &[3, 4],
+        density: Some(6.77),
+        melting_point: Some(1208.0),
+    },
+    ElementData {
+        symbol: ElementSymbol::Nd,
+        name: "Neodymium",
+        atomic_number: 60,
+        group: None,
+        period: 6,
+        block: Block::F,
+        category: ElementCategory::Lanthanide,
+        electronegativity: Some(1.14),
+        covalent_radius: Some(201.0),
+        ionic_radius: Some(IonicRadius {
+            charge: 3,
+            radius: 98.3,
+        }),
+        metallic_radius: Some(181.4),
+        oxidation_states: &[3],
+        density: Some(7.01),
+        melting_point: Some(1297.0),
+    },
+    ElementData {
+        symbol: ElementSymbol::Pm,
+        name: "Promethium",
+        atomic_number: 61,
+        group: None,
+        period: 6,
+        block: Block::F,
+        category: ElementCategory::Lanthanide,
+        electronegativity: None,
+        covalent_radius: Some(199.0),
+        ionic_radius: Some(IonicRadius {
+            charge: 3,
+            radius: 97.0,
+        }),
+        metallic_radius: Some(183.4),
+        oxidation_states: &[3],
+        density: Some(7.26),
+        melting_point: Some(1315.0),
+    },
+    ElementData {
+        symbol: ElementSymbol::Sm,
+        name: "Samarium",
+        atomic_number: 62,
+        group: None,
+        period: 6,
+        block: Block::F,
+        category: ElementCategory::Lanthanide,
+        electronegativity: Some(1.17),
+        covalent_radius: Some(198.0),
+        ionic_radius: Some(IonicRadius {
+            charge: 3,
+            radius: 95.8,
+        }),
+        metallic_radius: Some(180.4),
+        oxidation_states: &[3, 2],
+        density: Some(7.52),
+        melting_point: Some(1345.0),
+    },
+    ElementData {
+        symbol: ElementSymbol::Eu,
+        name: "Europium",
+        atomic_number: 63,
+        group: None,
+        period: 6,
+        block: Block::F,
+        category: ElementCategory::Lanthanide,
+        electronegativity: None,
+        covalent_radius: Some(198.0),
+        ionic_radius: Some(IonicRadius {
+            charge: 3,
+            radius: 94.7,
+        }),
+        metallic_radius: Some(208.4),
+        oxidation_states: &[3, 2],
+        density: Some(5.244),
+        melting_point: Some(1099.0),
+    },
+    ElementData {
+        symbol: ElementSymbol::Gd,
+        name: "Gadolinium",
+        atomic_number: 64,
+        group: None,
+        period: 6,
+        block: Block::F,
+        category: ElementCategory::Lanthanide,
+        electronegativity: Some(1.2),
+        covalent_radius: Some(196.0),
+        ionic_radius: Some(IonicRadius {
+            charge: 3,
+            radius: 93.8,
+        }),
+        metallic_radius: Some(180.4),
+        oxidation_states: &[3],
+        density: Some(7.9),
+        melting_point: Some(1585.0),
+    },
+    ElementData {
+        symbol: ElementSymbol::Tb,
+        name: "Terbium",
+        atomic_number: 65,
+        group: None,
+        period: 6,
+        block: Block::F,
+        category: ElementCategory::Lanthanide,
+        electronegativity: None,
+        covalent_radius: Some(194.0),
+        ionic_radius: Some(IonicRadius {
+            charge: 3,
+            radius: 92.3,
+        }),
+        metallic_radius: Some(177.3),
+        oxidation_states: &[3, 4],
+        density: Some(8.23),
+        melting_point: Some(1629.0),
+    },
+    ElementData {
+        symbol: ElementSymbol::Dy,
+        name: "Dysprosium",
+        atomic_number: 66,
+        group: None,
+        period: 6,
+        block: Block::F,
+        category: ElementCategory::Lanthanide,
+        electronegativity: Some(1.22),
+        covalent_radius: Some(192.0),
+        ionic_radius: Some(IonicRadius {
+            charge: 3,
+            radius: 91.2,
+        }),
+        metallic_radius: Some(178.1),
+        oxidation_states: &[3],
+        density: Some(8.54),
+        melting_point: Some(1680.0),
+    },
+    ElementData {
+        symbol: ElementSymbol::Ho,
+        name: "Holmium",
+        atomic_number: 67,
+        group: None,
+        period: 6,
+        block: Block::F,
+        category: ElementCategory::Lanthanide,
+        electronegativity: Some(1.23),
+        covalent_radius: Some(192.0),
+        ionic_radius: Some(IonicRadius {
+            charge: 3,
+            radius: 90.1,
+        }),
+        metallic_radius: Some(176.2),
+        oxidation_states: &[3],
+        density: Some(8.79),
+        melting_point: Some(1734.0),
+    },
+    ElementData {
+        symbol: ElementSymbol::Er,
+        name: "Erbium",
+        atomic_number: 68,
+        group: None,
+        period: 6,
+        block: Block::F,
+        category: ElementCategory::Lanthanide,
+        electronegativity: Some(1.24),
+        covalent_radius: Some(189.0),
+        ionic_radius: Some(IonicRadius {
+            charge: 3,
+            radius: 89.0,
+        }),
+        metallic_radius: Some(176.1),
+        oxidation_states: &[3],
+        density: Some(9.066),
+        melting_point: Some(1802.0),
+    },
+    ElementData {
+        symbol: ElementSymbol::Tm,
+        name: "Thulium",
+        atomic_number: 69,
+        group: None,
+        period: 6,
+        block: Block::F,
+        category: ElementCategory::Lanthanide,
+        electronegativity: Some(1.25),
+        covalent_radius: Some(190.0),
+        ionic_radius: Some(IonicRadius {
+            charge: 3,
+            radius: 88.0,
+        }),
+        metallic_radius: Some(175.9),
+        oxidation_states: &[3],
+        density: Some(9.32),
+        melting_point: Some(1818.0),
+    },
+    ElementData {
+        symbol: ElementSymbol::Yb,
+        name: "Ytterbium",
+        atomic_number: 70,
+        group: None,
+        period: 6,
+        block: Block::F,
+        category: ElementCategory::Lanthanide,
+        electronegativity: None,
+        covalent_radius: Some(187.0),
+        ionic_radius: Some(IonicRadius {
+            charge: 3,
+            radius: 86.8,
+        }),
+        metallic_radius: Some(193.3),
+        oxidation_states: &[3, 2],
+        density: Some(6.9),
+        melting_point: Some(1097.0),
+    },
+    ElementData {
+        symbol: ElementSymbol::Lu,
+        name: "Lutetium",
+        atomic_number: 71,
+        group: None,
+        period: 6,
+        block: Block::F,
+        category: ElementCategory::Lanthanide,
+        electronegativity: Some(1.27),
+        covalent_radius: Some(187.0),
+        ionic_radius: Some(IonicRadius {
+            charge: 3,
+            radius: 86.1,
+        }),
+        metallic_radius: Some(173.8),
+        oxidation_states: &[3],
+        density: Some(9.841),
+        melting_point: Some(1925.0),
+    },
+    ElementData {
+        symbol: ElementSymbol::Hf,
+        name: "Hafnium",
+        atomic_number: 72,
+        group: Some(4),
+        period: 6,
+        block: Block::D,
+        category: ElementCategory::TransitionMetal,
+        electronegativity: Some(1.3),
+        covalent_radius: Some(175.0),
+        ionic_radius: Some(IonicRadius {
+            charge: 4,
+            radius: 71.0,
+        }),
+        metallic_radius: Some(159.0),
+        oxidation_states: &[4],
+        density: Some(13.31),
+        melting_point: Some(2506.0),
+    },
+    ElementData {
+        symbol: ElementSymbol::Ta,
+        name: "Tantalum",
+        atomic_number: 73,
+        group: Some(5),
+        period: 6,
+        block: Block::D,
+        category: ElementCategory::TransitionMetal,
+        electronegativity: Some(1.5),
+        covalent_radius: Some(170.0),
+        ionic_radius: Some(IonicRadius {
+            charge: 5,
+            radius: 64.0,
+        }),
+        metallic_radius: Some(146.0),
+        oxidation_states: &[5],
+        density: Some(16.69),
+        melting_point: Some(3290.0),
+    },
+    ElementData {
+        symbol: ElementSymbol::W,
+        name: "Tungsten",
+        atomic_number: 74,
+        group: Some(6),
+        period: 6,
+        block: Block::D,
+        category: ElementCategory::TransitionMetal,
+        electronegativity: Some(2.36),
+        covalent_radius: Some(162.0),
+        ionic_radius: Some(IonicRadius {
+            charge: 6,
+            radius: 60.0,
+        }),
+        metallic_radius: Some(139.0),
+        oxidation_states: &[6, 4],
+        density: Some(19.25),
+        melting_point: Some(3695.0),
+    },
+    ElementData {
+        symbol: ElementSymbol::Re,
+        name: "Rhenium",
+        atomic_number: 75,
+        group: Some(7),
+        period: 6,
+        block: Block::D,
+        category: ElementCategory::TransitionMetal,
+        electronegativity: Some(1.9),
+        covalent_radius: Some(151.0),
+        ionic_radius: Some(IonicRadius {
+            charge: 7,
+            radius: 53.0,
+        }),
+        metallic_radius: Some(137.0),
+        oxidation_states: &[7, 4],
+        density: Some(21.02),
+        melting_point: Some(3459.0),
+    },
+    ElementData {
+        symbol: ElementSymbol::Os,
+        name: "Osmium",
+        atomic_number: 76,
+        group: Some(8),
+        period: 6,
+        block: Block::D,
+        category: ElementCategory::TransitionMetal,
+        electronegativity: Some(2.2),
+        covalent_radius: Some(144.0),
+        ionic_radius: Some(IonicRadius {
+            charge: 4,
+            radius: 63.0,
+        }),
+        metallic_radius: Some(135.0),
+        oxidation_states: &[4, 8],
+        density: Some(22.59),
+        melting_point: Some(3306.0),
+    },
+    ElementData {
+        symbol: ElementSymbol::Ir,
+        name: "Iridium",
+        atomic_number: 77,
+        group: Some(9),
+        period: 6,
+        block: Block::D,
+        category: ElementCategory::TransitionMetal,
+        electronegativity: Some(2.2),
+        covalent_radius: Some(141.0),
+        ionic_radius: Some(IonicRadius {
+            charge: 4,
+            radius: 62.5,
+        }),
+        metallic_radius: Some(135.5),
+        oxidation_states: &[4, 3],
+        density: Some(22.56),
+        melting_point: Some(2719.0),
+    },
+    ElementData {
+        symbol: ElementSymbol::Pt,
+        name: "Platinum",
+        atomic_number: 78,
+        group: Some(10),
+        period: 6,
+        block: Block::D,
+        category: ElementCategory::TransitionMetal,
+        electronegativity: Some(2.28),
+        covalent_radius: Some(136.0),
+        ionic_radius: Some(IonicRadius {
+            charge: 2,
+            radius: 80.0,
+        }),
+        metallic_radius: Some(138.5),
+        oxidation_states: &[2, 4],
+        density: Some(21.45),
+        melting_point: Some(2041.4),
+    },
+    ElementData {
+        symbol: ElementSymbol::Au,
+        name: "Gold",
+        atomic_number: 79,
+        group: Some(11),
+        period: 6,
+        block: Block::D,
+        category: ElementCategory::TransitionMetal,
+        electronegativity: Some(2.54),
+        covalent_radius: Some(136.0),
+        ionic_radius: Some(IonicRadius {
+            charge: 3,
+            radius: 85.0,
+        }),
+        metallic_radius: Some(144.0),
+        oxidation_states: &[3, 1],
+        density: Some(19.3),
+        melting_point: Some(1337.33),
+    },
+    ElementData {
+        symbol: ElementSymbol::Hg,
+        name: "Mercury",
+        atomic_number: 80,
+        group: Some(12),
+        period: 6,
+        block: Block::D,
+        category: ElementCategory::TransitionMetal,
+        electronegativity: Some(2.0),
+        covalent_radius: Some(132.0),
+        ionic_radius: Some(IonicRadius {
+            charge: 2,
+            radius: 102.0,
+        }),
+        metallic_radius: Some(151.0),
+        oxidation_states: &[2, 1],
+        density: Some(13.534),
+        melting_point: Some(234.32),
+    },
+    ElementData {
+        symbol: ElementSymbol::Tl,
+        name: "Thallium",
+        atomic_number: 81,
+        group: Some(13),
+        period: 6,
+        block: Block::P,
+        category: ElementCategory::PostTransitionMetal,
+        electronegativity: Some(1.62),
+        covalent_radius: Some(145.0),
+        ionic_radius: Some(IonicRadius {
+            charge: 1,
+            radius: 150.0,
+        }),
+        metallic_radius: Some(170.0),
+        oxidation_states: &[1, 3],
+        density: Some(11.85),
+        melting_point: Some(577.0),
+    },
+    ElementData {
+        symbol: ElementSymbol::Pb,
+        name: "Lead",
+        atomic_number: 82,
+        group: Some(14),
+        period: 6,
+        block: Block::P,
+        category: ElementCategory::PostTransitionMetal,
+        electronegativity: Some(2.33),
+        covalent_radius: Some(146.0),
+        ionic_radius: Some(IonicRadius {
+            charge: 2,
+            radius: 119.0,
+        }),
+        metallic_radius: Some(175.0),
+        oxidation_states: &[2, 4],
+        density: Some(11.34),
+        melting_point: Some(600.61),
+    },
+    ElementData {
+        symbol: ElementSymbol::Bi,
+        name: "Bismuth",
+        atomic_number: 83,
+        group: Some(15),
+        period: 6,
+        block: Block::P,
+        category: ElementCategory::PostTransitionMetal,
+        electronegativity: Some(2.02),
+        covalent_radius: Some(148.0),
+        ionic_radius: Some(IonicRadius {
+            charge: 3,
+            radius: 103.0,
+        }),
+        metallic_radius: None,
+        oxidation_states: &[3, 5],
+        density: Some(9.78),
+        melting_point: Some(544.7),
+    },
+    ElementData {
+        symbol: ElementSymbol::Po,
+        name: "Polonium",
+        atomic_number: 84,
+        group: Some(16),
+        period: 6,
+        block: Block::P,
+        category: ElementCategory::PostTransitionMetal,
+        electronegativity: Some(2.0),
+        covalent_radius: Some(140.0),
+        ionic_radius: Some(IonicRadius {
+            charge: 4,
+            radius: 94.0,
+        }),
+        metallic_radius: None,
+        oxidation_states: &[4, 2],
+        density: Some(9.196),
+        melting_point: Some(527.0),
+    },
+    ElementData {
+        symbol: ElementSymbol::At,
+        name: "Astatine",
+        atomic_number: 85,
+        group: Some(17),
+        period: 6,
+        block: Block::P,
+        category: ElementCategory::Halogen,
+        electronegativity: Some(2.2),
+        covalent_radius: Some(150.0),
+        ionic_radius: None,
+        metallic_radius: None,
+        oxidation_states: &[-1, 1],
+        density: None,
+        melting_point: None,
+    },
+    ElementData {
+        symbol: ElementSymbol::Rn,
+        name: "Radon",
+        atomic_number: 86,
+        group: Some(18),
+        period: 6,
+        block: Block::P,
+        category: ElementCategory::NobleGas,
+        electronegativity: None,
+        covalent_radius: Some(150.0),
+        ionic_radius: None,
+        metallic_radius: None,
+        oxidation_states: &[2],
+        density: Some(0.00973),
+        melting_point: Some(202.0),
+    },
+    ElementData {
+        symbol: ElementSymbol::Fr,
+        name: "Francium",
+        atomic_number: 87,
+        group: Some(1),
+        period: 7,
+        block: Block::S,
+        category: ElementCategory::AlkaliMetal,
+        electronegativity: Some(0.7),
+        covalent_radius: Some(260.0),
+        ionic_radius: Some(IonicRadius {
+            charge: 1,
+            radius: 180.0,
+        }),
+        metallic_radius: None,
+        oxidation_states: &[1],
+        density: None,
+        melting_point: None,
+    },
+    ElementData {
+        symbol: ElementSymbol::Ra,
+        name: "Radium",
+        atomic_number: 88,
+        group: Some(2),
+        period: 7,
+        block: Block::S,
+        category: ElementCategory::AlkalineEarthMetal,
+        electronegativity: Some(0.9),
+        covalent_radius: Some(221.0),
+        ionic_radius: None,
+        metallic_radius: None,
+        oxidation_states: &[2],
+        density: Some(5.5),
+        melting_point: Some(973.0),
+    },
+    ElementData {
+        symbol: ElementSymbol::Ac,
+        name: "Actinium",
+        atomic_number: 89,
+        group: Some(3),
+        period: 7,
+        block: Block::D,
+        category: ElementCategory::Actinide,
+        electronegativity: Some(1.1),
+        covalent_radius: Some(215.0),
+        ionic_radius: Some(IonicRadius {
+            charge: 3,
+            radius: 112.0,
+        }),
+        metallic_radius: Some(187.8),
+        oxidation_states: &[3],
+        density: Some(10.0),
+        melting_point: Some(1323.0),
+    },
+    ElementData {
+        symbol: ElementSymbol::Th,
+        name: "Thorium",
+        atomic_number: 90,
+        group: None,
+        period: 7,
+        block: Block::F,
+        category: ElementCategory::Actinide,
+        electronegativity: Some(1.3),
+        covalent_radius: Some(206.0),
+        ionic_radius: Some(IonicRadius {
+            charge: 4,
+            radius: 94.0,
+        }),
+        metallic_radius: Some(179.0),
+        oxidation_states: &[4],
+        density: Some(11.7),
+        melting_point: Some(2023.0),
+    },
+    ElementData {
+        symbol: ElementSymbol::Pa,
+        name: "Protactinium",
+        atomic_number: 91,
+        group: None,
+        period: 7,
+        block: Block::F,
+        category: ElementCategory::Actinide,
+        electronegativity: Some(1.5),
+        covalent_radius: Some(200.0),
+        ionic_radius: Some(IonicRadius {
+            charge: 5,
+            radius: 78.0,
+        }),
+        metallic_radius: Some(163.0),
+        oxidation_states: &[5, 4],
+        density: Some(15.37),
+        melting_point: Some(1841.0),
+    },
+    ElementData {
+        symbol: ElementSymbol::U,
+        name: "Uranium",
+        atomic_number: 92,
+        group: None,
+        period: 7,
+        block: Block::F,
+        category: ElementCategory::Actinide,
+        electronegativity: Some(1.38),
+        covalent_radius: Some(196.0),
+        ionic_radius: Some(IonicRadius {
+            charge: 6,
+            radius: 73.0,
+        }),
+        metallic_radius: Some(156.0),
+        oxidation_states: &[6, 4],
+        density: Some(19.1),
+        melting_point: Some(1405.3),
+    },
+    ElementData {
+        symbol: ElementSymbol::Np,
+        name: "Neptunium",
+        atomic_number: 93,
+        group: None,
+        period: 7,
+        block: Block::F,
+        category: ElementCategory::Actinide,
+        electronegativity: Some(1.36),
+        covalent_radius: Some(190.0),
+        ionic_radius: Some(IonicRadius {
+            charge: 5,
+            radius: 75.0,
+        }),
+        metallic_radius: Some(155.0),
+        oxidation_states: &[5, 4, 6],
+        density: Some(20.45),
+        melting_point: Some(912.0),
+    },
+    ElementData {
+        symbol: ElementSymbol::Pu,
+        name: "Plutonium",
+        atomic_number: 94,
+        group: None,
+        period: 7,
+        block: Block::F,
+        category: ElementCategory::Actinide,
+        electronegativity: Some(1.28),
+        covalent_radius: Some(187.0),
+        ionic_radius: Some(IonicRadius {
+            charge: 4,
+            radius: 86.0,
+        }),
+        metallic_radius: Some(159.0),
+        oxidation_states: &[4, 3, 6],
+        density: Some(19.816),
+        melting_point: Some(912.5),
+    },
+    ElementData {
+        symbol: ElementSymbol::Am,
+        name: "Americium",
+        atomic_number: 95,
+        group: None,
+        period: 7,
+        block: Block::F,
+        category: ElementCategory::Actinide,
+        electronegativity: Some(1.13),
+        covalent_radius: Some(180.0),
+        ionic_radius: Some(IonicRadius {
+            charge: 3,
+            radius: 97.5,
+        }),
+        metallic_radius: Some(173.0),
+        oxidation_states: &[3],
+        density: Some(12.0),
+        melting_point: Some(1449.0),
+    },
+    ElementData {
+        symbol: ElementSymbol::Cm,
+        name: "Curium",
+        atomic_number: 96,
+        group: None,
+        period: 7,
+        block: Block::F,
+        category: ElementCategory::Actinide,
+        electronegativity: Some(1.28),
+        covalent_radius: Some(169.0),
+        ionic_radius: Some(IonicRadius {
+            charge: 3,
+            radius: 97.0,
+        }),
+        metallic_radius: Some(174.0),
+        oxidation_states: &[3],
+        density: Some(13.51),
+        melting_point: Some(1613.0),
+    },
+    ElementData {
+        symbol: ElementSymbol::Bk,
+        name: "Berkelium",
+        atomic_number: 97,
+        group: None,
+        period: 7,
+        block: Block::F,
+        category: ElementCategory::Actinide,
+        electronegativity: Some(1.3),
+        covalent_radius: None,
+        ionic_radius: None,
+        metallic_radius: None,
+        oxidation_states: &[3, 4],
+        density: Some(14.78),
+        melting_point: Some(1259.0),
+    },
+    ElementData {
+        symbol: ElementSymbol::Cf,
+        name: "Californium",
+        atomic_number: 98,
+        group: None,
+        period: 7,
+        block: Block::F,
+        category: ElementCategory::Actinide,
+        electronegativity: Some(1.3),
+        covalent_radius: None,
+        ionic_radius: None,
+        metallic_radius: None,
+        oxidation_states: &[3],
+        density: Some(15.1),
+        melting_point: Some(1173.0),
+    },
+    ElementData {
+        symbol: ElementSymbol::Es,
+        name: "Einsteinium",
+        atomic_number: 99,
+        group: None,
+        period: 7,
+        block: Block::F,
+        category: ElementCategory::Actinide,
+        electronegativity: Some(1.3),
+        covalent_radius: None,
+        ionic_radius: None,
+        metallic_radius: None,
+        oxidation_states: &[3],
+        density: None,
+        melting_point: Some(1133.0),
+    },
+    ElementData {
+        symbol: ElementSymbol::Fm,
+        name: "Fermium",
+        atomic_number: 100,
+        group: None,
+        period: 7,
+        block: Block::F,
+        category: ElementCategory::Actinide,
+        electronegativity: Some(1.3),
+        covalent_radius: None,
+        ionic_radius: None,
+        metallic_radius: None,
+        oxidation_states: &[3],
+        density: None,
+        melting_point: None,
+    },
+    ElementData {
+        symbol: ElementSymbol::Md,
+        name: "Mendelevium",
+        atomic_number: 101,
+        group: None,
+        period: 7,
+        block: Block::F,
+        category: ElementCategory::Actinide,
+        electronegativity: Some(1.3),
+        covalent_radius: None,
+        ionic_radius: None,
+        metallic_radius: None,
+        oxidation_states: &[3],
+        density: None,
+        melting_point: None,
+    },
+    ElementData {
+        symbol: ElementSymbol::No,
+        name: "Nobelium",
+        atomic_number: 102,
+        group: None,
+        period: 7,
+        block: Block::F,
+        category: ElementCategory::Actinide,
+        electronegativity: Some(1.3),
+        covalent_radius: None,
+        ionic_radius: None,
+        metallic_radius: None,
+        oxidation_states: &[2, 3],
+        density: None,
+        melting_point: None,
+    },
+    ElementData {
+        symbol: ElementSymbol::Lr,
+        name: "Lawrencium",
+        atomic_number: 103,
+        group: None,
+        period: 7,
+        block: Block::F,
+        category: ElementCategory::Actinide,
+        electronegativity: None,
+        covalent_radius: None,
+        ionic_radius: None,
+        metallic_radius: None,
+        oxidation_states: &[3],
+        density: None,
+        melting_point: None,
+    },
+    ElementData {
+        symbol: ElementSymbol::Rf,
+        name: "Rutherfordium",
+        atomic_number: 104,
+        group: Some(4),
+        period: 7,
+        block: Block::D,
+        category: ElementCategory::TransitionMetal,
+        electronegativity: None,
+        covalent_radius: None,
+        ionic_radius: None,
+        metallic_radius: None,
+        oxidation_states: &[4],
+        density: None,
+        melting_point: None,
+    },
+    ElementData {
+        symbol: ElementSymbol::Db,
+        name: "Dubnium",
+        atomic_number: 105,
+        group: Some(5),
+        period: 7,
+        block: Block::D,
+        category: ElementCategory::TransitionMetal,
+        electronegativity: None,
+        covalent_radius: None,
+        ionic_radius: None,
+        metallic_radius: None,
+        oxidation_states: &[5],
+        density: None,
+        melting_point: None,
+    },
+    ElementData {
+        symbol: ElementSymbol::Sg,
+        name: "Seaborgium",
+        atomic_number: 106,
+        group: Some(6),
+        period: 7,
+        block: Block::D,
+        category: ElementCategory::TransitionMetal,
+        electronegativity: None,
+        covalent_radius: None,
+        ionic_radius: None,
+        metallic_radius: None,
+        oxidation_states: &[6],
+        density: None,
+        melting_point: None,
+    },
+    ElementData {
+        symbol: ElementSymbol::Bh,
+        name: "Bohrium",
+        atomic_number: 107,
+        group: Some(7),
+        period: 7,
+        block: Block::D,
+        category: ElementCategory::TransitionMetal,
+        electronegativity: None,
+        covalent_radius: None,
+        ionic_radius: None,
+        metallic_radius: None,
+        oxidation_states: &[7],
+        density: None,
+        melting_point: None,
+    },
+    ElementData {
+        symbol: ElementSymbol::Hs,
+        name: "Hassium",
+        atomic_number: 108,
+        group: Some(8),
+        period: 7,
+        block: Block::D,
+        category: ElementCategory::TransitionMetal,
+        electronegativity: None,
+        covalent_radius: None,
+        ionic_radius: None,
+        metallic_radius: None,
+        oxidation_states: &[8],
+        density: None,
+        melting_point: None,
+    },
+    ElementData {
+        symbol: ElementSymbol::Mt,
+        name: "Meitnerium",
+        atomic_number: 109,
+        group: Some(9),
+        period: 7,
+        block: Block::D,
+        category: ElementCategory::Unknown,
+        electronegativity: None,
+        covalent_radius: None,
+        ionic_radius: None,
+        metallic_radius: None,
+        oxidation_states: &[],
+        density: None,
+        melting_point: None,
+    },
+    ElementData {
+        symbol: ElementSymbol::Ds,
+        name: "Darmstadtium",
+        atomic_number: 110,
+        group: Some(10),
+        period: 7,
+        block: Block::D,
+        category: ElementCategory::Unknown,
+        electronegativity: None,
+        covalent_radius: None,
+        ionic_radius: None,
+        metallic_radius: None,
+        oxidation_states: &[],
+        density: None,
+        melting_point: None,
+    },
+    ElementData {
+        symbol: ElementSymbol::Rg,
+        name: "Roentgenium",
+        atomic_number: 111,
+        group: Some(11),
+        period: 7,
+        block: Block::D,
+        category: ElementCategory::Unknown,
+        electronegativity: None,
+        covalent_radius: None,
+        ionic_radius: None,
+        metallic_radius: None,
+        oxidation_states: &[],
+        density: None,
+        melting_point: None,
+    },
+    ElementData {
+        symbol: ElementSymbol::Cn,
+        name: "Copernicium",
+        atomic_number: 112,
+        group: Some(12),
+        period: 7,
+        block: Block::D,
+        category: ElementCategory::Unknown,
+        electronegativity: None,
+        covalent_radius: None,
+        ionic_radius: None,
+        metallic_radius: None,
+        oxidation_states: &[],
+        density: None,
+        melting_point: None,
+    },
+    ElementData {
+        symbol: ElementSymbol::Nh,
+        name: "Nihonium",
+        atomic_number: 113,
+        group: Some(13),
+        period: 7,
+        block: Block::P,
+        category: ElementCategory::Unknown,
+        electronegativity: None,
+        covalent_radius: None,
+        ionic_radius: None,
+        metallic_radius: None,
+        oxidation_states: &[],
+        density: None,
+        melting_point: None,
+    },
+    ElementData {
+        symbol: ElementSymbol::Fl,
+        name: "Flerovium",
+        atomic_number: 114,
+        group: Some(14),
+        period: 7,
+        block: Block::P,
+        category: ElementCategory::Unknown,
+        electronegativity: None,
+        covalent_radius: None,
+        ionic_radius: None,
+        metallic_radius: None,
+        oxidation_states: &[],
+        density: None,
+        melting_point: None,
+    },
+    ElementData {
+        symbol: ElementSymbol::Mc,
+        name: "Moscovium",
+        atomic_number: 115,
+        group: Some(15),
+        period: 7,
+        block: Block::P,
+        category: ElementCategory::Unknown,
+        electronegativity: None,
+        covalent_radius: None,
+        ionic_radius: None,
+        metallic_radius: None,
+        oxidation_states: &[],
+        density: None,
+        melting_point: None,
+    },
+    ElementData {
+        symbol: ElementSymbol::Lv,
+        name: "Livermorium",
+        atomic_number: 116,
+        group: Some(16),
+        period: 7,
+        block: Block::P,
+        category: ElementCategory::Unknown,
+        electronegativity: None,
+        covalent_radius: None,
+        ionic_radius: None,
+        metallic_radius: None,
+        oxidation_states: &[],
+        density: None,
+        melting_point: None,
+    },
+    ElementData {
+        symbol: ElementSymbol::Ts,
+        name: "Tennessine",
+        atomic_number: 117,
+        group: Some(17),
+        period: 7,
+        block: Block::P,
+        category: ElementCategory::Unknown,
+        electronegativity: None,
+        covalent_radius: None,
+        ionic_radius: None,
+        metallic_radius: None,
+        oxidation_states: &[],
+        density: None,
+        melting_point: None,
+    },
+    ElementData {
+        symbol: ElementSymbol::Og,
+        name: "Oganesson",
+        atomic_number: 118,
+        group: Some(18),
+        period: 7,
+        block: Block::P,
+        category: ElementCategory::Unknown,
+        electronegativity: None,
+        covalent_radius: None,
+        ionic_radius: None,
+        metallic_radius: None,
+        oxidation_states: &[],
+        density: None,
+        melting_point: None,
+    },
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::element::FormulaError;
+
+    #[test]
+    fn test_element_data() {
+        for (i, data) in ELEMENTS.iter().enumerate() {
+            assert_eq!(data.atomic_number as usize, i + 1);
+            assert_eq!(data.symbol as usize, i + 1);
+            assert_eq!(data.symbol.data(), Some(data));
+            assert_eq!(
+                data.symbol.to_string().parse::<ElementSymbol>(),
+                Ok(data.symbol)
+            );
+
+            // The block follows from the group
+            match data.group {
+                Some(1 | 2) => assert_eq!(data.block, Block::S),
+                Some(3..=12) => assert_eq!(data.block, Block::D),
+                Some(18) if data.symbol == ElementSymbol::He => assert_eq!(data.block, Block::S),
+                Some(_) => assert_eq!(data.block, Block::P),
+                None => assert_eq!(data.block, Block::F),
+            }
+        }
+
+        let periods: Vec<usize> = (1..=7)
+            .map(|period| ELEMENTS.iter().filter(|data| data.period == period).count())
+            .collect();
+        assert_eq!(periods, vec![2, 8, 8, 18, 18, 32, 32]);
+
+        let groups: Vec<usize> = (1..=18)
+            .map(|group| {
+                ELEMENTS
+                    .iter()
+                    .filter(|data| data.group == Some(group))
+                    .count()
+            })
+            .collect();
+        assert_eq!(
+            groups,
+            vec![7, 6, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 6, 6, 6, 6, 6, 7]
+        );
+    }
+
+    #[test]
+    fn test_element_iteration() {
+        for (i, element) in ElementSymbol::iter().enumerate() {
+            assert_eq!(element.atomic_number() as usize, i + 1);
+            assert_eq!(
+                ElementSymbol::try_from(element.atomic_number()).unwrap(),
+                element
+            );
+        }
+        assert!(ElementSymbol::iter()
+            .collect::<Vec<_>>()
+            .windows(2)
+            .all(|pair| pair[0] < pair[1]));
+
+        assert_eq!(ElementSymbol::None.atomic_number(), 0);
+        assert!(matches!(
+            ElementSymbol::try_from(0),
+            Err(FormulaError::InvalidAtomicNumber(0))
+        ));
+        assert!(matches!(
+            ElementSymbol::try_from(255),
+            Err(FormulaError::InvalidAtomicNumber(255))
+        ));
+
+        let first_row: Vec<_> = ElementSymbol::in_period(4)
+            .filter(|element| element.block() == Some(Block::D))
+            .collect();
+        assert_eq!(
+            first_row,
+            ElementSymbol::range(ElementSymbol::Sc..=ElementSymbol::Zn).collect::<Vec<_>>()
+        );
+        assert_eq!(ElementSymbol::in_block(Block::F).count(), 28);
+        assert_eq!(
+            ElementSymbol::in_category(ElementCategory::Actinide).count(),
+            15
+        );
+        assert_eq!(ElementSymbol::range(..ElementSymbol::He).count(), 1);
+        assert_eq!(ElementSymbol::in_period(8).count(), 0);
+    }
+
+    #[test]
+    fn test_element_properties() {
+        assert_eq!(ElementSymbol::O.name(), Some("Oxygen"));
+        assert_eq!(ElementSymbol::Al.name(), Some("Aluminium"));
+        assert_eq!(ElementSymbol::Ce.group(), None);
+        assert_eq!(ElementSymbol::La.group(), Some(3));
+        assert_eq!(ElementSymbol::Ce.block(), Some(Block::F));
+        assert_eq!(ElementSymbol::Br.category(), Some(ElementCategory::Halogen));
+        assert_eq!(
+            ElementSymbol::Si.category(),
+            Some(ElementCategory::Metalloid)
+        );
+        assert_eq!(ElementSymbol::Og.category(), Some(ElementCategory::Unknown));
+        assert_eq!(ElementSymbol::F.electronegativity(), Some(3.98));
+        assert_eq!(ElementSymbol::Ne.electronegativity(), None);
+        assert_eq!(
+            ElementSymbol::Fe.ionic_radius(),
+            Some(IonicRadius {
+                charge: 3,
+                radius: 64.5
+            })
+        );
+        assert_eq!(ElementSymbol::W.melting_point(), Some(3695.0));
+        assert_eq!(ElementSymbol::Os.density(), Some(22.59));
+        assert!(ElementSymbol::Ar.oxidation_states().is_empty());
+        assert!(ElementSymbol::None.oxidation_states().is_empty());
+        assert_eq!(ElementSymbol::None.name(), None);
+    }
+}
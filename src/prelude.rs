@@ -8,5 +8,19 @@
 //! ```
 //! This will import all the necessary types and functions for the crate.
 //! You can import them individually as well.
-pub use crate::element::{ChemicalFormula, ElementSymbol, FormulaError};
-pub use crate::parser::parse_formula;
+pub use crate::catalyst::{
+    ImpregnationRecipe, IncipientWetnessPlan, Precursor, SolutionConcentration,
+};
+pub use crate::element::{ChemicalFormula, ElementSymbol, FormulaError, UnknownElement};
+pub use crate::empirical::{EmpiricalFormula, EmpiricalFormulaOptions};
+pub use crate::isotope::{Isotope, IsotopePatternOptions, IsotopePeak};
+pub use crate::mineral::FerricIronEstimate;
+pub use crate::oxidation::{OxidationStateAssignment, OxidationStateTable};
+pub use crate::oxide::{OxideBasis, OxideComposition, OxideWtPercent};
+pub use crate::parser::{parse_formula, parse_formula_tree, parse_symbolic_formula, ParseError};
+pub use crate::periodic::{Block, ElementCategory, ElementData, IonicRadius};
+pub use crate::reaction::{parse_reaction, Quantity, Reaction, ReactionYield, Species};
+pub use crate::series::{CompositionSeries, SeriesMember};
+pub use crate::symbolic::SymbolicFormula;
+pub use crate::tree::{FormulaNode, FormulaTree};
+pub use crate::writer::{ElementOrder, FormulaWriter};
@@ -0,0 +1,814 @@
+//! # Reactions
+//!
+//! This module contains the `Reaction` type, parsed from a chemical equation such as `H2PtCl6 + H2 -> Pt + HCl`.
+//!
+//! The species are separated by ` + ` and the two sides by `->`, `=>`, `=`, `<->`, `<=>`, `→` or `⇌`.
+//! A leading number is the coefficient of the species, e.g. `2H2O` or `2 H2O`,
+//! as in `parse_formula`, where an isotope is written with `^`, in brackets or as `D`/`T`, e.g. `^13CO2`.
+//!
+//! `Reaction::balance` finds the smallest integer coefficients that conserve every element and the charge.
+//! It returns `FormulaError::Unbalanceable` if no such coefficients exist,
+//! and `FormulaError::MultipleBalances` if the reaction is a combination of independent reactions.
+//!
+//! `Reaction::calculate_yield` takes the mass or moles of each reactant and gives the limiting reagent,
+//! the theoretical yield of each product and the excess of each reactant as a `ReactionYield`.
+//!
+//! # Example
+//! ```
+//! use chemical_formula::prelude::*;
+//!
+//! let mut reaction = parse_reaction("H2PtCl6 + H2 -> Pt + HCl").unwrap();
+//! reaction.balance().unwrap();
+//!
+//! assert_eq!(reaction.to_string(), "H2PtCl6 + 2H2 -> Pt + 6HCl");
+//! assert!(reaction.is_balanced());
+//! ```
+
+use crate::element::{ChemicalFormula, ElementSymbol, FormulaError};
+use crate::parser::{parse_formula, Expected, ParseError, ParseErrorKind};
+use std::collections::BTreeSet;
+use std::fmt;
+use std::ops::Range;
+use std::str::FromStr;
+
+const ARROWS: [&str; 7] = ["<=>", "<->", "->", "=>", "→", "⇌", "="];
+
+/// Species of a reaction with its coefficient
+#[derive(Debug, Clone)]
+pub struct Species {
+    pub coefficient: f64,
+    pub formula: ChemicalFormula,
+    /// The formula as it was written, without the coefficient
+    pub notation: String,
+}
+
+impl Species {
+    /// Create a species with a coefficient of 1
+    pub fn new(formula: ChemicalFormula) -> Self {
+        Species {
+            coefficient: 1.,
+            notation: formula.to_string(),
+            formula,
+        }
+    }
+}
+
+impl fmt::Display for Species {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.coefficient == 1. {
+            write!(f, "{}", self.notation)
+        } else {
+            write!(f, "{}{}", self.coefficient, self.notation)
+        }
+    }
+}
+
+/// Chemical reaction, returned by `parse_reaction`
+#[derive(Debug, Clone, Default)]
+pub struct Reaction {
+    pub reactants: Vec<Species>,
+    pub products: Vec<Species>,
+}
+
+impl Reaction {
+    pub fn new(reactants: Vec<Species>, products: Vec<Species>) -> Self {
+        Reaction {
+            reactants,
+            products,
+        }
+    }
+
+    /// Elements of all species in atomic number order
+    pub fn elements(&self) -> BTreeSet<ElementSymbol> {
+        self.species()
+            .flat_map(|(species, _)| species.formula.stoichiometry.keys().copied())
+            .collect()
+    }
+
+    /// Iterate over the reactants and products, with the sign of the side, +1 for reactants and -1 for products
+    fn species(&self) -> impl Iterator<Item = (&Species, f64)> + '_ {
+        self.reactants
+            .iter()
+            .map(|species| (species, 1.))
+            .chain(self.products.iter().map(|species| (species, -1.)))
+    }
+
+    /// Rows of the conservation matrix, one per element and one for the charge if any species is charged
+    fn conservation_matrix(&self) -> Result<Vec<Vec<f64>>, FormulaError> {
+        let formulas = self
+            .species()
+            .map(|(species, side)| Ok((species.formula.to_molecular_formula()?, side)))
+            .collect::<Result<Vec<_>, FormulaError>>()?;
+
+        let mut matrix: Vec<Vec<f64>> = self
+            .elements()
+            .iter()
+            .map(|element| {
+                formulas
+                    .iter()
+                    .map(|(formula, side)| {
+                        side * formula.stoichiometry.get(element).copied().unwrap_or(0.)
+                    })
+                    .collect()
+            })
+            .collect();
+
+        if formulas.iter().any(|(formula, _)| formula.charge != 0) {
+            matrix.push(
+                formulas
+                    .iter()
+                    .map(|(formula, side)| side * formula.charge as f64)
+                    .collect(),
+            );
+        }
+
+        Ok(matrix)
+    }
+
+    /// Whether every element and the charge are conserved with the current coefficients
+    pub fn is_balanced(&self) -> bool {
+        let coefficients: Vec<f64> = self
+            .species()
+            .map(|(species, _)| species.coefficient)
+            .collect();
+
+        match self.conservation_matrix() {
+            Ok(matrix) => matrix.iter().all(|row| {
+                let scale = row
+                    .iter()
+                    .zip(coefficients.iter())
+                    .map(|(x, c)| (x * c).abs())
+                    .sum::<f64>();
+                let sum = row
+                    .iter()
+                    .zip(coefficients.iter())
+                    .map(|(x, c)| x * c)
+                    .sum::<f64>();
+                sum.abs() <= 1e-9 * scale.max(1.)
+            }),
+            Err(_) => false,
+        }
+    }
+
+    /// Balance the reaction with the smallest positive integer coefficients
+    ///
+    /// Returns `FormulaError::Unbalanceable` if no positive coefficients conserve every element and the charge,
+    /// and `FormulaError::MultipleBalances` with the number of independent solutions if they are not unique up to a factor.
+    /// `FormulaError::CoefficientOverflow` is returned if the exact fractions of many decimal stoichiometries get too large.
+    ///
+    /// # Example
+    /// ```
+    /// use chemical_formula::prelude::*;
+    ///
+    /// let mut reaction = parse_reaction("Fe2O3 + CO = Fe + CO2").unwrap();
+    /// reaction.balance().unwrap();
+    /// assert_eq!(reaction.to_string(), "Fe2O3 + 3CO -> 2Fe + 3CO2");
+    ///
+    /// let mut reaction = parse_reaction("H2 + O2 -> H2O + H2O2").unwrap();
+    /// assert!(matches!(reaction.balance(), Err(FormulaError::MultipleBalances(2))));
+    ///
+    /// let mut reaction = parse_reaction("H2 + O2 -> CH4").unwrap();
+    /// assert!(matches!(reaction.balance(), Err(FormulaError::Unbalanceable)));
+    /// ```
+    pub fn balance(&mut self) -> Result<&mut Self, FormulaError> {
+        let matrix = self
+            .conservation_matrix()?
+            .into_iter()
+            .map(|row| row.into_iter().map(Ratio::from_f64).collect())
+            .collect::<Option<Vec<Vec<Ratio>>>>()
+            .ok_or(FormulaError::Unbalanceable)?;
+        let species_count = self.reactants.len() + self.products.len();
+
+        let basis = null_space(matrix, species_count).ok_or(FormulaError::CoefficientOverflow)?;
+        let coefficients = match basis {
+            mut basis if basis.len() == 1 => basis.remove(0),
+            basis if basis.is_empty() => return Err(FormulaError::Unbalanceable),
+            basis => return Err(FormulaError::MultipleBalances(basis.len())),
+        };
+
+        let sign = if coefficients[0].num < 0 { -1 } else { 1 };
+        if coefficients.iter().any(|c| c.num * sign <= 0) {
+            return Err(FormulaError::Unbalanceable);
+        }
+
+        let denominator = coefficients
+            .iter()
+            .try_fold(1, |acc, c| lcm(acc, c.den))
+            .ok_or(FormulaError::CoefficientOverflow)?;
+        let integers: Vec<i128> = coefficients
+            .iter()
+            .map(|c| (sign * c.num).checked_mul(denominator / c.den))
+            .collect::<Option<_>>()
+            .ok_or(FormulaError::CoefficientOverflow)?;
+        let divisor = integers.iter().fold(0, |acc, x| gcd(acc, *x));
+
+        for (species, coefficient) in self
+            .reactants
+            .iter_mut()
+            .chain(self.products.iter_mut())
+            .zip(integers)
+        {
+            species.coefficient = (coefficient / divisor) as f64;
+        }
+
+        Ok(self)
+    }
+
+    /// Limiting reagent, theoretical yield and excess for the given amount of each reactant
+    ///
+    /// `quantities` are given in the order of `reactants`. The coefficients are used as they are,
+    /// so the reaction has to be balanced first.
+    ///
+    /// Returns `FormulaError::UnbalancedReaction` if the reaction is not balanced,
+    /// `FormulaError::QuantityCount` if the number of quantities does not match the reactants,
+    /// and `FormulaError::NoLimitingReagent` if all reactants are in excess.
+    ///
+    /// # Example
+    /// ```
+    /// use chemical_formula::prelude::*;
+    /// use approx::assert_abs_diff_eq;
+    ///
+    /// let reaction = parse_reaction("2H2 + O2 -> 2H2O").unwrap();
+    /// let result = reaction
+    ///     .calculate_yield(&[Quantity::Mass(4.0), Quantity::Mass(64.0)])
+    ///     .unwrap();
+    ///
+    /// // 4 g of H2 is 1.98 mol, which needs 0.99 mol of the 2 mol O2
+    /// assert_eq!(result.limiting, 0);
+    /// assert_abs_diff_eq!(result.products[0].mass, 4.0 / 2.016 * 18.015, epsilon = 1e-9);
+    /// assert_abs_diff_eq!(result.reactants[1].excess_moles.unwrap(), 2.0 - 1.0 / 1.008, epsilon = 1e-3);
+    /// assert_abs_diff_eq!(result.percent_yield(0, 30.0).unwrap(), 30.0 / (4.0 / 2.016 * 18.015) * 100., epsilon = 1e-9);
+    /// ```
+    pub fn calculate_yield(&self, quantities: &[Quantity]) -> Result<ReactionYield, FormulaError> {
+        if !self.is_balanced() {
+            return Err(FormulaError::UnbalancedReaction);
+        }
+
+        if quantities.len() != self.reactants.len() {
+            return Err(FormulaError::QuantityCount {
+                expected: self.reactants.len(),
+                found: quantities.len(),
+            });
+        }
+
+        let reactant_weights = self
+            .reactants
+            .iter()
+            .map(|species| species.formula.molecular_weight())
+            .collect::<Result<Vec<_>, FormulaError>>()?;
+
+        let moles: Vec<Option<f64>> = quantities
+            .iter()
+            .zip(reactant_weights.iter())
+            .map(|(quantity, molecular_weight)| match quantity {
+                Quantity::Mass(mass) => Some(mass / molecular_weight),
+                Quantity::Moles(moles) => Some(*moles),
+                Quantity::Excess => None,
+            })
+            .collect();
+
+        // Extent of reaction in mol, limited by the reactant that runs out first
+        let (limiting, extent) = moles
+            .iter()
+            .zip(self.reactants.iter())
+            .enumerate()
+            .filter_map(|(i, (moles, species))| moles.map(|moles| (i, moles / species.coefficient)))
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .ok_or(FormulaError::NoLimitingReagent)?;
+
+        let reactants = self
+            .reactants
+            .iter()
+            .zip(moles.iter())
+            .zip(reactant_weights.iter())
+            .map(|((species, moles), molecular_weight)| {
+                let consumed_moles = extent * species.coefficient;
+                let excess_moles = moles.map(|moles| moles - consumed_moles);
+
+                ReactantYield {
+                    moles: *moles,
+                    consumed_moles,
+                    consumed_mass: consumed_moles * molecular_weight,
+                    excess_moles,
+                    excess_mass: excess_moles.map(|moles| moles * molecular_weight),
+                }
+            })
+            .collect();
+
+        let products = self
+            .products
+            .iter()
+            .map(|species| {
+                let moles = extent * species.coefficient;
+                Ok(ProductYield {
+                    moles,
+                    mass: moles * species.formula.molecular_weight()?,
+                })
+            })
+            .collect::<Result<Vec<_>, FormulaError>>()?;
+
+        Ok(ReactionYield {
+            extent,
+            limiting,
+            reactants,
+            products,
+        })
+    }
+}
+
+/// Amount of a reactant given to `Reaction::calculate_yield`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Quantity {
+    /// Mass in g
+    Mass(f64),
+    /// Amount of substance in mol
+    Moles(f64),
+    /// The reactant is in excess and never limits the reaction, e.g. H2 gas in a reduction
+    Excess,
+}
+
+/// Amounts of a reactant calculated by `Reaction::calculate_yield`
+///
+/// `moles`, `excess_moles` and `excess_mass` are `None` for a reactant given as `Quantity::Excess`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReactantYield {
+    pub moles: Option<f64>,
+    pub consumed_moles: f64,
+    pub consumed_mass: f64,
+    pub excess_moles: Option<f64>,
+    pub excess_mass: Option<f64>,
+}
+
+/// Theoretical yield of a product calculated by `Reaction::calculate_yield`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProductYield {
+    pub moles: f64,
+    /// Mass in g
+    pub mass: f64,
+}
+
+/// Result of `Reaction::calculate_yield`
+///
+/// `reactants` and `products` are in the order of the reaction.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReactionYield {
+    /// Extent of reaction in mol
+    pub extent: f64,
+    /// Index of the limiting reagent in `reactants`
+    pub limiting: usize,
+    pub reactants: Vec<ReactantYield>,
+    pub products: Vec<ProductYield>,
+}
+
+impl ReactionYield {
+    /// Percent yield of a product from the actual mass in g
+    ///
+    /// `product` is the index in `products`. Returns `None` if there is no such product.
+    pub fn percent_yield(&self, product: usize, actual_mass: f64) -> Option<f64> {
+        self.products
+            .get(product)
+            .map(|product| actual_mass / product.mass * 100.)
+    }
+}
+
+impl fmt::Display for Reaction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let join = |species: &[Species]| {
+            species
+                .iter()
+                .map(|species| species.to_string())
+                .collect::<Vec<_>>()
+                .join(" + ")
+        };
+
+        write!(f, "{} -> {}", join(&self.reactants), join(&self.products))
+    }
+}
+
+impl FromStr for Reaction {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_reaction(s)
+    }
+}
+
+/// Parse a chemical equation such as `H2PtCl6 + H2 -> Pt + HCl` or `2H2 + O2 = 2H2O`
+///
+/// The spans of the errors are given in the whole equation.
+///
+/// # Example
+/// ```
+/// use chemical_formula::prelude::*;
+///
+/// let reaction = parse_reaction("2H2 + O2 -> 2H2O").unwrap();
+/// assert_eq!(reaction.reactants[0].coefficient, 2.0);
+/// assert!(reaction.is_balanced());
+///
+/// let err = parse_reaction("H2 + O2 -> H2Q").unwrap_err();
+/// assert_eq!(err.span, 13..14);
+/// ```
+pub fn parse_reaction(s: &str) -> Result<Reaction, ParseError> {
+    let (arrow_start, arrow) = ARROWS
+        .iter()
+        .find_map(|arrow| s.find(arrow).map(|start| (start, *arrow)))
+        .ok_or_else(|| {
+            ParseError::new(
+                ParseErrorKind::UnexpectedToken {
+                    found: None,
+                    expected: vec![Expected::ReactionArrow],
+                },
+                s.len()..s.len(),
+                s,
+            )
+        })?;
+    let products_start = arrow_start + arrow.len();
+
+    Ok(Reaction {
+        reactants: parse_side(s, 0..arrow_start)?,
+        products: parse_side(s, products_start..s.len())?,
+    })
+}
+
+/// Parse the species separated by ` + ` in `input[range]`
+fn parse_side(input: &str, range: Range<usize>) -> Result<Vec<Species>, ParseError> {
+    let mut start = range.start;
+    let mut species = Vec::new();
+
+    for part in input[range].split(" + ") {
+        let lead = part.len() - part.trim_start().len();
+        let span = start + lead..start + lead + part.trim().len();
+        species.push(parse_species(input, span)?);
+        start += part.len() + " + ".len();
+    }
+
+    Ok(species)
+}
+
+/// Parse a species with an optional leading coefficient in `input[span]`
+fn parse_species(input: &str, span: Range<usize>) -> Result<Species, ParseError> {
+    let text = &input[span.clone()];
+    let number_len = text
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(text.len());
+
+    let coefficient = match number_len {
+        0 => 1.,
+        _ => text[..number_len].parse().map_err(|_| {
+            ParseError::new(
+                ParseErrorKind::InvalidNumber(text[..number_len].to_string()),
+                span.start..span.start + number_len,
+                input,
+            )
+        })?,
+    };
+
+    let notation = text[number_len..].trim_start();
+    let offset = span.end - notation.len();
+
+    if notation.is_empty() {
+        return Err(ParseError::new(
+            ParseErrorKind::UnexpectedToken {
+                found: input[offset..].chars().next(),
+                expected: vec![Expected::ElementSymbol, Expected::OpeningBracket],
+            },
+            offset..offset + input[offset..].chars().next().map_or(0, char::len_utf8),
+            input,
+        ));
+    }
+
+    let formula = parse_formula(notation).map_err(|err| {
+        ParseError::new(
+            err.kind,
+            err.span.start + offset..err.span.end + offset,
+            input,
+        )
+    })?;
+
+    Ok(Species {
+        coefficient,
+        formula,
+        notation: notation.to_string(),
+    })
+}
+
+/// Exact rational number used to balance reactions
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Ratio {
+    num: i128,
+    den: i128,
+}
+
+impl Ratio {
+    const ZERO: Ratio = Ratio { num: 0, den: 1 };
+
+    fn new(num: i128, den: i128) -> Self {
+        let divisor = gcd(num, den).max(1) * den.signum();
+        Ratio {
+            num: num / divisor,
+            den: den / divisor,
+        }
+    }
+
+    /// Exact fraction of a decimal with up to 6 digits, e.g. 0.123457 is 123457/10^6,
+    /// otherwise the closest fraction with a denominator up to 10^6, e.g. 1/3 for a converted composition
+    fn from_f64(x: f64) -> Option<Self> {
+        if !x.is_finite() {
+            return None;
+        }
+
+        // A decimal as written in a formula is taken exactly, so that the sums of the stoichiometries are conserved
+        let scaled = x * 1e6;
+        if scaled.abs() < 1e15 && (scaled - scaled.round()).abs() <= 1e-6 {
+            return Some(Ratio::new(scaled.round() as i128, 1_000_000));
+        }
+
+        let (mut h0, mut h1, mut k0, mut k1) = (0i128, 1i128, 1i128, 0i128);
+        let mut r = x.abs();
+
+        loop {
+            let a = r.floor() as i128;
+            let (h2, k2) = (a * h1 + h0, a * k1 + k0);
+            if k2 > 1_000_000 {
+                break;
+            }
+            (h0, h1, k0, k1) = (h1, h2, k1, k2);
+
+            let fraction = r - r.floor();
+            if (x.abs() - h1 as f64 / k1 as f64).abs() <= 1e-9 * x.abs().max(1.) || fraction < 1e-12
+            {
+                break;
+            }
+            r = 1. / fraction;
+        }
+
+        Some(Ratio::new(x.signum() as i128 * h1, k1))
+    }
+
+    // The arithmetic returns `None` on overflow, and reduces by the gcd first to keep the numbers small
+
+    fn sub(self, other: Ratio) -> Option<Ratio> {
+        let divisor = gcd(self.den, other.den);
+        let num = self
+            .num
+            .checked_mul(other.den / divisor)?
+            .checked_sub(other.num.checked_mul(self.den / divisor)?)?;
+        let den = (self.den / divisor).checked_mul(other.den)?;
+        Some(Ratio::new(num, den))
+    }
+
+    fn mul(self, other: Ratio) -> Option<Ratio> {
+        let first = gcd(self.num, other.den).max(1);
+        let second = gcd(other.num, self.den).max(1);
+        let num = (self.num / first).checked_mul(other.num / second)?;
+        let den = (self.den / second).checked_mul(other.den / first)?;
+        Some(Ratio::new(num, den))
+    }
+
+    fn div(self, other: Ratio) -> Option<Ratio> {
+        self.mul(Ratio::new(other.den, other.num))
+    }
+}
+
+fn gcd(a: i128, b: i128) -> i128 {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+fn lcm(a: i128, b: i128) -> Option<i128> {
+    (a / gcd(a, b)).checked_mul(b)
+}
+
+/// Basis of the null space of the matrix by Gauss-Jordan elimination
+///
+/// Returns `None` if the fractions overflow.
+fn null_space(mut matrix: Vec<Vec<Ratio>>, columns: usize) -> Option<Vec<Vec<Ratio>>> {
+    let mut pivots = Vec::new();
+    let mut row = 0;
+
+    for column in 0..columns {
+        let pivot = match (row..matrix.len()).find(|&r| matrix[r][column] != Ratio::ZERO) {
+            Some(pivot) => pivot,
+            None => continue,
+        };
+        matrix.swap(row, pivot);
+
+        let value = matrix[row][column];
+        for x in matrix[row].iter_mut() {
+            *x = x.div(value)?;
+        }
+
+        let pivot_row = matrix[row].clone();
+        for (r, current) in matrix.iter_mut().enumerate() {
+            let factor = current[column];
+            if r != row && factor != Ratio::ZERO {
+                for (x, pivot) in current.iter_mut().zip(pivot_row.iter()) {
+                    *x = x.sub(factor.mul(*pivot)?)?;
+                }
+            }
+        }
+
+        pivots.push(column);
+        row += 1;
+    }
+
+    (0..columns)
+        .filter(|column| !pivots.contains(column))
+        .map(|free| {
+            let mut vector = vec![Ratio::ZERO; columns];
+            vector[free] = Ratio::new(1, 1);
+            for (r, pivot) in pivots.iter().enumerate() {
+                vector[*pivot] = Ratio::ZERO.sub(matrix[r][free])?;
+            }
+            Some(vector)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_abs_diff_eq;
+
+    #[test]
+    fn test_balance() {
+        for (reaction_str, expected) in [
+            ("H2PtCl6 + H2 -> Pt + HCl", "H2PtCl6 + 2H2 -> Pt + 6HCl"),
+            ("H2 + O2 -> H2O", "2H2 + O2 -> 2H2O"),
+            ("C3H8 + O2 -> CO2 + H2O", "C3H8 + 5O2 -> 3CO2 + 4H2O"),
+            (
+                "KMnO4 + HCl -> KCl + MnCl2 + H2O + Cl2",
+                "2KMnO4 + 16HCl -> 2KCl + 2MnCl2 + 8H2O + 5Cl2",
+            ),
+            (
+                "Cu(NO3)2·3H2O -> CuO + NO2 + O2 + H2O",
+                "2Cu(NO3)2·3H2O -> 2CuO + 4NO2 + O2 + 6H2O",
+            ),
+            ("Fe0.95O + O2 -> Fe2O3", "80Fe0.95O + 17O2 -> 38Fe2O3"),
+            (
+                "CaSO4.0.5H2O -> CaSO4 + H2O",
+                "2CaSO4.0.5H2O -> 2CaSO4 + H2O",
+            ),
+            (
+                "MnO4- + Fe^2+ + H+ -> Mn^2+ + Fe^3+ + H2O",
+                "MnO4- + 5Fe^2+ + 8H+ -> Mn^2+ + 5Fe^3+ + 4H2O",
+            ),
+        ] {
+            let mut reaction = parse_reaction(reaction_str).unwrap();
+            reaction.balance().unwrap();
+            assert_eq!(reaction.to_string(), expected);
+            assert!(reaction.is_balanced());
+        }
+    }
+
+    #[test]
+    fn test_balance_errors() {
+        // N2 does not take part in the reaction
+        let mut reaction = parse_reaction("H2 + O2 + N2 -> H2O").unwrap();
+        assert!(matches!(
+            reaction.balance(),
+            Err(FormulaError::Unbalanceable)
+        ));
+
+        // Both sides are the same species
+        let mut reaction = parse_reaction("H2O -> H2O + H2").unwrap();
+        assert!(matches!(
+            reaction.balance(),
+            Err(FormulaError::Unbalanceable)
+        ));
+
+        let mut reaction = parse_reaction("C + O2 -> CO + CO2").unwrap();
+        assert!(matches!(
+            reaction.balance(),
+            Err(FormulaError::MultipleBalances(2))
+        ));
+
+        // Decimal stoichiometries are balanced exactly, without overflowing the fractions
+        let oxides = "Al0.123457O0.234567 + Si0.345679O0.456791 + Mg0.567913O0.678912 \
+                      + Ca0.791357O0.891357 + Na0.135791O0.246813 + K0.246813O0.357913";
+        let product = "Al0.123457Si0.345679Mg0.567913Ca0.791357Na0.135791K0.246813";
+
+        let mut reaction = parse_reaction(&format!("{} -> {}O2.866353", oxides, product)).unwrap();
+        reaction.balance().unwrap();
+        assert!(reaction
+            .reactants
+            .iter()
+            .all(|species| species.coefficient == 1.0));
+
+        let mut reaction = parse_reaction(&format!("{} -> {}O3.109123", oxides, product)).unwrap();
+        assert!(matches!(
+            reaction.balance(),
+            Err(FormulaError::Unbalanceable)
+        ));
+
+        let mut reaction =
+            parse_reaction(&format!("{} + O2 -> {}O3.109123", oxides, product)).unwrap();
+        reaction.balance().unwrap();
+        assert_eq!(reaction.reactants[6].coefficient, 24277.);
+        assert_eq!(reaction.products[0].coefficient, 200000.);
+
+        // The coefficients of seven coprime denominators do not fit in i128
+        let mut reaction = parse_reaction(
+            "Fe0.999983 + Co0.999979 + Ni0.999961 + Cu0.999959 + Zn0.999953 + Mn0.999931 + Cr0.999917 \
+             -> FeCoNiCuZnMnCr",
+        )
+        .unwrap();
+        assert!(matches!(
+            reaction.balance(),
+            Err(FormulaError::CoefficientOverflow)
+        ));
+    }
+
+    #[test]
+    fn test_parse_reaction() {
+        let reaction = parse_reaction("2 H2 + O2 → 2H2O").unwrap();
+        assert_eq!(reaction.reactants.len(), 2);
+        assert_eq!(reaction.reactants[0].coefficient, 2.0);
+        assert_eq!(reaction.reactants[0].notation, "H2");
+        assert_eq!(reaction.products[0].coefficient, 2.0);
+
+        let reaction: Reaction = "NH4+ + OH- <=> NH3 + H2O".parse().unwrap();
+        assert_eq!(reaction.reactants[0].formula.charge, 1);
+        assert_eq!(reaction.reactants[1].formula.charge, -1);
+        assert!(reaction.is_balanced());
+
+        let err = parse_reaction("H2 + O2").unwrap_err();
+        assert_eq!(
+            err.kind,
+            ParseErrorKind::UnexpectedToken {
+                found: None,
+                expected: vec![Expected::ReactionArrow]
+            }
+        );
+
+        let err = parse_reaction("H2 +  -> H2O").unwrap_err();
+        assert!(matches!(err.kind, ParseErrorKind::UnexpectedToken { .. }));
+
+        let err = parse_reaction("2 -> H2O").unwrap_err();
+        assert_eq!(err.span, 1..2);
+    }
+
+    #[test]
+    fn test_calculate_yield() {
+        let mut reaction = parse_reaction("H2PtCl6·6H2O + H2 -> Pt + HCl + H2O").unwrap();
+        reaction.balance().unwrap();
+
+        let precursor_weight = reaction.reactants[0].formula.molecular_weight().unwrap();
+        let result = reaction
+            .calculate_yield(&[Quantity::Mass(1.0), Quantity::Excess])
+            .unwrap();
+
+        assert_eq!(result.limiting, 0);
+        assert_abs_diff_eq!(result.extent, 1.0 / precursor_weight, epsilon = 1e-12);
+        assert_abs_diff_eq!(
+            result.products[0].mass,
+            ElementSymbol::Pt.atomic_weight() / precursor_weight,
+            epsilon = 1e-12
+        );
+        assert_abs_diff_eq!(
+            result.reactants[0].excess_moles.unwrap(),
+            0.0,
+            epsilon = 1e-12
+        );
+        assert_eq!(result.reactants[1].excess_moles, None);
+        assert_abs_diff_eq!(
+            result.reactants[1].consumed_moles,
+            2. * result.extent,
+            epsilon = 1e-12
+        );
+
+        let result = reaction
+            .calculate_yield(&[Quantity::Moles(1.0), Quantity::Moles(1.0)])
+            .unwrap();
+        assert_eq!(result.limiting, 1);
+        assert_abs_diff_eq!(result.reactants[0].excess_moles.unwrap(), 0.5);
+        assert_abs_diff_eq!(
+            result
+                .percent_yield(0, result.products[0].mass / 2.)
+                .unwrap(),
+            50.0
+        );
+        assert_eq!(result.percent_yield(3, 1.0), None);
+
+        assert!(matches!(
+            reaction.calculate_yield(&[Quantity::Excess, Quantity::Excess]),
+            Err(FormulaError::NoLimitingReagent)
+        ));
+        assert!(matches!(
+            reaction.calculate_yield(&[Quantity::Mass(1.0)]),
+            Err(FormulaError::QuantityCount {
+                expected: 2,
+                found: 1
+            })
+        ));
+
+        let reaction = parse_reaction("H2 + O2 -> H2O").unwrap();
+        assert!(matches!(
+            reaction.calculate_yield(&[Quantity::Mass(1.0), Quantity::Excess]),
+            Err(FormulaError::UnbalancedReaction)
+        ));
+    }
+}
@@ -0,0 +1,331 @@
+//! # Composition series
+//!
+//! This module generates a series of compositions for a combinatorial library.
+//!
+//! A series is defined by a template with `{name}` placeholders, e.g. `Pt{x}wt%/SiO2` or `Pt{x}wt%Pd{y}wt%/Al2O3`,
+//! or by two end members mixed by the mole fraction `x` of the second one.
+//! The values of each variable are given as a list or a range, and the series is the grid of all combinations.
+//! Each `SeriesMember` holds the `ChemicalFormula`, its molecular weight and the wt% of each element.
+//!
+//! # Example
+//! ```
+//! use chemical_formula::prelude::*;
+//! use approx::assert_abs_diff_eq;
+//!
+//! let mut series = CompositionSeries::from_template("Pt{x}wt%/SiO2");
+//! series.range("x", 0.5, 2.0, 0.5);
+//!
+//! let members = series.generate().unwrap();
+//! assert_eq!(members.len(), 4);
+//! assert_abs_diff_eq!(members[3].wt_percent[&ElementSymbol::Pt], 2.0, epsilon = 1e-9);
+//! ```
+
+use crate::element::{ChemicalFormula, ElementSymbol, FormulaError};
+use crate::parser::parse_formula;
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone)]
+enum SeriesTemplate {
+    Formula(String),
+    EndMembers(Box<ChemicalFormula>, Box<ChemicalFormula>),
+}
+
+/// Composition of a series for one combination of the variables
+#[derive(Debug, Clone)]
+pub struct SeriesMember {
+    /// Value of each variable
+    pub values: BTreeMap<String, f64>,
+    pub formula: ChemicalFormula,
+    /// `None` if the composition is only given by wt%, which has no formula unit
+    pub molecular_weight: Option<f64>,
+    pub wt_percent: BTreeMap<ElementSymbol, f64>,
+}
+
+/// Generator of a series of compositions
+///
+/// The variables are varied over the grid of all combinations, with the last variable varying fastest.
+///
+/// # Example
+/// ```
+/// use chemical_formula::prelude::*;
+/// use approx::assert_abs_diff_eq;
+///
+/// let mut series = CompositionSeries::from_template("Pt{x}wt%Pd{y}wt%/Al2O3");
+/// series.values("x", vec![1.0, 2.0]).values("y", vec![0.0, 0.5, 1.0]);
+///
+/// let members = series.generate().unwrap();
+/// assert_eq!(members.len(), 6);
+/// assert_eq!(members[1].values["x"], 1.0);
+/// assert_eq!(members[1].values["y"], 0.5);
+/// ```
+#[derive(Debug, Clone)]
+pub struct CompositionSeries {
+    template: SeriesTemplate,
+    variables: Vec<(String, Vec<f64>)>,
+}
+
+impl CompositionSeries {
+    /// Create a series from a formula with `{name}` placeholders
+    pub fn from_template(template: &str) -> Self {
+        CompositionSeries {
+            template: SeriesTemplate::Formula(template.to_string()),
+            variables: Vec::new(),
+        }
+    }
+
+    /// Create a series between two end members
+    ///
+    /// The variable `x` is the mole fraction of `end`, so each member is `(1 - x) start + x end`
+    /// per formula unit. `generate` returns `FormulaError::MoleFractionOutOfRange` for `x` outside 0 - 1.
+    ///
+    /// # Example
+    /// ```
+    /// use chemical_formula::prelude::*;
+    /// use approx::assert_abs_diff_eq;
+    ///
+    /// let mut series = CompositionSeries::from_end_members(
+    ///     parse_formula("LaMnO3").unwrap(),
+    ///     parse_formula("SrMnO3").unwrap(),
+    /// );
+    /// series.range("x", 0.0, 1.0, 0.25);
+    ///
+    /// let members = series.generate().unwrap();
+    /// assert_eq!(members.len(), 5);
+    /// assert_abs_diff_eq!(members[1].formula.stoichiometry[&ElementSymbol::Sr], 0.25);
+    /// assert_abs_diff_eq!(members[1].formula.stoichiometry[&ElementSymbol::Mn], 1.0);
+    /// ```
+    pub fn from_end_members(start: ChemicalFormula, end: ChemicalFormula) -> Self {
+        CompositionSeries {
+            template: SeriesTemplate::EndMembers(Box::new(start), Box::new(end)),
+            variables: Vec::new(),
+        }
+    }
+
+    /// Set the values of a variable
+    pub fn values(&mut self, variable: &str, values: Vec<f64>) -> &mut Self {
+        match self.variables.iter_mut().find(|(name, _)| name == variable) {
+            Some((_, old_values)) => *old_values = values,
+            None => self.variables.push((variable.to_string(), values)),
+        }
+        self
+    }
+
+    /// Set the values of a variable from `start` to `end` in steps of `step`
+    ///
+    /// `end` is included if it is reached within rounding error.
+    pub fn range(&mut self, variable: &str, start: f64, end: f64, step: f64) -> &mut Self {
+        let mut values = Vec::new();
+
+        if step > 0. && end >= start {
+            let count = ((end - start) / step + 1e-9).floor() as usize;
+            values.extend((0..=count).map(|i| start + i as f64 * step));
+        }
+
+        self.values(variable, values)
+    }
+
+    /// Generate the series
+    ///
+    /// Returns `FormulaError::UnresolvedVariable` if a placeholder has no values,
+    /// `FormulaError::UnknownVariable` for values of a variable that is not in the template,
+    /// and `FormulaError::ParseError` if the template does not give a valid formula.
+    pub fn generate(&self) -> Result<Vec<SeriesMember>, FormulaError> {
+        let used: Vec<&str> = match &self.template {
+            SeriesTemplate::Formula(template) => placeholders(template),
+            SeriesTemplate::EndMembers(..) => vec!["x"],
+        };
+
+        if let Some(variable) = used
+            .iter()
+            .find(|placeholder| !self.variables.iter().any(|(name, _)| name == *placeholder))
+        {
+            return Err(FormulaError::UnresolvedVariable(variable.to_string()));
+        }
+        if let Some((name, _)) = self
+            .variables
+            .iter()
+            .find(|(name, _)| !used.contains(&name.as_str()))
+        {
+            return Err(FormulaError::UnknownVariable(name.clone()));
+        }
+        if let SeriesTemplate::EndMembers(..) = &self.template {
+            if let Some(x) = self
+                .variables
+                .iter()
+                .flat_map(|(_, values)| values.iter())
+                .find(|x| !(-1e-9..=1. + 1e-9).contains(*x))
+            {
+                return Err(FormulaError::MoleFractionOutOfRange(*x));
+            }
+        }
+
+        let mut grid = vec![BTreeMap::new()];
+        for (name, values) in self.variables.iter() {
+            grid = grid
+                .into_iter()
+                .flat_map(|point| {
+                    values.iter().map(move |value| {
+                        let mut point = point.clone();
+                        point.insert(name.clone(), *value);
+                        point
+                    })
+                })
+                .collect();
+        }
+
+        grid.into_iter().map(|values| self.member(values)).collect()
+    }
+
+    fn member(&self, values: BTreeMap<String, f64>) -> Result<SeriesMember, FormulaError> {
+        let formula = match &self.template {
+            SeriesTemplate::Formula(template) => {
+                let mut formula_str = template.clone();
+                for (name, value) in values.iter() {
+                    formula_str = formula_str.replace(&format!("{{{}}}", name), &value.to_string());
+                }
+                parse_formula(&formula_str)
+                    .map_err(|err| FormulaError::ParseError(Box::new(err)))?
+            }
+            SeriesTemplate::EndMembers(start, end) => {
+                let x = values["x"].clamp(0., 1.);
+                let charge = (1. - x) * start.charge as f64 + x * end.charge as f64;
+                if (charge - charge.round()).abs() > 1e-9 {
+                    return Err(FormulaError::NonIntegerCharge(charge));
+                }
+
+                let mut formula = start.as_ref().clone();
+                formula
+                    .multiply(1. - x)
+                    .add_formula(end.clone().multiply(x));
+                formula.charge = charge.round() as i32;
+                formula
+            }
+        };
+
+        let molecular_weight = if formula.stoichiometry.is_empty() && formula.at_percent.is_empty()
+        {
+            None
+        } else {
+            Some(formula.molecular_weight()?)
+        };
+        let wt_percent = formula.to_wt_percent()?.wt_percent;
+
+        Ok(SeriesMember {
+            values,
+            formula,
+            molecular_weight,
+            wt_percent,
+        })
+    }
+}
+
+/// Names of the `{name}` placeholders in a template
+///
+/// A placeholder starts with a lowercase letter, so it is not confused with a `{...}` group.
+fn placeholders(template: &str) -> Vec<&str> {
+    template
+        .split('{')
+        .skip(1)
+        .filter_map(|s| s.split_once('}').map(|(name, _)| name))
+        .filter(|name| {
+            name.starts_with(|c: char| c.is_ascii_lowercase())
+                && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_abs_diff_eq;
+
+    #[test]
+    fn test_template_series() {
+        let mut series = CompositionSeries::from_template("(Pt{x}wt%/SiO2)");
+        series.range("x", 0.0, 1.0, 0.1);
+
+        let members = series.generate().unwrap();
+        assert_eq!(members.len(), 11);
+
+        for member in members.iter() {
+            assert_abs_diff_eq!(
+                member.wt_percent[&ElementSymbol::Pt],
+                member.values["x"],
+                epsilon = 1e-9
+            );
+            assert!(member.molecular_weight.unwrap() >= 60.083);
+        }
+
+        // A decimal value is not read as an adduct
+        let mut series = CompositionSeries::from_template("Sr2Fe{x}MoO6");
+        series.values("x", vec![1.5]);
+        let members = series.generate().unwrap();
+        assert_abs_diff_eq!(members[0].formula.stoichiometry[&ElementSymbol::Fe], 1.5);
+        assert_abs_diff_eq!(members[0].formula.stoichiometry[&ElementSymbol::O], 6.0);
+
+        // The number of water of a hydrate
+        let mut series = CompositionSeries::from_template("CuSO4.{x}H2O");
+        series.values("x", vec![0.5, 1.0, 5.0]);
+        let members = series.generate().unwrap();
+        for (member, water) in members.iter().zip([0.5, 1.0, 5.0]) {
+            assert_abs_diff_eq!(member.formula.stoichiometry[&ElementSymbol::H], 2. * water);
+            assert_abs_diff_eq!(member.formula.stoichiometry[&ElementSymbol::O], 4. + water);
+        }
+
+        // A `{...}` group is not a placeholder
+        let mut series = CompositionSeries::from_template("{Pt{x}wt%/SiO2}");
+        series.values("x", vec![5.0]);
+        assert_eq!(series.generate().unwrap().len(), 1);
+
+        let mut series = CompositionSeries::from_template("(Pt{x}wt%/SiO2)50wt%(CeO2)50wt%");
+        series.values("x", vec![5.0]);
+        let members = series.generate().unwrap();
+        assert!(members[0].molecular_weight.is_none());
+        assert_abs_diff_eq!(
+            members[0].wt_percent[&ElementSymbol::Pt],
+            2.5,
+            epsilon = 1e-9
+        );
+    }
+
+    #[test]
+    fn test_series_errors() {
+        let series = CompositionSeries::from_template("Pt{x}wt%/SiO2");
+        assert!(matches!(
+            series.generate(),
+            Err(FormulaError::UnresolvedVariable(variable)) if variable == "x"
+        ));
+
+        let mut series = CompositionSeries::from_template("Pt{x}wt%/SiO2");
+        series.values("x", vec![150.0]);
+        assert!(series.generate().is_err());
+
+        let mut series = CompositionSeries::from_template("Pt{x}wt%/SiO2");
+        series.values("x", vec![]);
+        assert!(series.generate().unwrap().is_empty());
+
+        let mut series = CompositionSeries::from_template("Pt{x}wt%/SiO2");
+        series.values("x", vec![1.0]).values("y", vec![1.0, 2.0]);
+        assert!(matches!(
+            series.generate(),
+            Err(FormulaError::UnknownVariable(variable)) if variable == "y"
+        ));
+
+        let start = parse_formula("LaMnO3").unwrap();
+        let end = parse_formula("SrMnO3").unwrap();
+
+        let mut series = CompositionSeries::from_end_members(start.clone(), end.clone());
+        series.values("x", vec![0.5]).values("y", vec![0.1, 0.2]);
+        assert!(matches!(
+            series.generate(),
+            Err(FormulaError::UnknownVariable(variable)) if variable == "y"
+        ));
+
+        let mut series = CompositionSeries::from_end_members(start, end);
+        series.values("x", vec![0.5, 1.5]);
+        assert!(matches!(
+            series.generate(),
+            Err(FormulaError::MoleFractionOutOfRange(x)) if x == 1.5
+        ));
+    }
+}
@@ -0,0 +1,286 @@
+//! # Symbolic formulas
+//!
+//! This module contains `SymbolicFormula`, a formula whose stoichiometry has variables.
+//!
+//! Solid solutions and non-stoichiometric oxides are written with a variable composition,
+//! e.g. `Li1-xCoO2`, `La1-xSrxMnO3`, `CeO2-δ` or `Ba(Zr1-yYy)O3-δ`.
+//! `parse_symbolic_formula` keeps such a stoichiometry as a `LinearExpression` in `x`, `y`, `z` and `δ`,
+//! and `SymbolicFormula::evaluate` gives the `ChemicalFormula` for given values of the variables.
+//!
+//! # Example
+//! ```
+//! use chemical_formula::prelude::*;
+//! use std::collections::HashMap;
+//!
+//! let formula = parse_symbolic_formula("La1-xSrxMnO3").unwrap();
+//!
+//! for x in [0.0, 0.1, 0.2] {
+//!     let evaluated = formula.evaluate(&HashMap::from([("x", x)])).unwrap();
+//!
+//!     assert_eq!(evaluated.stoichiometry[&ElementSymbol::Sr], x);
+//!     assert!(evaluated.molecular_weight().unwrap() > 200.);
+//! }
+//! ```
+
+use crate::element::{ChemicalFormula, FormulaError};
+use crate::tree::{Amount, FormulaNode, FormulaTree};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt;
+
+/// Linear expression in named variables, e.g. `1-x` or `0.5+2y`
+///
+/// # Example
+/// ```
+/// use chemical_formula::symbolic::LinearExpression;
+/// use std::collections::HashMap;
+///
+/// let mut expression = LinearExpression::new(1.0);
+/// expression.add_term("x", -1.0);
+///
+/// assert_eq!(expression.to_string(), "1-x");
+/// assert_eq!(expression.evaluate(&HashMap::from([("x", 0.25)])).unwrap(), 0.75);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LinearExpression {
+    pub constant: f64,
+    /// Coefficient of each variable
+    pub coefficients: BTreeMap<String, f64>,
+}
+
+impl LinearExpression {
+    /// Create an expression with a constant and no variable
+    pub fn new(constant: f64) -> Self {
+        LinearExpression {
+            constant,
+            coefficients: BTreeMap::new(),
+        }
+    }
+
+    /// Add `coefficient * variable` to the expression
+    pub fn add_term(&mut self, variable: &str, coefficient: f64) -> &mut Self {
+        *self.coefficients.entry(variable.to_string()).or_insert(0.0) += coefficient;
+        self
+    }
+
+    /// Iterate over the variables in alphabetical order
+    pub fn variables(&self) -> impl Iterator<Item = &str> + '_ {
+        self.coefficients.keys().map(|variable| variable.as_str())
+    }
+
+    /// Evaluate the expression
+    ///
+    /// Returns `FormulaError::UnresolvedVariable` if a variable has no value.
+    pub fn evaluate(&self, values: &HashMap<&str, f64>) -> Result<f64, FormulaError> {
+        self.coefficients.iter().try_fold(
+            self.constant,
+            |acc, (variable, coefficient)| match values.get(variable.as_str()) {
+                Some(value) => Ok(acc + coefficient * value),
+                None => Err(FormulaError::UnresolvedVariable(variable.clone())),
+            },
+        )
+    }
+}
+
+impl fmt::Display for LinearExpression {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut first = true;
+
+        if self.constant != 0. || self.coefficients.is_empty() {
+            write!(f, "{}", self.constant)?;
+            first = false;
+        }
+
+        for (variable, coefficient) in self.coefficients.iter() {
+            let sign = if *coefficient < 0. {
+                "-"
+            } else if first {
+                ""
+            } else {
+                "+"
+            };
+
+            if coefficient.abs() == 1. {
+                write!(f, "{}{}", sign, variable)?;
+            } else {
+                write!(f, "{}{}{}", sign, coefficient.abs(), variable)?;
+            }
+            first = false;
+        }
+
+        Ok(())
+    }
+}
+
+/// Formula with a stoichiometry in variables, returned by `parse_symbolic_formula`
+///
+/// # Example
+/// ```
+/// use chemical_formula::prelude::*;
+/// use std::collections::HashMap;
+///
+/// let formula = parse_symbolic_formula("CeO2-δ").unwrap();
+/// assert_eq!(formula.to_string(), "CeO2-δ");
+///
+/// let evaluated = formula.evaluate(&HashMap::from([("δ", 0.25)])).unwrap();
+/// assert_eq!(evaluated.stoichiometry[&ElementSymbol::O], 1.75);
+///
+/// assert!(formula.evaluate(&HashMap::new()).is_err());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SymbolicFormula {
+    pub tree: FormulaTree,
+}
+
+impl SymbolicFormula {
+    pub fn new(tree: FormulaTree) -> Self {
+        SymbolicFormula { tree }
+    }
+
+    /// Variables of the formula in alphabetical order
+    pub fn variables(&self) -> BTreeSet<&str> {
+        let mut variables = BTreeSet::new();
+        collect_variables(&self.tree, &mut variables);
+        variables
+    }
+
+    /// Substitute the variables and return the tree with numeric stoichiometry
+    pub fn substitute(&self, values: &HashMap<&str, f64>) -> Result<FormulaTree, FormulaError> {
+        let mut tree = self.tree.clone();
+        substitute_variables(&mut tree, values)?;
+        Ok(tree)
+    }
+
+    /// Evaluate the formula for the given values of the variables
+    ///
+    /// Returns `FormulaError::UnresolvedVariable` if a variable has no value.
+    pub fn evaluate(&self, values: &HashMap<&str, f64>) -> Result<ChemicalFormula, FormulaError> {
+        self.substitute(values)?.flatten()
+    }
+}
+
+impl fmt::Display for SymbolicFormula {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.tree)
+    }
+}
+
+fn collect_variables<'a>(tree: &'a FormulaTree, variables: &mut BTreeSet<&'a str>) {
+    for node in tree.nodes.iter() {
+        let (amount, children) = match node {
+            FormulaNode::Element { amount, .. } => (amount, None),
+            FormulaNode::Group {
+                amount, children, ..
+            } => (amount, Some(children)),
+            FormulaNode::Adduct {
+                coefficient,
+                children,
+                ..
+            } => (coefficient, Some(children)),
+            FormulaNode::Separator { .. } => continue,
+        };
+
+        if let Amount::Symbolic(expression) = amount {
+            variables.extend(expression.variables());
+        }
+        if let Some(children) = children {
+            collect_variables(children, variables);
+        }
+    }
+}
+
+fn substitute_variables(
+    tree: &mut FormulaTree,
+    values: &HashMap<&str, f64>,
+) -> Result<(), FormulaError> {
+    for node in tree.nodes.iter_mut() {
+        let (amount, children) = match node {
+            FormulaNode::Element { amount, .. } => (amount, None),
+            FormulaNode::Group {
+                amount, children, ..
+            } => (amount, Some(children)),
+            FormulaNode::Adduct {
+                coefficient,
+                children,
+                ..
+            } => (coefficient, Some(children)),
+            FormulaNode::Separator { .. } => continue,
+        };
+
+        if let Amount::Symbolic(expression) = amount {
+            *amount = Amount::Stoichiometry(expression.evaluate(values)?);
+        }
+        if let Some(children) = children {
+            substitute_variables(children, values)?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::element::ElementSymbol;
+    use crate::parser::{parse_formula, parse_symbolic_formula};
+    use approx::assert_abs_diff_eq;
+
+    #[test]
+    fn test_symbolic_formula() {
+        for (formula_str, variables) in [
+            ("Li1-xCoO2", vec!["x"]),
+            ("La1-xSrxMnO3", vec!["x"]),
+            ("CeO2-δ", vec!["δ"]),
+            ("Ba(Zr1-yYy)O3-δ", vec!["y", "δ"]),
+            ("Li1+xMn2-xO4", vec!["x"]),
+            ("CuSO4·xH2O", vec!["x"]),
+        ] {
+            let formula = parse_symbolic_formula(formula_str).unwrap();
+            assert_eq!(formula.to_string(), formula_str);
+            assert_eq!(
+                formula.variables().into_iter().collect::<Vec<_>>(),
+                variables
+            );
+        }
+
+        let formula = parse_symbolic_formula("La1-xSrxMnO3").unwrap();
+        let evaluated = formula.evaluate(&HashMap::from([("x", 0.3)])).unwrap();
+        assert_abs_diff_eq!(
+            evaluated.molecular_weight().unwrap(),
+            parse_formula("La0.7Sr0.3MnO3")
+                .unwrap()
+                .molecular_weight()
+                .unwrap(),
+            epsilon = 1e-9
+        );
+
+        let formula = parse_symbolic_formula("Ba(Zr1-2yY2y)O3-δ").unwrap();
+        let evaluated = formula
+            .evaluate(&HashMap::from([("y", 0.1), ("δ", 0.1)]))
+            .unwrap();
+        assert_abs_diff_eq!(evaluated.stoichiometry[&ElementSymbol::Zr], 0.8);
+        assert_abs_diff_eq!(evaluated.stoichiometry[&ElementSymbol::Y], 0.2);
+        assert_abs_diff_eq!(evaluated.stoichiometry[&ElementSymbol::O], 2.9);
+
+        let formula = parse_symbolic_formula("CuSO4·xH2O").unwrap();
+        let evaluated = formula.evaluate(&HashMap::from([("x", 5.0)])).unwrap();
+        assert_abs_diff_eq!(evaluated.stoichiometry[&ElementSymbol::H], 10.0);
+
+        assert!(matches!(
+            formula.evaluate(&HashMap::from([("y", 5.0)])),
+            Err(FormulaError::UnresolvedVariable(variable)) if variable == "x"
+        ));
+    }
+
+    #[test]
+    fn test_numeric_formula_is_not_symbolic() {
+        // A sign without a variable is still a charge
+        for formula_str in ["Fe3+", "O2-", "SO4^2-", "Fe+3"] {
+            let formula = parse_symbolic_formula(formula_str).unwrap();
+            assert!(formula.variables().is_empty());
+            let evaluated = formula.evaluate(&HashMap::new()).unwrap();
+            let expected = parse_formula(formula_str).unwrap();
+            assert_eq!(evaluated.stoichiometry, expected.stoichiometry);
+            assert_eq!(evaluated.charge, expected.charge);
+        }
+    }
+}
@@ -0,0 +1,591 @@
+//! # Formula tree
+//!
+//! This module contains the `FormulaTree`, the structure of a formula as it was written.
+//!
+//! `parse_formula` flattens `(Pt5wt%/SiO2)50wt%(CeO2)50wt%` into a single composition.
+//! `parse_formula_tree` instead keeps the groups, the separators (`/`, `@`, ...) and the
+//! stoichiometry or wt% attached to each node, so that the hierarchy of a composite material can be queried.
+//! `FormulaTree::flatten()` gives the same `ChemicalFormula` as `parse_formula`.
+//!
+//! # Example
+//! ```
+//! use chemical_formula::prelude::*;
+//! use chemical_formula::tree::{Amount, FormulaNode};
+//!
+//! let tree = parse_formula_tree("(Pt5wt%/SiO2)50wt%(CeO2)50wt%").unwrap();
+//!
+//! assert_eq!(tree.nodes.len(), 2);
+//! assert_eq!(tree.nodes[0].amount(), Some(Amount::WeightPercent(50.0)));
+//!
+//! if let FormulaNode::Group { children, .. } = &tree.nodes[0] {
+//!     let components = children.components();
+//!     assert_eq!(components.len(), 2);
+//!     assert_eq!(components[0].to_string(), "Pt5wt%");
+//!     assert_eq!(components[1].to_string(), "SiO2");
+//! }
+//!
+//! let formula = tree.flatten().unwrap();
+//! assert!(formula.stoichiometry.is_empty());
+//! assert_eq!(tree.to_string(), "(Pt5wt%/SiO2)50wt%(CeO2)50wt%");
+//! ```
+
+use crate::element::{ChemicalFormula, ElementSymbol, FormulaError};
+use crate::isotope::Isotope;
+use crate::symbolic::LinearExpression;
+use crate::writer::format_charge;
+use std::fmt;
+use std::ops::Range;
+
+/// Stoichiometry, wt%, at% or mol% attached to an element or a group
+///
+/// For an element, mol% is the same as at%. For a group, mol% is the percentage of formula units.
+/// A `MassFraction` keeps the unit as written, e.g. `500ppm`, and is converted to wt% when flattening.
+/// A `Symbolic` stoichiometry such as `1-x` has to be evaluated with `SymbolicFormula` before flattening.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Amount {
+    /// No number was written, which means a stoichiometry of 1
+    Implicit,
+    Stoichiometry(f64),
+    WeightPercent(f64),
+    MassFraction {
+        value: f64,
+        unit: MassFractionUnit,
+    },
+    AtomicPercent(f64),
+    MolPercent(f64),
+    Symbolic(LinearExpression),
+}
+
+impl Amount {
+    /// wt% of a `WeightPercent` or `MassFraction` amount
+    ///
+    /// # Example
+    /// ```
+    /// use chemical_formula::tree::{Amount, MassFractionUnit};
+    ///
+    /// let amount = Amount::MassFraction {
+    ///     value: 500.,
+    ///     unit: MassFractionUnit::Ppm,
+    /// };
+    /// assert_eq!(amount.wt_percent(), Some(0.05));
+    /// assert_eq!(Amount::Stoichiometry(2.).wt_percent(), None);
+    /// ```
+    pub fn wt_percent(&self) -> Option<f64> {
+        match self {
+            Amount::WeightPercent(wt_ratio) => Some(*wt_ratio),
+            Amount::MassFraction { value, unit } => Some(unit.to_wt_percent(*value)),
+            _ => None,
+        }
+    }
+}
+
+/// Unit of a mass fraction other than wt%
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MassFractionUnit {
+    /// `ppm`, 10⁻⁶
+    Ppm,
+    /// `ppb`, 10⁻⁹
+    Ppb,
+    /// `mg/g`, 10⁻³
+    MgPerG,
+    /// `wt`, a weight fraction from 0 to 1
+    WeightFraction,
+}
+
+impl MassFractionUnit {
+    /// Convert a value in this unit to wt%
+    pub fn to_wt_percent(&self, value: f64) -> f64 {
+        match self {
+            MassFractionUnit::Ppm => value / 1e4,
+            MassFractionUnit::Ppb => value / 1e7,
+            MassFractionUnit::MgPerG => value / 10.,
+            MassFractionUnit::WeightFraction => value * 100.,
+        }
+    }
+}
+
+impl fmt::Display for MassFractionUnit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MassFractionUnit::Ppm => write!(f, "ppm"),
+            MassFractionUnit::Ppb => write!(f, "ppb"),
+            MassFractionUnit::MgPerG => write!(f, "mg/g"),
+            MassFractionUnit::WeightFraction => write!(f, "wt"),
+        }
+    }
+}
+
+impl fmt::Display for Amount {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Amount::Implicit => Ok(()),
+            Amount::Stoichiometry(x) => write!(f, "{}", x),
+            Amount::WeightPercent(x) => write!(f, "{}wt%", x),
+            Amount::MassFraction { value, unit } => write!(f, "{}{}", value, unit),
+            Amount::AtomicPercent(x) => write!(f, "{}at%", x),
+            Amount::MolPercent(x) => write!(f, "{}mol%", x),
+            Amount::Symbolic(expression) => write!(f, "{}", expression),
+        }
+    }
+}
+
+/// Kind of bracket enclosing a group
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Bracket {
+    Round,
+    Square,
+    Curly,
+}
+
+impl Bracket {
+    /// Opening and closing characters of the bracket
+    pub fn chars(&self) -> (char, char) {
+        match self {
+            Bracket::Round => ('(', ')'),
+            Bracket::Square => ('[', ']'),
+            Bracket::Curly => ('{', '}'),
+        }
+    }
+}
+
+/// Separator between nodes
+///
+/// `Slash` and `At` separate the components of a supported material, e.g. `Pt/SiO2` or `Pt@CeO2`.
+/// `Dot`, `MiddleDot` (`·`, `•` or `∙`) and `Asterisk` also start the adduct part of a hydrate, e.g. `CuSO4·5H2O`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Separator {
+    Slash,
+    At,
+    Dot,
+    MiddleDot,
+    Asterisk,
+    Whitespace,
+}
+
+impl Separator {
+    /// Whether the separator splits the formula into components
+    pub fn is_component_separator(&self) -> bool {
+        matches!(self, Separator::Slash | Separator::At)
+    }
+}
+
+impl fmt::Display for Separator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Separator::Slash => write!(f, "/"),
+            Separator::At => write!(f, "@"),
+            Separator::Dot => write!(f, "."),
+            Separator::MiddleDot => write!(f, "·"),
+            Separator::Asterisk => write!(f, "*"),
+            Separator::Whitespace => write!(f, " "),
+        }
+    }
+}
+
+/// Node of a `FormulaTree`
+///
+/// `span` is the byte range of the node in the parsed string.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FormulaNode {
+    /// `mass_number` is set for an isotope, e.g. 13 for `13C`
+    Element {
+        symbol: ElementSymbol,
+        mass_number: Option<u16>,
+        amount: Amount,
+        span: Range<usize>,
+    },
+    Group {
+        bracket: Bracket,
+        children: FormulaTree,
+        amount: Amount,
+        span: Range<usize>,
+    },
+    Separator {
+        separator: Separator,
+        span: Range<usize>,
+    },
+    /// Adduct part of a hydrate or an addition compound, e.g. `·5H2O` in `CuSO4·5H2O`
+    ///
+    /// `coefficient` is the leading coefficient and multiplies all of `children`.
+    Adduct {
+        separator: Separator,
+        coefficient: Amount,
+        children: FormulaTree,
+        span: Range<usize>,
+    },
+}
+
+impl FormulaNode {
+    /// Stoichiometry or wt% of the node, `None` for separators
+    ///
+    /// For an adduct, this is the leading coefficient.
+    pub fn amount(&self) -> Option<Amount> {
+        match self {
+            FormulaNode::Element { amount, .. } | FormulaNode::Group { amount, .. } => {
+                Some(amount.clone())
+            }
+            FormulaNode::Adduct { coefficient, .. } => Some(coefficient.clone()),
+            FormulaNode::Separator { .. } => None,
+        }
+    }
+
+    /// Byte range of the node in the parsed string
+    pub fn span(&self) -> Range<usize> {
+        match self {
+            FormulaNode::Element { span, .. }
+            | FormulaNode::Group { span, .. }
+            | FormulaNode::Separator { span, .. }
+            | FormulaNode::Adduct { span, .. } => span.clone(),
+        }
+    }
+
+    /// Flatten the node into a `ChemicalFormula`
+    ///
+    /// # Example
+    /// ```
+    /// use chemical_formula::prelude::*;
+    ///
+    /// let tree = parse_formula_tree("(SiO2)2").unwrap();
+    /// let formula = tree.nodes[0].flatten().unwrap();
+    ///
+    /// assert_eq!(formula.stoichiometry[&ElementSymbol::O], 4.0);
+    /// ```
+    pub fn flatten(&self) -> Result<ChemicalFormula, FormulaError> {
+        self.flatten_with_span().map_err(|(err, _)| err)
+    }
+
+    pub(crate) fn flatten_with_span(
+        &self,
+    ) -> Result<ChemicalFormula, (FormulaError, Range<usize>)> {
+        let mut formula = ChemicalFormula::new();
+
+        match self {
+            FormulaNode::Element {
+                symbol,
+                mass_number: None,
+                amount,
+                span,
+            } => match amount {
+                Amount::Implicit => formula.add_element(*symbol, 1.),
+                Amount::Stoichiometry(stoichiometry) => {
+                    formula.add_element(*symbol, *stoichiometry)
+                }
+                Amount::WeightPercent(_) | Amount::MassFraction { .. } => {
+                    formula.add_wt_percent(*symbol, amount.wt_percent().unwrap_or_default())
+                }
+                Amount::AtomicPercent(at_ratio) | Amount::MolPercent(at_ratio) => {
+                    formula.add_at_percent(*symbol, *at_ratio)
+                }
+                Amount::Symbolic(expression) => {
+                    return Err((unresolved_variable(expression), span.clone()))
+                }
+            },
+            FormulaNode::Element {
+                symbol,
+                mass_number: Some(mass_number),
+                amount,
+                span,
+            } => {
+                let isotope = Isotope::new(*symbol, *mass_number);
+                match amount {
+                    Amount::Implicit => formula.add_isotope(isotope, 1.),
+                    Amount::Stoichiometry(stoichiometry) => {
+                        formula.add_isotope(isotope, *stoichiometry)
+                    }
+                    Amount::WeightPercent(_)
+                    | Amount::MassFraction { .. }
+                    | Amount::AtomicPercent(_)
+                    | Amount::MolPercent(_) => {
+                        return Err((FormulaError::IsotopePercent(isotope), span.clone()))
+                    }
+                    Amount::Symbolic(expression) => {
+                        return Err((unresolved_variable(expression), span.clone()))
+                    }
+                }
+            }
+            FormulaNode::Group {
+                children,
+                amount,
+                span,
+                ..
+            } => {
+                formula = children.flatten_with_span()?;
+                apply_amount(&mut formula, amount).map_err(|err| (err, span.clone()))?
+            }
+            FormulaNode::Adduct {
+                coefficient,
+                children,
+                span,
+                ..
+            } => {
+                formula = children.flatten_with_span()?;
+                apply_amount(&mut formula, coefficient).map_err(|err| (err, span.clone()))?
+            }
+            FormulaNode::Separator { .. } => &mut formula,
+        };
+
+        Ok(formula)
+    }
+}
+
+/// Multiply the formula of a group by its amount
+///
+/// A group given by mol% is multiplied by mol% / 100 here, and rescaled by `FormulaTree` against the rest of the formula.
+fn apply_amount<'a>(
+    formula: &'a mut ChemicalFormula,
+    amount: &Amount,
+) -> Result<&'a mut ChemicalFormula, FormulaError> {
+    match amount {
+        Amount::Implicit => Ok(formula),
+        Amount::Stoichiometry(multiplier) => formula.try_multiply(*multiplier),
+        Amount::WeightPercent(_) | Amount::MassFraction { .. } => {
+            formula.multiply_wt_percent(amount.wt_percent().unwrap_or_default())
+        }
+        Amount::AtomicPercent(multiplier) => formula.multiply_at_percent(*multiplier),
+        Amount::MolPercent(mol_ratio) => {
+            *formula = formula.to_molecular_formula()?;
+            formula.try_multiply(mol_ratio / 100.)
+        }
+        Amount::Symbolic(expression) => Err(unresolved_variable(expression)),
+    }
+}
+
+fn unresolved_variable(expression: &LinearExpression) -> FormulaError {
+    let variable = expression.variables().next().unwrap_or_default();
+    FormulaError::UnresolvedVariable(variable.to_string())
+}
+
+impl fmt::Display for FormulaNode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FormulaNode::Element {
+                symbol,
+                mass_number: None,
+                amount,
+                ..
+            } => write!(f, "{}{}", symbol, amount),
+            FormulaNode::Element {
+                symbol,
+                mass_number: Some(mass_number),
+                amount,
+                ..
+            } => write!(f, "{}{}", Isotope::new(*symbol, *mass_number), amount),
+            FormulaNode::Group {
+                bracket,
+                children,
+                amount,
+                ..
+            } => {
+                let (open, close) = bracket.chars();
+                write!(f, "{}{}{}{}", open, children, close, amount)
+            }
+            FormulaNode::Separator { separator, .. } => write!(f, "{}", separator),
+            FormulaNode::Adduct {
+                separator,
+                coefficient,
+                children,
+                ..
+            } => write!(f, "{}{}{}", separator, coefficient, children),
+        }
+    }
+}
+
+/// Chemical formula as it was written, returned by `parse_formula_tree`
+///
+/// `charge` is the net charge written at the end of the formula, e.g. -2 for `SO4^2-`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FormulaTree {
+    pub nodes: Vec<FormulaNode>,
+    pub charge: i32,
+}
+
+impl FormulaTree {
+    /// Split the top level nodes at `/` and `@` separators
+    ///
+    /// For `Pt5wt%/SiO2`, the components are `Pt5wt%` and `SiO2`.
+    ///
+    /// # Example
+    /// ```
+    /// use chemical_formula::prelude::*;
+    ///
+    /// let tree = parse_formula_tree("Pt@CeO2").unwrap();
+    /// let components = tree.components();
+    ///
+    /// assert_eq!(components.len(), 2);
+    /// assert_eq!(components[1].to_string(), "CeO2");
+    /// ```
+    pub fn components(&self) -> Vec<FormulaTree> {
+        self.nodes
+            .split(|node| {
+                matches!(node, FormulaNode::Separator { separator, .. } if separator.is_component_separator())
+            })
+            .map(|nodes| FormulaTree {
+                nodes: nodes.to_vec(),
+                charge: 0,
+            })
+            .collect()
+    }
+
+    /// Flatten the tree into a `ChemicalFormula`
+    ///
+    /// This gives the same result as `parse_formula`.
+    ///
+    /// # Example
+    /// ```
+    /// use chemical_formula::prelude::*;
+    ///
+    /// let formula = parse_formula_tree("Pt5wt%/SiO2").unwrap().flatten().unwrap();
+    ///
+    /// assert_eq!(formula.wt_percent[&ElementSymbol::Pt], 5.0);
+    /// assert_eq!(formula.stoichiometry[&ElementSymbol::O], 2.0);
+    /// ```
+    pub fn flatten(&self) -> Result<ChemicalFormula, FormulaError> {
+        self.flatten_with_span().map_err(|(err, _)| err)
+    }
+
+    pub(crate) fn flatten_with_span(
+        &self,
+    ) -> Result<ChemicalFormula, (FormulaError, Range<usize>)> {
+        let mut formulas = Vec::with_capacity(self.nodes.len());
+        let mut mol_ratio_sum = 0.0;
+        let mut has_stoichiometry = false;
+
+        for node in self.nodes.iter() {
+            let formula = node.flatten_with_span()?;
+
+            match node {
+                FormulaNode::Group {
+                    amount: Amount::MolPercent(mol_ratio),
+                    ..
+                } => mol_ratio_sum += mol_ratio,
+                _ => has_stoichiometry |= !formula.stoichiometry.is_empty(),
+            }
+            formulas.push(formula);
+        }
+
+        // Groups given by mol% are per formula unit of the rest of the formula,
+        // e.g. `(Y2O3)8mol%/ZrO2` has 8/92 Y2O3 per ZrO2
+        let mol_multiplier = if mol_ratio_sum > 100. || (has_stoichiometry && mol_ratio_sum >= 100.)
+        {
+            let span = self.nodes.first().map_or(0, |node| node.span().start)
+                ..self.nodes.last().map_or(0, |node| node.span().end);
+            return Err((FormulaError::MolPercentOverflow, span));
+        } else if has_stoichiometry {
+            100. / (100. - mol_ratio_sum)
+        } else {
+            1.
+        };
+
+        let mut formula = ChemicalFormula::new();
+
+        for (node, mut node_formula) in self.nodes.iter().zip(formulas) {
+            if let FormulaNode::Group {
+                amount: Amount::MolPercent(_),
+                ..
+            } = node
+            {
+                node_formula
+                    .try_multiply(mol_multiplier)
+                    .map_err(|err| (err, node.span()))?;
+            }
+            formula.add_formula(&node_formula);
+        }
+        formula.charge += self.charge;
+
+        Ok(formula)
+    }
+}
+
+impl fmt::Display for FormulaTree {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for node in self.nodes.iter() {
+            write!(f, "{}", node)?;
+        }
+        write!(f, "{}", format_charge(self.charge))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::{parse_formula, parse_formula_tree};
+    use approx::assert_abs_diff_eq;
+
+    #[test]
+    fn test_flatten_nodes() {
+        let tree = parse_formula_tree("(Pt5wt%/SiO2)50wt%(CeO2)50wt%").unwrap();
+
+        let ceo2 = tree.nodes[1].flatten().unwrap();
+        assert_abs_diff_eq!(
+            ceo2.wt_percent[&ElementSymbol::Ce] + ceo2.wt_percent[&ElementSymbol::O],
+            50.0,
+            epsilon = 1e-10
+        );
+
+        let formula = tree.flatten().unwrap();
+        assert_abs_diff_eq!(formula.wt_percent[&ElementSymbol::Pt], 2.5, epsilon = 1e-10);
+
+        let formula = parse_formula_tree("{(SiO2)2}3").unwrap().flatten().unwrap();
+        assert_abs_diff_eq!(formula.stoichiometry[&ElementSymbol::Si], 6.0);
+        assert_abs_diff_eq!(formula.stoichiometry[&ElementSymbol::O], 12.0);
+
+        let tree = parse_formula_tree("(Pt150wt%)50wt%").unwrap();
+        assert!(matches!(
+            tree.flatten(),
+            Err(FormulaError::WeightPercentOverflow)
+        ));
+        assert!(parse_formula("(Pt150wt%)50wt%").is_err());
+    }
+
+    #[test]
+    fn test_tree_structure() {
+        let tree = parse_formula_tree("[Fe(CN)6] Pt@CeO2").unwrap();
+
+        assert_eq!(tree.nodes.len(), 6);
+        assert_eq!(tree.components().len(), 2);
+
+        match &tree.nodes[0] {
+            FormulaNode::Group {
+                bracket,
+                children,
+                amount,
+                span,
+            } => {
+                assert_eq!(*bracket, Bracket::Square);
+                assert_eq!(*amount, Amount::Implicit);
+                assert_eq!(*span, 0..9);
+                assert_eq!(children.to_string(), "Fe(CN)6");
+            }
+            _ => panic!("expected a group"),
+        }
+
+        assert_eq!(
+            tree.nodes[1],
+            FormulaNode::Separator {
+                separator: Separator::Whitespace,
+                span: 9..10
+            }
+        );
+        assert_eq!(tree.to_string(), "[Fe(CN)6] Pt@CeO2");
+    }
+
+    #[test]
+    fn test_adduct_structure() {
+        let tree = parse_formula_tree("CaSO4.0.5H2O").unwrap();
+
+        match tree.nodes.last().unwrap() {
+            FormulaNode::Adduct {
+                separator,
+                coefficient,
+                children,
+                ..
+            } => {
+                assert_eq!(*separator, Separator::Dot);
+                assert_eq!(*coefficient, Amount::Stoichiometry(0.5));
+                assert_eq!(children.to_string(), "H2O");
+            }
+            _ => panic!("expected an adduct"),
+        }
+        assert_eq!(tree.to_string(), "CaSO4.0.5H2O");
+    }
+}
@@ -0,0 +1,317 @@
+//! # Formula writer
+//!
+//! This module converts a `ChemicalFormula` back into a formula string.
+//!
+//! `ChemicalFormula` implements `Display`, which writes the elements in the order they were first added.
+//! The wt% and at% terms are written first and separated from the stoichiometric part by `/`,
+//! so that `Pt5wt%/SiO2` and `Pt2at%/Al2O3` round-trip to themselves.
+//! A net charge is written at the end, e.g. `NH4+` or `SO4^2-`.
+//! Isotopes are written after the unlabelled atoms of the element, e.g. `CH3OD` is written as `CH3DO`
+//! and `[13C]O2` as `^13CO2`.
+//!
+//! `FormulaWriter` can be configured to use Hill order or a user-specified element order,
+//! a fixed numeric precision, and whether the wt% and at% terms are emitted or converted to stoichiometry.
+//!
+//! # Example
+//! ```
+//! use chemical_formula::prelude::*;
+//! use chemical_formula::writer::{ElementOrder, FormulaWriter};
+//!
+//! let formula = parse_formula("Pt5wt%/SiO2").unwrap();
+//! assert_eq!(formula.to_string(), "Pt5wt%/SiO2");
+//!
+//! let formula = parse_formula("OH2C").unwrap();
+//!
+//! let mut writer = FormulaWriter::new();
+//! writer.order(ElementOrder::Hill);
+//! assert_eq!(writer.write(&formula).unwrap(), "CH2O");
+//! ```
+
+use crate::element::{ChemicalFormula, ElementSymbol, FormulaError};
+use std::fmt;
+
+/// Order in which the elements are written
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum ElementOrder {
+    /// The order in which the elements were first added to the formula
+    #[default]
+    Input,
+    /// C first, then H, then the other elements alphabetically.
+    /// Without C, all elements are sorted alphabetically.
+    Hill,
+    /// The given elements first, then the remaining elements in input order
+    Custom(Vec<ElementSymbol>),
+}
+
+impl ElementOrder {
+    fn sort(&self, elements: &mut [ElementSymbol]) {
+        match self {
+            ElementOrder::Input => {}
+            ElementOrder::Hill => {
+                let has_carbon = elements.contains(&ElementSymbol::C);
+                elements.sort_by_key(|element| match element {
+                    ElementSymbol::C if has_carbon => (0, String::new()),
+                    ElementSymbol::H if has_carbon => (1, String::new()),
+                    _ => (2, element.to_string()),
+                });
+            }
+            ElementOrder::Custom(order) => {
+                elements.sort_by_key(|element| {
+                    order
+                        .iter()
+                        .position(|e| e == element)
+                        .unwrap_or(order.len())
+                });
+            }
+        }
+    }
+}
+
+/// Configurable writer of `ChemicalFormula`
+///
+/// The default writer uses input order, writes numbers with the shortest representation and emits wt% and at% terms.
+///
+/// # Example
+/// ```
+/// use chemical_formula::prelude::*;
+/// use chemical_formula::writer::{ElementOrder, FormulaWriter};
+///
+/// let formula = parse_formula("Pt5wt%/SiO2").unwrap();
+///
+/// let mut writer = FormulaWriter::new();
+/// writer
+///     .order(ElementOrder::Custom(vec![ElementSymbol::O]))
+///     .precision(4)
+///     .wt_percent(false);
+///
+/// assert_eq!(writer.write(&formula).unwrap(), "O2Pt0.0162Si");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormulaWriter {
+    pub order: ElementOrder,
+    pub precision: Option<usize>,
+    pub wt_percent: bool,
+}
+
+impl Default for FormulaWriter {
+    fn default() -> Self {
+        FormulaWriter::new()
+    }
+}
+
+impl FormulaWriter {
+    /// Create a writer with input order, shortest number representation and wt% and at% terms
+    pub fn new() -> Self {
+        FormulaWriter {
+            order: ElementOrder::Input,
+            precision: None,
+            wt_percent: true,
+        }
+    }
+
+    /// Set the element order
+    pub fn order(&mut self, order: ElementOrder) -> &mut Self {
+        self.order = order;
+        self
+    }
+
+    /// Set the number of decimal places. Trailing zeros are removed.
+    pub fn precision(&mut self, precision: usize) -> &mut Self {
+        self.precision = Some(precision);
+        self
+    }
+
+    /// Set whether wt% and at% terms are emitted
+    ///
+    /// If `false`, the formula is converted with `to_molecular_formula()` before writing.
+    pub fn wt_percent(&mut self, wt_percent: bool) -> &mut Self {
+        self.wt_percent = wt_percent;
+        self
+    }
+
+    /// Write the formula as a string
+    ///
+    /// # Example
+    /// ```
+    /// use chemical_formula::prelude::*;
+    /// use chemical_formula::writer::FormulaWriter;
+    ///
+    /// let formula = parse_formula("(SiO2)0.5").unwrap();
+    /// let writer = FormulaWriter::new();
+    ///
+    /// assert_eq!(writer.write(&formula).unwrap(), "Si0.5O");
+    /// ```
+    pub fn write(&self, formula: &ChemicalFormula) -> Result<String, FormulaError> {
+        if !self.wt_percent && (!formula.wt_percent.is_empty() || !formula.at_percent.is_empty()) {
+            return Ok(self.write_terms(&formula.to_molecular_formula()?));
+        }
+
+        Ok(self.write_terms(formula))
+    }
+
+    fn write_terms(&self, formula: &ChemicalFormula) -> String {
+        let mut elements = formula.element.clone();
+        for element in formula
+            .stoichiometry
+            .keys()
+            .chain(formula.wt_percent.keys())
+            .chain(formula.at_percent.keys())
+        {
+            if !elements.contains(element) {
+                elements.push(*element);
+            }
+        }
+        self.order.sort(&mut elements);
+
+        let mut wt_percent_terms = String::new();
+        let mut stoichiometry_terms = String::new();
+
+        for element in elements.iter() {
+            if let Some(wt_ratio) = formula.wt_percent.get(element) {
+                wt_percent_terms += &format!("{}{}wt%", element, self.number(*wt_ratio));
+            }
+
+            if let Some(at_ratio) = formula.at_percent.get(element) {
+                wt_percent_terms += &format!("{}{}at%", element, self.number(*at_ratio));
+            }
+
+            if let Some(stoichiometry) = formula.stoichiometry.get(element) {
+                let isotopes: Vec<_> = formula
+                    .isotope
+                    .iter()
+                    .filter(|(isotope, _)| isotope.element == *element)
+                    .collect();
+                let unlabelled = stoichiometry - isotopes.iter().map(|(_, x)| *x).sum::<f64>();
+
+                if isotopes.is_empty() || unlabelled.abs() > 1e-9 * stoichiometry.abs() {
+                    stoichiometry_terms += &self.term(element, unlabelled);
+                }
+                for (isotope, stoichiometry) in isotopes {
+                    stoichiometry_terms += &self.term(isotope, *stoichiometry);
+                }
+            }
+        }
+
+        let terms = if wt_percent_terms.is_empty() || stoichiometry_terms.is_empty() {
+            wt_percent_terms + &stoichiometry_terms
+        } else {
+            format!("{}/{}", wt_percent_terms, stoichiometry_terms)
+        };
+
+        terms + &format_charge(formula.charge)
+    }
+
+    /// Symbol followed by the stoichiometry, which is omitted if it is 1
+    fn term(&self, symbol: &impl fmt::Display, stoichiometry: f64) -> String {
+        let stoichiometry = self.number(stoichiometry);
+        if stoichiometry == "1" {
+            symbol.to_string()
+        } else {
+            format!("{}{}", symbol, stoichiometry)
+        }
+    }
+
+    fn number(&self, x: f64) -> String {
+        match self.precision {
+            None => x.to_string(),
+            Some(precision) => {
+                let s = format!("{:.*}", precision, x);
+                if s.contains('.') {
+                    s.trim_end_matches('0').trim_end_matches('.').to_string()
+                } else {
+                    s
+                }
+            }
+        }
+    }
+}
+
+/// Format a net charge as `+`, `-`, `^2+`, `^3-`, ...
+pub(crate) fn format_charge(charge: i32) -> String {
+    let sign = if charge > 0 { "+" } else { "-" };
+
+    match charge.unsigned_abs() {
+        0 => String::new(),
+        1 => sign.to_string(),
+        n => format!("^{}{}", n, sign),
+    }
+}
+
+impl fmt::Display for ChemicalFormula {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let writer = FormulaWriter::new();
+
+        write!(f, "{}", writer.write_terms(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_formula;
+
+    #[test]
+    fn test_round_trip() {
+        for formula_str in [
+            "Pt5wt%/SiO2",
+            "H2O",
+            "Si0.5O",
+            "Pt1.5wt%Au2wt%/Al2O3",
+            "NH4+",
+            "SO4^2-",
+            "D2O",
+            "^13CO2",
+            "CH3D",
+            "Pt2at%/Al2O3",
+            "Pt1wt%Sn1at%/Al2O3",
+        ] {
+            let formula = parse_formula(formula_str).unwrap();
+            assert_eq!(formula.to_string(), formula_str);
+        }
+    }
+
+    #[test]
+    fn test_element_order() {
+        let formula = parse_formula("OH2C2Br").unwrap();
+        let mut writer = FormulaWriter::new();
+
+        assert_eq!(writer.write(&formula).unwrap(), "OH2C2Br");
+
+        writer.order(ElementOrder::Hill);
+        assert_eq!(writer.write(&formula).unwrap(), "C2H2BrO");
+
+        let formula = parse_formula("OH2Br").unwrap();
+        assert_eq!(writer.write(&formula).unwrap(), "BrH2O");
+
+        writer.order(ElementOrder::Custom(vec![
+            ElementSymbol::Br,
+            ElementSymbol::O,
+        ]));
+        assert_eq!(writer.write(&formula).unwrap(), "BrOH2");
+    }
+
+    #[test]
+    fn test_precision_and_wt_percent() {
+        let formula = parse_formula("Pt5wt%/SiO2").unwrap();
+        assert_eq!(FormulaWriter::default(), FormulaWriter::new());
+        assert_eq!(
+            FormulaWriter::default().write(&formula).unwrap(),
+            "Pt5wt%/SiO2"
+        );
+
+        let mut writer = FormulaWriter::new();
+        writer.precision(3).wt_percent(false);
+
+        assert_eq!(writer.write(&formula).unwrap(), "Pt0.016SiO2");
+
+        writer.precision(0);
+        assert_eq!(writer.write(&formula).unwrap(), "Pt0SiO2");
+
+        let formula = parse_formula("(Pt5wt%/SiO2)50wt%(CeO2)50wt%").unwrap();
+        writer.precision(1).wt_percent(true);
+        assert_eq!(
+            writer.write(&formula).unwrap(),
+            "Pt2.5wt%Si22.2wt%O34.6wt%Ce40.7wt%"
+        );
+    }
+}